@@ -1,11 +1,13 @@
 use iced::{Background, Border, Color, Font, Padding, border::Radius, widget::markdown};
 
-use crate::ui::journal_theme::LIGHT;
+use crate::ui::journal_theme::preferred_journal_theme;
 
 // TODO: move to to user preferences and make serdeable
 pub fn markdown_settings() -> markdown::Settings {
     let base_font_size = 13.into();
 
+    let journal_theme = preferred_journal_theme();
+
     markdown::Settings {
         text_size: base_font_size,
         h1_size: base_font_size * 2.0,
@@ -19,7 +21,7 @@ pub fn markdown_settings() -> markdown::Settings {
         style: markdown::Style {
             font: Font::DEFAULT,
             inline_code_highlight: markdown::Highlight {
-                background: Background::Color(LIGHT.darkened_background()),
+                background: Background::Color(journal_theme.darkened_background()),
                 border: Border {
                     color: Color::TRANSPARENT,
                     width: 0.0,
@@ -27,10 +29,10 @@ pub fn markdown_settings() -> markdown::Settings {
                 },
             },
             inline_code_padding: Padding::default(),
-            inline_code_color: LIGHT.default_text,
+            inline_code_color: journal_theme.default_text,
             inline_code_font: Font::MONOSPACE,
             code_block_font: Font::MONOSPACE,
-            link_color: LIGHT.link,
+            link_color: journal_theme.link,
         },
     }
 }
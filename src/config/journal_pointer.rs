@@ -27,8 +27,7 @@ impl JournalPointer {
     /// the path to the file that contains the JournalPointer. this is always located at
     /// dirs::config_local_dir()/ironnote/journal_pointer.json
     fn journal_pointer_file() -> PathBuf {
-        let mut journal_path_file =
-            dirs::config_local_dir().expect("couldn't open local config dir");
+        let mut journal_path_file = config_local_dir();
         journal_path_file.push("ironnote");
 
         fs::create_dir_all(&journal_path_file).expect("unable to create config directory");
@@ -77,7 +76,7 @@ impl JournalPointer {
 
 impl Default for JournalPointer {
     fn default() -> Self {
-        let local_data_dir = dirs::data_local_dir().expect("local data dir unavailable");
+        let local_data_dir = data_local_dir();
 
         // default journal location: dirs::data_local_dir()/ironnote
         let mut journal_path = local_data_dir;
@@ -94,3 +93,33 @@ impl Default for JournalPointer {
         }
     }
 }
+
+/// the local config directory of the system
+#[cfg(not(test))]
+fn config_local_dir() -> PathBuf {
+    dirs::config_local_dir().expect("couldn't open local config dir")
+}
+
+/// the local data directory of the system
+#[cfg(not(test))]
+fn data_local_dir() -> PathBuf {
+    dirs::data_local_dir().expect("local data dir unavailable")
+}
+
+/// tests use a directory of their own in place of the system's config directory, so they never touch a real journal
+#[cfg(test)]
+fn config_local_dir() -> PathBuf {
+    test_dir().join("config")
+}
+
+/// tests use a directory of their own in place of the system's data directory, so they never touch a real journal
+#[cfg(test)]
+fn data_local_dir() -> PathBuf {
+    test_dir().join("data")
+}
+
+/// the temporary directory that holds everything the tests write to the disk
+#[cfg(test)]
+pub fn test_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("ironnote-tests-{}", std::process::id()))
+}
@@ -4,7 +4,16 @@ pub mod user_preferences;
 
 // re-exports
 pub use journal_pointer::JournalPointer;
+pub use user_preferences::EditorAlignment;
+pub use user_preferences::JsonFormat;
+pub use user_preferences::SaveNotification;
+pub use user_preferences::SearchEnterAction;
+pub use user_preferences::StorageBackend;
+pub use user_preferences::ThemePreference;
 pub use user_preferences::UserPreferences;
 pub use user_preferences::overwrite_preferences;
 pub use user_preferences::preferences;
 pub use user_preferences::preferences_mut;
+pub use user_preferences::take_first_run;
+#[cfg(test)]
+pub use user_preferences::with_test_preferences;
@@ -1,12 +1,18 @@
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fs, io,
     path::PathBuf,
-    sync::{LazyLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    sync::{
+        LazyLock, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        atomic::{AtomicBool, Ordering},
+    },
     time::Duration,
 };
+use strum::Display;
 
 use super::JournalPointer;
+use crate::keyboard_manager::BindableAction;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -18,9 +24,76 @@ pub struct GeneralPreferences {
     /// How often the autosave would occour if autosaving is enabled
     pub(crate) autosave_interval: Duration,
 
+    /// If true, the editor autosaves shortly after a sentence is finished, once typing has paused. This works
+    /// independently of the interval autosave
+    pub(crate) save_after_sentence: bool,
+
+    /// If true, the journal's savedata is copied into a timestamped folder under the journal's backups directory at
+    /// the backup_interval
+    pub(crate) backups_enabled: bool,
+
+    /// How often a backup is made if backups are enabled
+    pub(crate) backup_interval: Duration,
+
+    /// How many of the most recent backups are kept. Older backups are deleted once a new backup is made
+    pub(crate) backup_retention_count: usize,
+
+    /// Backups older than this many days are deleted once a new backup is made, even if they are within the retention
+    /// count. If 0, backups are only deleted by the retention count
+    pub(crate) backup_retention_days: usize,
+
     /// If enabled and there are empty entries, the forward/backward navigation buttons will skip over empty entries to
     /// the next day that contains an active entry
     pub(crate) smart_navigation: bool,
+
+    /// If true, pressing the Today button while already on today scrolls the entry back to the top. Otherwise the
+    /// button does nothing while today is shown. Either way the entry isn't reloaded, so its undo history is kept
+    pub(crate) today_scrolls_to_top: bool,
+
+    /// If true, the calender only shows as many weeks as the current month needs, rather than always showing 6
+    pub(crate) compact_calender: bool,
+
+    /// If true, each day of the calender shows how many words its entry has under the day number
+    pub(crate) calender_word_counts: bool,
+
+    /// How many of the most recent months are shown in the month strip above the calender. If 0, the strip is hidden
+    pub(crate) month_strip_length: usize,
+
+    /// Navigating more than this many days away from today asks for confirmation first, but only when it would create
+    /// a month that isn't in the journal yet. If 0, navigation is never confirmed
+    pub(crate) far_date_confirmation_days: usize,
+
+    /// The color theme of every window
+    pub(crate) theme: ThemePreference,
+
+    /// If true, tasks whose entry on the current date has text are shown expanded, unless they were expanded or
+    /// collapsed by hand this session
+    pub(crate) auto_expand_tasks: bool,
+
+    /// How saving is confirmed to the user
+    pub(crate) save_notification: SaveNotification,
+
+    /// If true, closing the main window leaves the application running in the system tray, where it can be reopened or
+    /// quit from the tray menu
+    pub(crate) minimize_to_tray: bool,
+
+    /// If true, pressing the summon hotkey anywhere on the system brings the main window to the front, opening it if
+    /// it was closed
+    pub(crate) summon_hotkey_enabled: bool,
+
+    /// The key combination of the summon hotkey, written like "Ctrl+Shift+J"
+    pub(crate) summon_hotkey: String,
+
+    /// The chrono format of the timestamp placed before each quick capture note, like "%H:%M". If empty, notes are
+    /// added without a timestamp
+    pub(crate) quick_capture_timestamp: String,
+
+    /// If true, the main window hides the calender, tabs, and editor mode buttons, leaving only the editor
+    pub(crate) focus_mode: bool,
+
+    /// If true, opening the task creator while it is already open clears the form, rather than only bringing the
+    /// existing window to the front
+    pub(crate) reset_task_creator_on_reopen: bool,
 }
 
 impl Default for GeneralPreferences {
@@ -28,7 +101,26 @@ impl Default for GeneralPreferences {
         Self {
             autosave_enabled: false,
             autosave_interval: Duration::from_mins(5),
+            save_after_sentence: false,
+            backups_enabled: false,
+            backup_interval: Duration::from_hours(24),
+            backup_retention_count: 7,
+            backup_retention_days: 0,
             smart_navigation: false,
+            today_scrolls_to_top: true,
+            compact_calender: false,
+            calender_word_counts: false,
+            month_strip_length: 7,
+            far_date_confirmation_days: 0,
+            theme: ThemePreference::default(),
+            auto_expand_tasks: false,
+            save_notification: SaveNotification::default(),
+            minimize_to_tray: false,
+            summon_hotkey_enabled: false,
+            summon_hotkey: "Ctrl+Shift+J".to_string(),
+            quick_capture_timestamp: "%H:%M".to_string(),
+            focus_mode: false,
+            reset_task_creator_on_reopen: false,
         }
     }
 }
@@ -42,6 +134,12 @@ pub struct PathPreferences {
     pub(crate) system_dictionary_aff: PathBuf,
     pub(crate) personal_dictionary_dic: PathBuf,
     pub(crate) preferences_path: PathBuf,
+
+    /// How the journal entries are laid out inside the savedata directory
+    pub(crate) storage_backend: StorageBackend,
+
+    /// How the json of the journal entries is laid out when it is saved
+    pub(crate) json_format: JsonFormat,
 }
 
 impl Default for PathPreferences {
@@ -75,6 +173,8 @@ impl Default for PathPreferences {
             system_dictionary_dic: PathBuf::from(dic_path),
             personal_dictionary_dic,
             preferences_path,
+            storage_backend: StorageBackend::default(),
+            json_format: JsonFormat::default(),
         }
     }
 }
@@ -88,6 +188,14 @@ impl PathPreferences {
         savedata_dir
     }
 
+    /// The /ironnote/backups directory
+    pub fn backups_dir(&self) -> PathBuf {
+        let mut backups_dir = self.journal_path.clone();
+        backups_dir.push("backups");
+
+        backups_dir
+    }
+
     /// The /ironnote/tasks directory
     fn tasks_dir(&self) -> PathBuf {
         let mut tasks_path = self.journal_path.clone();
@@ -112,6 +220,11 @@ impl PathPreferences {
         event_tasks_path
     }
 
+    /// Returns true if both of the system dictionary files exist
+    pub fn system_dictionary_exists(&self) -> bool {
+        self.system_dictionary_aff.is_file() && self.system_dictionary_dic.is_file()
+    }
+
     /// Creates any missing directories that are required for ironnote to operate properly
     fn create_all_missing_dirs(&self) -> io::Result<()> {
         fs::create_dir_all(self.savedata_dir())?;
@@ -143,18 +256,151 @@ impl PathPreferences {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+/// The color theme of the application
+pub enum ThemePreference {
+    #[default]
+    Light,
+    Dark,
+
+    /// Follows the light or dark mode of the operating system
+    System,
+}
+
+impl ThemePreference {
+    pub const ALL: [ThemePreference; 3] = [
+        ThemePreference::Light,
+        ThemePreference::Dark,
+        ThemePreference::System,
+    ];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+/// How a save is confirmed to the user
+pub enum SaveNotification {
+    /// The confirmation is written to the logbox, where it stays until the next message replaces it
+    #[default]
+    #[strum(to_string = "Log message")]
+    LogMessage,
+
+    /// The confirmation is shown in a toast that fades away after a few seconds, leaving the logbox as it was
+    Toast,
+}
+
+impl SaveNotification {
+    pub const ALL: [SaveNotification; 2] = [SaveNotification::LogMessage, SaveNotification::Toast];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+/// How the journal entries are stored on disk. Switching between backends moves the existing entries over the next time
+/// the journal is loaded
+pub enum StorageBackend {
+    /// Each month is saved to its own YYYY-MM.json file
+    #[default]
+    #[strum(to_string = "One file per month")]
+    MonthFiles,
+
+    /// Every month is saved together in a single journal.json file
+    #[strum(to_string = "Single file")]
+    SingleFile,
+}
+
+impl StorageBackend {
+    pub const ALL: [StorageBackend; 2] = [StorageBackend::MonthFiles, StorageBackend::SingleFile];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+/// How the json of the journal entries is written. Either format is read back the same way, so it can be switched at
+/// any time, with each month taking the new format the next time it is saved
+pub enum JsonFormat {
+    /// Indented over multiple lines. Larger on disk, but changes show up as small diffs under version control
+    #[default]
+    Pretty,
+
+    /// Written on a single line without any whitespace, keeping the files as small as possible
+    Compact,
+}
+
+impl JsonFormat {
+    pub const ALL: [JsonFormat; 2] = [JsonFormat::Pretty, JsonFormat::Compact];
+
+    /// Serializes the value into json laid out in this format
+    pub fn to_json<T: Serialize + ?Sized>(&self, value: &T) -> serde_json::Result<String> {
+        match self {
+            JsonFormat::Pretty => serde_json::to_string_pretty(value),
+            JsonFormat::Compact => serde_json::to_string(value),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+/// What pressing Enter in the search bar does. Newlines are never inserted into the search bar
+pub enum SearchEnterAction {
+    /// Enter is ignored
+    #[default]
+    #[strum(to_string = "Do nothing")]
+    Ignore,
+
+    /// Enter opens the most recent search result
+    #[strum(to_string = "Open first result")]
+    FirstResult,
+
+    /// Each press of Enter opens the next search result, wrapping back around to the first
+    #[strum(to_string = "Open next result")]
+    NextResult,
+}
+
+impl SearchEnterAction {
+    pub const ALL: [SearchEnterAction; 3] = [
+        SearchEnterAction::Ignore,
+        SearchEnterAction::FirstResult,
+        SearchEnterAction::NextResult,
+    ];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+/// How the lines of the entry are aligned in the editor. Only the display changes, the entry text is never touched
+pub enum EditorAlignment {
+    #[default]
+    Left,
+    Center,
+
+    /// Lines that wrap are stretched to fill the whole width of the editor, like the pages of a book
+    Justified,
+}
+
+impl EditorAlignment {
+    pub const ALL: [EditorAlignment; 3] = [
+        EditorAlignment::Left,
+        EditorAlignment::Center,
+        EditorAlignment::Justified,
+    ];
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 /// Settings specific to the search functionality
 pub struct SearchPreferences {
     /// If true, the text typed in the search bar will ignore the capitalization the search
     pub(crate) ignore_search_case: bool,
+
+    /// If true, the search history is saved with the preferences so it persists between sessions
+    pub(crate) persist_search_history: bool,
+
+    /// The most recent searches, newest first. Only stored if persist_search_history is enabled
+    pub(crate) search_history: Vec<String>,
+
+    /// The action performed when Enter is pressed in the search bar
+    pub(crate) enter_action: SearchEnterAction,
 }
 
 impl Default for SearchPreferences {
     fn default() -> Self {
         Self {
             ignore_search_case: true,
+            persist_search_history: false,
+            search_history: Vec::new(),
+            enter_action: SearchEnterAction::default(),
         }
     }
 }
@@ -166,6 +412,113 @@ impl SearchPreferences {
     }
 }
 
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+/// Settings that change how the statistics are calculated
+pub struct StatsPreferences {
+    /// If true, weekends are not expected to have entries. Empty weekends don't break streaks, and weekend entries
+    /// aren't included in the averages
+    pub(crate) exclude_weekends: bool,
+
+    /// If true, averages are taken over every day that has passed, including the days without an entry. Otherwise,
+    /// only the days with an entry are averaged
+    pub(crate) include_empty_days: bool,
+
+    /// How many words the current day's entry should reach. If None, no word goal is shown
+    pub(crate) daily_word_goal: Option<usize>,
+
+    /// How many characters the current day's entry should reach. If None, no character goal is shown
+    pub(crate) daily_char_goal: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+/// Settings that change how entries are exported
+pub struct ExportPreferences {
+    /// Text added before every exported entry. {date}, {weekday}, {day}, {month}, and {year} are replaced with the
+    /// date of the entry being exported
+    pub(crate) header: String,
+
+    /// Text added after every exported entry, supporting the same date tokens as the header
+    pub(crate) footer: String,
+
+    /// The directory the export file dialogs start in. Each export updates it to the directory that was exported to,
+    /// so repeated exports start where the last one went. If None, the dialogs start in the system default
+    pub(crate) default_directory: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+/// Settings for the keyboard shortcuts
+pub struct KeyboardPreferences {
+    /// The key chords of the actions that were rebound, like "Ctrl+Shift+j". Actions that aren't in here use their
+    /// default binding
+    pub(crate) custom_bindings: BTreeMap<BindableAction, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+/// Settings that change how entries are edited
+pub struct EditorPreferences {
+    /// Reusable pieces of text that can be inserted into an entry, keyed by the name of the snippet
+    pub(crate) snippets: BTreeMap<String, String>,
+
+    /// If true, pasting more than large_paste_threshold characters into an entry asks for confirmation first
+    pub(crate) confirm_large_pastes: bool,
+
+    /// The number of characters a paste must exceed to be considered large
+    pub(crate) large_paste_threshold: usize,
+
+    /// If true, line numbers are shown in a gutter next to the editor. Lines aren't wrapped while the line numbers
+    /// are shown, so every number stays aligned with its line
+    pub(crate) show_line_numbers: bool,
+
+    /// If true, spellcheck suggestions are sorted by how often each word is used in the journal. Otherwise, the
+    /// suggestions are kept in the dictionary's own order
+    pub(crate) sort_suggestions_by_usage: bool,
+
+    /// Suggestions with fewer characters than this aren't moved up by their usage, so short common words don't crowd
+    /// out the more likely suggestions
+    pub(crate) suggestion_min_sort_length: usize,
+
+    /// If true, pressing Enter on a list item ("- ", "* ", or "1. ") starts the next line with the same list marker.
+    /// Pressing Enter on an empty list item removes its marker instead
+    pub(crate) continue_lists: bool,
+
+    /// How many recently visited days keep their undo history for the rest of the session, so navigating back to one
+    /// of them can still undo its edits. If 0, the undo history is cleared whenever the day changes
+    pub(crate) history_cache_days: usize,
+
+    /// Entries with more characters than this are only spellchecked near the cursor, since checking the whole entry on
+    /// every keystroke gets slow for very long entries. If 0, the whole entry is always spellchecked
+    pub(crate) spellcheck_length_limit: usize,
+
+    /// If true, pasting an image saves it into the journal's attachments folder and inserts a markdown image linking to
+    /// it. Otherwise, pasted images are ignored since the editor only holds text
+    pub(crate) paste_images_as_attachments: bool,
+
+    /// How the lines of the entry are aligned in the editor
+    pub(crate) text_alignment: EditorAlignment,
+}
+
+impl Default for EditorPreferences {
+    fn default() -> Self {
+        Self {
+            snippets: BTreeMap::new(),
+            confirm_large_pastes: true,
+            large_paste_threshold: 10000,
+            show_line_numbers: false,
+            sort_suggestions_by_usage: true,
+            suggestion_min_sort_length: 0,
+            continue_lists: true,
+            history_cache_days: 5,
+            spellcheck_length_limit: 50000,
+            paste_images_as_attachments: false,
+            text_alignment: EditorAlignment::default(),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(default)]
 /// Stores all of the settings of the application
@@ -173,6 +526,10 @@ pub struct UserPreferences {
     pub(crate) general: GeneralPreferences,
     pub(crate) paths: PathPreferences,
     pub(crate) search: SearchPreferences,
+    pub(crate) editor: EditorPreferences,
+    pub(crate) stats: StatsPreferences,
+    pub(crate) export: ExportPreferences,
+    pub(crate) keyboard: KeyboardPreferences,
 }
 
 impl From<&UserPreferences> for JournalPointer {
@@ -231,10 +588,18 @@ impl UserPreferences {
     }
 }
 
+/// Set when PREFERENCES is initalized without an existing preferences file on disk, meaning this is the first time
+/// ironnote has been launched with the current journal
+static FIRST_RUN: AtomicBool = AtomicBool::new(false);
+
 /// Global preferences object that stores all of the settings of the application
 static PREFERENCES: LazyLock<RwLock<UserPreferences>> = LazyLock::new(|| {
     let default_preferences = UserPreferences::load_from_disk_or_default();
 
+    if !default_preferences.paths.preferences_path.exists() {
+        FIRST_RUN.store(true, Ordering::Relaxed);
+    }
+
     default_preferences
         .initalize_paths_and_files()
         .expect("unable to initalize preferences files/paths");
@@ -264,3 +629,82 @@ pub fn overwrite_preferences(new_preferences: UserPreferences) {
 
     *preferences_mut() = new_preferences;
 }
+
+/// Returns true if this is the first run of ironnote. This only returns true on the first call, so any first run
+/// handling only happens once per session
+pub fn take_first_run() -> bool {
+    LazyLock::force(&PREFERENCES);
+
+    FIRST_RUN.swap(false, Ordering::Relaxed)
+}
+
+/// Serializes the tests that depend on the global preferences, since tests otherwise run in parallel
+#[cfg(test)]
+static TEST_PREFERENCES_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Runs the test with the global preferences edited by edit_preferences, restoring the previous preferences afterwards
+/// even if the test panics. No other test using this can run at the same time, so the edits can't leak between tests
+#[cfg(test)]
+pub fn with_test_preferences<R>(
+    edit_preferences: impl FnOnce(&mut UserPreferences),
+    test: impl FnOnce() -> R,
+) -> R {
+    struct RestorePreferences(Option<UserPreferences>);
+
+    impl Drop for RestorePreferences {
+        fn drop(&mut self) {
+            if let Some(previous_preferences) = self.0.take() {
+                *PREFERENCES
+                    .write()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner()) = previous_preferences;
+            }
+        }
+    }
+
+    let _test_lock = TEST_PREFERENCES_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let previous_preferences = preferences().clone();
+    let _restore_preferences = RestorePreferences(Some(previous_preferences.clone()));
+
+    let mut test_preferences = previous_preferences;
+    edit_preferences(&mut test_preferences);
+    *preferences_mut() = test_preferences;
+
+    test()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    #[test]
+    fn json_formats_round_trip_identically() {
+        let month_json = json!({
+            "2024-01-01": "first line\nsecond line",
+            "2024-01-02": "",
+            "locked_days": [1, 2],
+        });
+
+        let pretty_json = JsonFormat::Pretty
+            .to_json(&month_json)
+            .expect("couldn't serialize pretty json");
+        let compact_json = JsonFormat::Compact
+            .to_json(&month_json)
+            .expect("couldn't serialize compact json");
+
+        assert!(pretty_json.contains('\n'));
+        assert!(!compact_json.contains('\n'));
+        assert!(compact_json.len() < pretty_json.len());
+
+        let pretty_value: Value =
+            serde_json::from_str(&pretty_json).expect("unreadable pretty json");
+        let compact_value: Value =
+            serde_json::from_str(&compact_json).expect("unreadable compact json");
+
+        assert_eq!(pretty_value, month_json);
+        assert_eq!(compact_value, month_json);
+    }
+}
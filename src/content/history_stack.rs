@@ -1,5 +1,8 @@
 use iced::widget::text_editor::{Action, Content, Cursor, Edit, Motion, Position};
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
 
 use super::UpgradedContent;
 
@@ -62,13 +65,20 @@ pub struct HistoryStack {
     redo_history: VecDeque<HistoryEvent>,
     max_redo_size: Option<usize>,
     able_to_revert: bool,
+
+    /// when the last event was pushed, if it can still be coalesced with the next event
+    last_push_time: Option<Instant>,
 }
 
 impl HistoryStack {
+    /// how long typing can pause before the next character starts a new undo step
+    const COALESCE_TIMEOUT: Duration = Duration::from_secs(2);
+
     /// removes all contents from both the undo and redo stacks
     pub fn clear(&mut self) {
         self.undo_history.clear();
         self.redo_history.clear();
+        self.last_push_time = None;
     }
 
     /// prevents revert() from being called when unrevertable. this should be set whenever a HistoryEvent should have
@@ -79,14 +89,57 @@ impl HistoryStack {
     }
 
     /// adds a new event onto the undo stack. the redo stack gets cleared when doing this, since the redo actions are
-    /// no longer valid when a new edit is added to the undo stack. HistoryStack becomes revert()able after a push
+    /// no longer valid when a new edit is added to the undo stack. HistoryStack becomes revert()able after a push.
+    /// consecutive typed characters are coalesced into the previous event, so a run of typing is undone all at once
     pub fn push_undo_action(&mut self, history_event: HistoryEvent) {
         self.able_to_revert = true;
 
-        self.stack_undo_action(history_event);
+        let push_time = Instant::now();
+        let within_timeout = self.last_push_time.is_some_and(|last_push_time| {
+            push_time.duration_since(last_push_time) < Self::COALESCE_TIMEOUT
+        });
+        self.last_push_time = Some(push_time);
+
+        if within_timeout
+            && let Some(last_event) = self.undo_history.front_mut()
+            && Self::can_coalesce(last_event, &history_event)
+            && let Some(added_text) = &history_event.text_added
+        {
+            last_event
+                .text_added
+                .get_or_insert_default()
+                .push_str(added_text);
+            last_event.undo_cursor = history_event.undo_cursor;
+        } else {
+            self.stack_undo_action(history_event);
+        }
+
         self.redo_history.clear();
     }
 
+    /// returns true if the next event is a single typed character that directly continues the typing of the last event.
+    /// whitespace always starts a new group, so each word (along with the space before it) is its own undo step. any
+    /// cursor movement between the events breaks the group, since the next event wouldn't start where the last ended
+    fn can_coalesce(last_event: &HistoryEvent, next_event: &HistoryEvent) -> bool {
+        let is_typed_char = |history_event: &HistoryEvent| {
+            history_event.text_removed.is_none()
+                && history_event.selection_char_count == 0
+                && history_event
+                    .text_added
+                    .as_ref()
+                    .is_some_and(|added_text| !added_text.is_empty())
+        };
+
+        let next_starts_group = next_event.text_added.as_ref().is_some_and(|added_text| {
+            added_text.chars().count() != 1 || added_text.chars().all(char::is_whitespace)
+        });
+
+        is_typed_char(last_event)
+            && is_typed_char(next_event)
+            && !next_starts_group
+            && last_event.undo_cursor == next_event.redo_cursor
+    }
+
     fn stack_undo_action(&mut self, history_event: HistoryEvent) {
         if history_event == HistoryEvent::default() {
             return;
@@ -130,6 +183,8 @@ impl HistoryStack {
     /// undoes the last HistoryEvent on the undo stack, applying its effects to the provided content and moving the event
     /// into the redo stack
     pub fn perform_undo(&mut self, content: &mut Content) {
+        self.last_push_time = None;
+
         if let Some(history_event) = self.move_undo_to_redo_stack() {
             if content.selection().is_some() {
                 // this clears any existing selection since move_to() doesn't work right when there is one
@@ -155,6 +210,8 @@ impl HistoryStack {
     /// redoes the last HistoryEvent on the redo stack, applying its effects to the provided content and moving the event
     /// back onto the undo stack
     pub fn perform_redo(&mut self, content: &mut Content) {
+        self.last_push_time = None;
+
         if let Some(history_event) = self.move_redo_to_undo_stack() {
             let bytewise_redo_cursor =
                 UpgradedContent::byte_cursor(&history_event.redo_cursor, content);
@@ -265,6 +322,79 @@ impl Default for HistoryStack {
             redo_history: Default::default(),
             max_redo_size: Some(1000),
             able_to_revert: false,
+            last_push_time: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::ContentAction;
+
+    fn type_text(content: &mut UpgradedContent, text: &str) {
+        for chara in text.chars() {
+            content.perform(ContentAction::Standard(Action::Edit(Edit::Insert(chara))));
+        }
+    }
+
+    fn content_text(content: &UpgradedContent) -> String {
+        content.text().trim_end_matches('\n').to_string()
+    }
+
+    #[test]
+    fn undoing_a_typed_word_removes_the_whole_word() {
+        let mut content = UpgradedContent::default();
+
+        type_text(&mut content, "hello");
+
+        assert_eq!(content.undo_stack_height(), 1);
+
+        content.perform(ContentAction::Undo);
+
+        assert_eq!(content_text(&content), "");
+    }
+
+    #[test]
+    fn a_space_starts_a_new_undo_step() {
+        let mut content = UpgradedContent::default();
+
+        type_text(&mut content, "hello world");
+
+        assert_eq!(content.undo_stack_height(), 2);
+
+        content.perform(ContentAction::Undo);
+
+        assert_eq!(content_text(&content), "hello");
+    }
+
+    #[test]
+    fn moving_the_cursor_starts_a_new_undo_step() {
+        let mut content = UpgradedContent::default();
+
+        type_text(&mut content, "ac");
+        content.perform(ContentAction::Standard(Action::Move(Motion::Left)));
+        type_text(&mut content, "b");
+
+        assert_eq!(content_text(&content), "abc");
+        assert_eq!(content.undo_stack_height(), 2);
+
+        content.perform(ContentAction::Undo);
+
+        assert_eq!(content_text(&content), "ac");
+    }
+
+    #[test]
+    fn typing_after_an_undo_starts_a_new_undo_step() {
+        let mut content = UpgradedContent::default();
+
+        type_text(&mut content, "first");
+        content.perform(ContentAction::Undo);
+        type_text(&mut content, "second");
+        type_text(&mut content, "third");
+
+        content.perform(ContentAction::Undo);
+
+        assert_eq!(content_text(&content), "");
+    }
+}
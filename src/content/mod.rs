@@ -4,5 +4,6 @@ pub mod upgraded_content;
 // re-exports
 pub use upgraded_content::ContentAction;
 pub use upgraded_content::CtrlEdit;
+pub use upgraded_content::CtrlMotion;
 pub use upgraded_content::Restriction;
 pub use upgraded_content::UpgradedContent;
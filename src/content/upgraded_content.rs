@@ -1,5 +1,4 @@
 use super::history_stack::{HistoryEvent, HistoryStack, TextRemoval};
-use crate::utils::misc_tools;
 
 use iced::widget::text_editor::{self, Action, Content, Cursor, Edit, Position};
 
@@ -26,6 +25,16 @@ impl CtrlEdit {
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+/// cursor motions that move over a whole word at once. they stop at exactly the same characters the word CtrlEdits stop
+/// at, so moving over a word and deleting a word always cover the same text
+pub enum CtrlMotion {
+    WordLeft,
+    WordRight,
+    SelectWordLeft,
+    SelectWordRight,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 /// a Restriction is a subset of the ContentAction::Standard variant, which imposes additional requirements on the
 /// types of Actions that can be performed on the UpgradedContent. Note a Restriction only ever blocks Actions from
@@ -37,6 +46,9 @@ pub enum Restriction {
 
     /// Edits that would insert a '\n' into the content are modified to remove any newline characters
     NoNewlines,
+
+    /// All Edits are blocked, only non-Edit Actions (moving the cursor, selecting...etc) are performed
+    ReadOnly,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -45,6 +57,10 @@ pub enum ContentAction {
     Standard(text_editor::Action),
     Restricted((Restriction, text_editor::Action)),
     Ctrl(CtrlEdit),
+    CtrlMotion(CtrlMotion),
+
+    /// an Enter that continues the list the cursor is in. if the current line isn't a list item, it is a regular Enter
+    ListEnter,
     Undo,
     Redo,
     ClearHistoryStack,
@@ -95,6 +111,12 @@ impl UpgradedContent {
             .map(|selection_text| TextRemoval::new(selection_text, false));
 
         let content_action_status = match content_action {
+            // the text_editor's own word motions use different stopping rules than the CtrlEdits, so they are ignored
+            // in favor of the CtrlMotions, which are bound to the same shortcuts
+            ContentAction::Standard(
+                Action::Move(text_editor::Motion::WordLeft | text_editor::Motion::WordRight)
+                | Action::Select(text_editor::Motion::WordLeft | text_editor::Motion::WordRight),
+            ) => ActionHistoryEvent::Ignore,
             ContentAction::Standard(action) => {
                 self.content.perform(action.clone());
 
@@ -274,6 +296,11 @@ impl UpgradedContent {
                             self.perform(ContentAction::Standard(no_newline_action));
                         }
                     }
+                    Restriction::ReadOnly => {
+                        if !matches!(action, Action::Edit(_)) {
+                            self.perform(ContentAction::Standard(action));
+                        }
+                    }
                 }
 
                 // the self.perform() will take care of any HistoryEvents it generates on its own. if the ContentAction
@@ -311,6 +338,47 @@ impl UpgradedContent {
                     }
                 }
             }
+            ContentAction::CtrlMotion(ctrl_motion) => {
+                Self::perform_ctrl_motion(&mut self.content, ctrl_motion);
+
+                ActionHistoryEvent::Ignore
+            }
+            ContentAction::ListEnter => {
+                let current_line = old_text
+                    .lines()
+                    .nth(old_char_cursor.position.line)
+                    .unwrap_or_default();
+
+                match Self::list_markers(current_line) {
+                    // with the cursor inside of the marker, the item is split like any other line, which moves the
+                    // whole item down instead of putting a second marker in front of it
+                    Some((marker, next_marker))
+                        if selection.is_none()
+                            && old_char_cursor.position.column >= marker.chars().count() =>
+                    {
+                        if current_line == marker {
+                            // enter on an empty item ends the list, leaving the line blank
+                            self.perform(ContentAction::Standard(Action::Move(
+                                text_editor::Motion::Home,
+                            )));
+                            self.perform(ContentAction::Standard(Action::Select(
+                                text_editor::Motion::End,
+                            )));
+                            self.perform(ContentAction::Standard(Action::Edit(Edit::Backspace)));
+                        } else {
+                            let continuation = format!("\n{}", next_marker);
+
+                            self.perform(ContentAction::Standard(Action::Edit(Edit::Paste(
+                                continuation.into(),
+                            ))));
+                        }
+                    }
+                    _ => self.perform(ContentAction::Standard(Action::Edit(Edit::Enter))),
+                }
+
+                // the edits are perform()ed individually, which pushes their own HistoryEvents
+                ActionHistoryEvent::Ignore
+            }
             ContentAction::Undo => {
                 self.history_stack.perform_undo(&mut self.content);
 
@@ -339,6 +407,38 @@ impl UpgradedContent {
         }
     }
 
+    /// if the line is a list item, returns the list marker of the line (including its indentation) along with the
+    /// marker the next item in the list should start with. numbered markers are incremented for the next item
+    fn list_markers(line: &str) -> Option<(String, String)> {
+        let item = line.trim_start();
+        let indentation = &line[..line.len() - item.len()];
+
+        for bullet in ["- ", "* "] {
+            if item.starts_with(bullet) {
+                let marker = format!("{}{}", indentation, bullet);
+
+                return Some((marker.clone(), marker));
+            }
+        }
+
+        let digit_count = item
+            .chars()
+            .take_while(|chara| chara.is_ascii_digit())
+            .count();
+
+        if digit_count > 0
+            && item[digit_count..].starts_with(". ")
+            && let Ok(number) = item[..digit_count].parse::<u64>()
+        {
+            let marker = format!("{}{}. ", indentation, number);
+            let next_marker = format!("{}{}. ", indentation, number.saturating_add(1));
+
+            return Some((marker, next_marker));
+        }
+
+        None
+    }
+
     /// returns true if the given cursor is at the start of the current line
     fn cursor_at_start_of_line(cursor: &Cursor) -> bool {
         cursor.position.column == 0
@@ -498,6 +598,49 @@ impl UpgradedContent {
         Self::char_cursor(&self.content).position.column
     }
 
+    /// moves the cursor to the given position, in characters, clearing any selection
+    pub fn move_cursor_to(&mut self, char_position: Position) {
+        if self.content.selection().is_some() {
+            // move_to() doesn't work right when there is a selection
+            self.content
+                .perform(Action::Move(text_editor::Motion::DocumentStart));
+        }
+
+        let char_cursor = Cursor {
+            position: char_position,
+            selection: None,
+        };
+
+        self.content
+            .move_to(Self::byte_cursor(&char_cursor, &self.content));
+    }
+
+    /// selects the given number of characters, starting from the given position in characters
+    pub fn select_from(&mut self, char_position: Position, char_count: usize) {
+        self.move_cursor_to(char_position);
+
+        for _char in 0..char_count {
+            self.content
+                .perform(Action::Select(text_editor::Motion::Right));
+        }
+    }
+
+    /// removes the undo and redo history from the content, leaving it with an empty history
+    pub fn take_history_stack(&mut self) -> HistoryStack {
+        std::mem::take(&mut self.history_stack)
+    }
+
+    /// replaces the undo and redo history of the content. the history must have been taken from content with the same
+    /// text, otherwise undoing would apply the events to the wrong text
+    pub fn set_history_stack(&mut self, history_stack: HistoryStack) {
+        self.history_stack = history_stack;
+    }
+
+    /// returns the number of lines in the content
+    pub fn line_count(&self) -> usize {
+        self.content.line_count()
+    }
+
     /// returns the underlying Content. this should only be used when constructing a text_editor
     pub fn raw_content(&self) -> &Content {
         &self.content
@@ -508,6 +651,72 @@ impl UpgradedContent {
         self.content.selection().unwrap_or_default()
     }
 
+    /// returns the selected text if there is a selection, otherwise the word the cursor is placed on. returns the
+    /// empty string if the cursor isn't touching a word
+    pub fn word_under_cursor(&self) -> String {
+        let selection = self.selection();
+
+        if !selection.is_empty() {
+            return selection;
+        }
+
+        let (line_chars, word_start, word_end) = self.word_bounds_at_cursor();
+
+        line_chars[word_start..word_end].iter().collect()
+    }
+
+    /// selects the word the cursor is placed on. if the cursor isn't touching a word, such as when it is surrounded by
+    /// whitespace or punctuation, nothing is selected. returns true if a word was selected
+    pub fn select_word_at_cursor(&mut self) -> bool {
+        let cursor_line = Self::char_cursor(&self.content).position.line;
+
+        let (_line_chars, word_start, word_end) = self.word_bounds_at_cursor();
+
+        let word_position = Position {
+            line: cursor_line,
+            column: word_start,
+        };
+
+        if word_start == word_end {
+            self.move_cursor_to(word_position);
+
+            return false;
+        }
+
+        self.select_from(word_position, word_end - word_start);
+
+        true
+    }
+
+    /// the characters of the cursor's line, along with the character range of the word the cursor is placed on. the
+    /// range is empty if the cursor isn't touching a word
+    fn word_bounds_at_cursor(&self) -> (Vec<char>, usize, usize) {
+        let text = self.content.text();
+        let cursor = Self::char_cursor(&self.content);
+
+        let line_chars: Vec<char> = text
+            .lines()
+            .nth(cursor.position.line)
+            .unwrap_or_default()
+            .chars()
+            .collect();
+
+        let is_word_char = |chara: &char| chara.is_alphanumeric() || *chara == '\'';
+
+        let cursor_column = cursor.position.column.min(line_chars.len());
+
+        let word_start = line_chars[..cursor_column]
+            .iter()
+            .rposition(|chara| !is_word_char(chara))
+            .map_or(0, |index| index + 1);
+        let word_end = line_chars[cursor_column..]
+            .iter()
+            .position(|chara| !is_word_char(chara))
+            .map_or(line_chars.len(), |index| cursor_column + index);
+
+        (line_chars, word_start, word_end)
+    }
+
     /// returns the Content text as a string
     pub fn text(&self) -> String {
         self.content.text()
@@ -538,8 +747,6 @@ impl UpgradedContent {
             return None;
         }
 
-        let mut removed_chars = String::new();
-
         let cursor_line_start = old_char_cursor.position.line;
         let cursor_char_start = old_char_cursor.position.column;
 
@@ -590,67 +797,155 @@ impl UpgradedContent {
             return Some(history_event);
         }
 
-        let char_line = old_text
+        let char_line: Vec<char> = old_text
             .lines()
             .nth(cursor_line_start)
-            .expect("couldn't extract line");
-
-        let mut backspace_head = cursor_char_start - 1;
-
-        let first_char_removed = char_line
+            .expect("couldn't extract line")
             .chars()
-            .nth(backspace_head)
-            .expect("couldn't extract char from line");
+            .collect();
 
-        let mut removing_seqence_of_stops = false;
+        // the boundary is shared with the word motions, so deleting a word removes exactly what moving over it passes
+        let boundary = Self::word_boundary_left(&char_line, cursor_char_start, stopping_chars);
 
-        loop {
-            let char_to_remove = char_line
-                .chars()
-                .nth(backspace_head)
-                .expect("couldn't extract char from line");
+        let removed_chars: String = char_line[boundary..cursor_char_start].iter().collect();
 
-            removed_chars.push(char_to_remove);
+        for _removed_char in boundary..cursor_char_start {
             content.perform(Action::Edit(text_editor::Edit::Backspace));
+        }
 
-            if backspace_head > 0 {
-                backspace_head -= 1;
+        let new_char_cursor = Self::char_cursor(content);
+
+        Some(HistoryEvent {
+            text_removed: Some(TextRemoval::new(removed_chars, false)),
+            text_added: None,
+            selection_char_count,
+            redo_cursor: new_char_cursor,
+            undo_cursor: new_char_cursor,
+        })
+    }
+
+    /// moves the cursor over a word on the content, extending the selection for the Select motions. the word boundaries
+    /// follow the same rules as the word CtrlEdits. at the start or end of a line, the cursor moves onto the neighboring
+    /// line, just like a ctrl+backspace or ctrl+delete there removes only the newline
+    pub fn perform_ctrl_motion(content: &mut Content, ctrl_motion: CtrlMotion) {
+        let stopping_chars = CtrlEdit::BackspaceWord.stopping_char_set();
+
+        let (moving_left, step) = match ctrl_motion {
+            CtrlMotion::WordLeft => (true, Action::Move(text_editor::Motion::Left)),
+            CtrlMotion::WordRight => (false, Action::Move(text_editor::Motion::Right)),
+            CtrlMotion::SelectWordLeft => (true, Action::Select(text_editor::Motion::Left)),
+            CtrlMotion::SelectWordRight => (false, Action::Select(text_editor::Motion::Right)),
+        };
+
+        if matches!(step, Action::Move(_)) && content.selection().is_some() {
+            // a move collapses the selection onto its edge first, then moves over the word from there
+            content.perform(step.clone());
+        }
+
+        let text = content.text();
+        let char_cursor = Self::char_cursor(content);
+
+        let line: Vec<char> = text
+            .lines()
+            .nth(char_cursor.position.line)
+            .unwrap_or_default()
+            .chars()
+            .collect();
+        let column = char_cursor.position.column.min(line.len());
+
+        let step_count = if moving_left {
+            if column == 0 {
+                1
             } else {
-                break;
+                column - Self::word_boundary_left(&line, column, stopping_chars)
             }
+        } else if column == line.len() {
+            1
+        } else {
+            Self::word_boundary_right(&line, column, stopping_chars) - column
+        };
 
-            let next_char_to_remove = char_line
-                .chars()
-                .nth(backspace_head)
-                .expect("couldn't extract char from line");
+        for _step in 0..step_count {
+            content.perform(step.clone());
+        }
+    }
+
+    /// finds the column a ctrl+backspace from the given column removes characters up to, which is also where a word
+    /// motion to the left stops. the column must be greater than 0
+    fn word_boundary_left(line: &[char], column: usize, stopping_chars: &[char]) -> usize {
+        let first_char = line[column - 1];
+
+        let mut head = column - 1;
+        let mut passing_sequence_of_stops = false;
+
+        loop {
+            if head == 0 {
+                return 0;
+            }
+
+            // line[head..column] are the characters that have been passed so far
+            let passed_chars = &line[head..column];
+
+            head -= 1;
+
+            let next_char = line[head];
 
-            if stopping_chars.contains(&first_char_removed)
-                && first_char_removed == next_char_to_remove
-                && misc_tools::chars_all_same_in_string(&removed_chars)
-                && (removed_chars.chars().count() == 1 || removing_seqence_of_stops)
+            if stopping_chars.contains(&first_char)
+                && first_char == next_char
+                && passed_chars.iter().all(|chara| *chara == first_char)
+                && (passed_chars.len() == 1 || passing_sequence_of_stops)
             {
-                removing_seqence_of_stops = true;
+                passing_sequence_of_stops = true;
                 continue;
-            } else if removing_seqence_of_stops {
+            } else if passing_sequence_of_stops {
                 break;
             }
 
-            if stopping_chars.contains(&next_char_to_remove) {
+            if stopping_chars.contains(&next_char) {
                 break;
             }
         }
 
-        removed_chars = removed_chars.chars().rev().collect();
+        head + 1
+    }
 
-        let new_char_cursor = Self::char_cursor(content);
+    /// finds the column a ctrl+delete from the given column removes characters up to, which is also where a word
+    /// motion to the right stops. the column must be less than the length of the line
+    fn word_boundary_right(line: &[char], column: usize, stopping_chars: &[char]) -> usize {
+        let first_char = line[column];
 
-        Some(HistoryEvent {
-            text_removed: Some(TextRemoval::new(removed_chars, false)),
-            text_added: None,
-            selection_char_count,
-            redo_cursor: new_char_cursor,
-            undo_cursor: new_char_cursor,
-        })
+        let mut head = column;
+        let mut passing_sequence_of_stops = false;
+
+        loop {
+            if head + 1 >= line.len() {
+                return line.len();
+            }
+
+            // line[column..=head] are the characters that have been passed so far
+            let passed_chars = &line[column..=head];
+
+            head += 1;
+
+            let next_char = line[head];
+
+            if stopping_chars.contains(&first_char)
+                && first_char == next_char
+                && passed_chars.iter().all(|chara| *chara == first_char)
+                && (passed_chars.len() == 1 || passing_sequence_of_stops)
+            {
+                passing_sequence_of_stops = true;
+                continue;
+            } else if passing_sequence_of_stops {
+                break;
+            }
+
+            if stopping_chars.contains(&next_char) {
+                break;
+            }
+        }
+
+        head
     }
 
     /// performs a ctrl+delete on the content, for a given set of stopping_chars, which dictates the characters that
@@ -671,8 +966,6 @@ impl UpgradedContent {
 
         let line = old_text.lines().nth(cursor_line_start)?;
 
-        let char_count = line.chars().count();
-
         if let Some(selection_text) = selection {
             content.perform(Action::Edit(text_editor::Edit::Backspace));
 
@@ -704,51 +997,15 @@ impl UpgradedContent {
 
             Some(history_event)
         } else {
-            // standard ctrl+delete
-            let mut removed_chars = String::new();
-            let first_char_removed = line
-                .chars()
-                .nth(cursor_char_start)
-                .expect("couldn't extract char from line");
-
-            let mut delete_head = cursor_char_start;
-
-            let mut removing_sequence_of_stops = false;
+            // standard ctrl+delete, up to the same boundary as a word motion to the right
+            let line_chars: Vec<char> = line.chars().collect();
+            let boundary =
+                Self::word_boundary_right(&line_chars, cursor_char_start, stopping_chars);
 
-            loop {
-                let char_to_remove = line
-                    .chars()
-                    .nth(delete_head)
-                    .expect("couldn't extract char from line");
+            let removed_chars: String = line_chars[cursor_char_start..boundary].iter().collect();
 
-                removed_chars.push(char_to_remove);
+            for _removed_char in cursor_char_start..boundary {
                 content.perform(Action::Edit(text_editor::Edit::Delete));
-
-                if (delete_head + 1) < char_count {
-                    delete_head += 1;
-                } else {
-                    break;
-                }
-
-                let next_char_to_remove = line
-                    .chars()
-                    .nth(delete_head)
-                    .expect("couldn't extract char from line");
-
-                if stopping_chars.contains(&first_char_removed)
-                    && first_char_removed == next_char_to_remove
-                    && misc_tools::chars_all_same_in_string(&removed_chars)
-                    && (removed_chars.chars().count() == 1 || removing_sequence_of_stops)
-                {
-                    removing_sequence_of_stops = true;
-                    continue;
-                } else if removing_sequence_of_stops {
-                    break;
-                }
-
-                if stopping_chars.contains(&next_char_to_remove) {
-                    break;
-                }
             }
 
             Some(HistoryEvent {
@@ -761,3 +1018,164 @@ impl UpgradedContent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// content with the given text and the cursor at the end of it
+    fn content_at_end(text: &str) -> UpgradedContent {
+        let mut content = UpgradedContent::with_text(text);
+        content.perform(ContentAction::Standard(Action::Move(
+            text_editor::Motion::DocumentEnd,
+        )));
+
+        content
+    }
+
+    /// the text of the content, without the newline that may be added after the last line
+    fn content_text(content: &UpgradedContent) -> String {
+        let text = content.text();
+
+        text.strip_suffix('\n').unwrap_or(&text).to_string()
+    }
+
+    /// content with the given text and the cursor at the given column of the first line
+    fn content_at_column(text: &str, column: usize) -> UpgradedContent {
+        let mut content = UpgradedContent::with_text(text);
+        content.move_cursor_to(Position { line: 0, column });
+
+        content
+    }
+
+    #[test]
+    fn word_motions_stop_where_word_deletions_stop() {
+        let line = "Hello, wörld... (it's) done!!";
+        let line_length = line.chars().count();
+        let stopping_chars = CtrlEdit::BackspaceWord.stopping_char_set();
+
+        for column in 1..=line_length {
+            let mut moved_content = content_at_column(line, column);
+            moved_content.perform(ContentAction::CtrlMotion(CtrlMotion::WordLeft));
+
+            let mut deleted_content = content_at_column(line, column);
+            UpgradedContent::perform_ctrl_backspace(&mut deleted_content.content, stopping_chars);
+
+            assert_eq!(
+                moved_content.cursor_column(),
+                deleted_content.cursor_column(),
+                "word left from column {column}"
+            );
+        }
+
+        for column in 0..line_length {
+            let mut moved_content = content_at_column(line, column);
+            moved_content.perform(ContentAction::CtrlMotion(CtrlMotion::WordRight));
+
+            let mut deleted_content = content_at_column(line, column);
+            UpgradedContent::perform_ctrl_delete(&mut deleted_content.content, stopping_chars);
+
+            let removed_count = line_length - content_text(&deleted_content).chars().count();
+
+            assert_eq!(
+                moved_content.cursor_column(),
+                column + removed_count,
+                "word right from column {column}"
+            );
+        }
+    }
+
+    #[test]
+    fn word_deletions_pass_over_repeated_stops() {
+        let stopping_chars = CtrlEdit::BackspaceWord.stopping_char_set();
+
+        let mut content = content_at_column("wait... what", 7);
+        UpgradedContent::perform_ctrl_backspace(&mut content.content, stopping_chars);
+        assert_eq!(content_text(&content), "wait what");
+
+        let mut content = content_at_column("wait... what", 4);
+        UpgradedContent::perform_ctrl_delete(&mut content.content, stopping_chars);
+        assert_eq!(content_text(&content), "wait what");
+    }
+
+    #[test]
+    fn list_markers_of_bullets_repeat() {
+        assert_eq!(
+            UpgradedContent::list_markers("- item"),
+            Some(("- ".to_string(), "- ".to_string()))
+        );
+        assert_eq!(
+            UpgradedContent::list_markers("  * item"),
+            Some(("  * ".to_string(), "  * ".to_string()))
+        );
+    }
+
+    #[test]
+    fn list_markers_of_numbers_increment() {
+        assert_eq!(
+            UpgradedContent::list_markers("9. item"),
+            Some(("9. ".to_string(), "10. ".to_string()))
+        );
+        assert_eq!(
+            UpgradedContent::list_markers("    12. item"),
+            Some(("    12. ".to_string(), "    13. ".to_string()))
+        );
+    }
+
+    #[test]
+    fn list_markers_ignore_non_list_lines() {
+        assert_eq!(UpgradedContent::list_markers("plain text"), None);
+        assert_eq!(UpgradedContent::list_markers("-item"), None);
+        assert_eq!(UpgradedContent::list_markers("1.item"), None);
+        assert_eq!(UpgradedContent::list_markers(""), None);
+    }
+
+    #[test]
+    fn list_enter_continues_bullets() {
+        let mut content = content_at_end("- first");
+
+        content.perform(ContentAction::ListEnter);
+
+        assert_eq!(content_text(&content), "- first\n- ");
+    }
+
+    #[test]
+    fn list_enter_continues_numbers() {
+        let mut content = content_at_end("1. first");
+
+        content.perform(ContentAction::ListEnter);
+
+        assert_eq!(content_text(&content), "1. first\n2. ");
+    }
+
+    #[test]
+    fn list_enter_on_an_empty_item_ends_the_list() {
+        let mut content = content_at_end("- first\n- ");
+
+        content.perform(ContentAction::ListEnter);
+
+        assert_eq!(content_text(&content), "- first");
+    }
+
+    #[test]
+    fn list_enter_at_the_start_of_an_item_moves_it_down() {
+        let mut content = UpgradedContent::with_text("- item");
+        content.perform(ContentAction::Standard(Action::Move(
+            text_editor::Motion::DocumentStart,
+        )));
+
+        content.perform(ContentAction::ListEnter);
+
+        assert_eq!(content_text(&content), "\n- item");
+    }
+
+    #[test]
+    fn list_enter_outside_of_a_list_is_a_regular_enter() {
+        let mut content = content_at_end("plain text");
+
+        content.perform(ContentAction::ListEnter);
+        content.perform(ContentAction::Standard(Action::Edit(Edit::Insert('x'))));
+
+        assert_eq!(content_text(&content), "plain text\nx");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::ui::{
-    journal_theme::LIGHT,
+    journal_theme::journal_theme,
     layout::{
         CALENDER_DAY_HEIGHT, CALENDER_DAY_OF_WEEK_HEIGHT, CALENDER_DAY_WIDTH,
         CALENDER_MONTH_YEAR_HEIGHT, TOTAL_CALENDER_WIDTH,
@@ -58,12 +58,18 @@ impl Default for CalenderColormap {
 }
 
 #[derive(Debug)]
-/// calender widget that is displayed out as a set of 6 rows by 7 days, with month and year navigations
+/// calender widget that is displayed out as a set of 6 rows by 7 days, with month and year navigations. in compact mode,
+/// trailing rows that only contain days of the next month are hidden
 pub struct Calender {
     day_mapping: [NaiveDate; 42],
     bolded_days: [bool; 42],
     current_date: NaiveDate,
     colormap: CalenderColormap,
+    compact_weeks: bool,
+    dimmed_days: [bool; 42],
+
+    /// the word counts shown under each day number. if None, only the day numbers are shown
+    word_counts: Option<[usize; 42]>,
 }
 
 impl Calender {
@@ -135,9 +141,16 @@ impl Calender {
 
         let mut calender = column![month_year_bar, day_bar];
 
+        let displayed_day_count = self.week_count() * 7;
+
         let mut day_count = 0;
         let mut week_row = row![];
-        for (day_index, date) in self.day_mapping.iter().enumerate() {
+        for (day_index, date) in self
+            .day_mapping
+            .iter()
+            .enumerate()
+            .take(displayed_day_count)
+        {
             let font = Font {
                 weight: if self.bolded_days[day_index] {
                     Weight::Bold
@@ -147,17 +160,34 @@ impl Calender {
                 ..Font::DEFAULT
             };
 
-            let day_button_content = rich_text![span(date.day().to_string()).font(font)]
+            let day_number = rich_text![span(date.day().to_string()).font(font)]
                 .size(13)
                 .on_link_click(never)
                 .center();
 
-            let day_button = widget::button(day_button_content)
+            let word_count = self.word_counts.map(|word_counts| word_counts[day_index]);
+
+            let day_button_content: Element<'a, CalenderMessage> = match word_count {
+                Some(word_count) if word_count > 0 => column![
+                    day_number.size(11),
+                    Text::new(Self::compact_word_count(word_count)).size(8)
+                ]
+                .align_x(Center)
+                .into(),
+                _ => day_number.into(),
+            };
+
+            let mut day_button = widget::button(day_button_content)
                 .on_press(CalenderMessage::DayClicked(*date))
                 .width(CALENDER_DAY_WIDTH)
                 .height(CALENDER_DAY_HEIGHT)
                 .style(self.day_button_color(day_index));
 
+            // the word count needs the room the padding would take up to fit under the day number
+            if word_count.is_some() {
+                day_button = day_button.padding(0);
+            }
+
             week_row = week_row.push(day_button);
             day_count += 1;
 
@@ -171,20 +201,41 @@ impl Calender {
         calender.into()
     }
 
-    /// calculates the number of days from the previous month should be included at the start of the calender. the
-    /// value that is returned will always be at least 1, in the range of [1, 7]
+    /// calculates the number of days from the previous month should be included at the start of the calender. with
+    /// compact weeks this is in the range of [0, 6], so months starting on a sunday don't spend a row on the previous
+    /// month. otherwise the value that is returned will always be at least 1, in the range of [1, 7]
     fn previous_month_days(&self) -> u32 {
         let current_month_first = self.current_date.with_day(1).expect("1st doesn't exist");
 
         let previous_month_days = current_month_first.weekday().num_days_from_sunday();
 
-        if previous_month_days == 0 {
+        if previous_month_days == 0 && !self.compact_weeks {
             7
         } else {
             previous_month_days
         }
     }
 
+    /// the number of week rows the calender displays. this is always 6 unless compact weeks are enabled, in which case
+    /// it is only the number of weeks needed to fit the current month (between 4 and 6)
+    fn week_count(&self) -> usize {
+        if !self.compact_weeks {
+            return 6;
+        }
+
+        let needed_days = self.previous_month_days() + self.current_date.num_days_in_month() as u32;
+
+        needed_days.div_ceil(7) as usize
+    }
+
+    /// sets whether the calender should only display the weeks needed to fit the current month
+    pub fn set_compact_weeks(&mut self, compact_weeks: bool) {
+        self.compact_weeks = compact_weeks;
+
+        // the days from the previous month depend on whether weeks are compact, so the layout has to be redone
+        self.set_current_date(self.current_date);
+    }
+
     /// sets the days from the current month that should be bolded. only days that are in the current month are able to
     /// be bolded
     pub fn set_bolded_days(&mut self, bolded_days: &[bool; 31]) {
@@ -195,6 +246,29 @@ impl Calender {
         self.bolded_days[start_offset..(start_offset + 31)].clone_from_slice(bolded_days);
     }
 
+    /// sets the word counts shown under the days of the current month, indexed by day of the month. if None, the word
+    /// counts are hidden
+    pub fn set_word_counts(&mut self, word_counts: Option<&[usize; 31]>) {
+        self.word_counts = word_counts.map(|word_counts| {
+            let mut calender_word_counts = [0; 42];
+
+            let start_offset = self.previous_month_days() as usize;
+
+            calender_word_counts[start_offset..(start_offset + 31)].clone_from_slice(word_counts);
+
+            calender_word_counts
+        });
+    }
+
+    /// shortens a word count to fit under a day number, like "850" or "1.2k"
+    fn compact_word_count(word_count: usize) -> String {
+        if word_count < 1000 {
+            word_count.to_string()
+        } else {
+            format!("{:.1}k", word_count as f64 / 1000.0)
+        }
+    }
+
     /// sets the current date of the calender, updating the calender structure to reflect the current date
     pub fn set_current_date(&mut self, current_date: NaiveDate) {
         self.current_date = current_date;
@@ -218,6 +292,12 @@ impl Calender {
         }
     }
 
+    /// sets the days that are dimmed on the calender, indexed the same way as the calender days starting from the
+    /// calender_start_date. dimmed days are shown the same way as days outside of the current month
+    pub fn set_dimmed_days(&mut self, dimmed_days: [bool; 42]) {
+        self.dimmed_days = dimmed_days;
+    }
+
     /// sets the colormap of the calender
     pub fn set_colormap(&mut self, new_colormap: CalenderColormap) {
         self.colormap = new_colormap;
@@ -251,31 +331,16 @@ impl Calender {
         let is_current_month = calender_main_month == current_days_month;
         let is_current_day = self.day_mapping[day_index] == self.current_date;
 
-        let background_color = if is_current_day && self.colormap.current_day_overwrite {
-            LIGHT.selection
-        } else if let Some(color_weight) = self.colormap.colormap_weights[day_index] {
+        let current_day_overwrite = self.colormap.current_day_overwrite;
+        let dimmed_day = !is_current_month || self.dimmed_days[day_index];
+
+        let colormap_color = self.colormap.colormap_weights[day_index].map(|color_weight| {
             Self::color_linear_interpolate(
                 self.colormap.color_floor,
                 self.colormap.color_ceiling,
                 color_weight,
             )
-        } else {
-            LIGHT.default_background
-        };
-
-        let text_color = if !is_current_month {
-            LIGHT.dimmed_text
-        } else if is_current_day && self.colormap.current_day_overwrite {
-            LIGHT.selection_text
-        } else {
-            LIGHT.default_text
-        };
-
-        let border_color = if is_current_day && !self.colormap.current_day_overwrite {
-            LIGHT.selection
-        } else {
-            LIGHT.default_background
-        };
+        });
 
         let (boarder_radius, border_width) = if is_current_day {
             (2.0, 1.0)
@@ -283,11 +348,35 @@ impl Calender {
             (6.0, 2.0)
         };
 
-        move |_theme: &Theme, status: Status| {
+        move |theme: &Theme, status: Status| {
+            let journal_theme = journal_theme(theme);
+
+            let background_color = if is_current_day && current_day_overwrite {
+                journal_theme.selection
+            } else if let Some(colormap_color) = colormap_color {
+                colormap_color
+            } else {
+                journal_theme.default_background
+            };
+
+            let text_color = if dimmed_day {
+                journal_theme.dimmed_text
+            } else if is_current_day && current_day_overwrite {
+                journal_theme.selection_text
+            } else {
+                journal_theme.default_text
+            };
+
+            let border_color = if is_current_day && !current_day_overwrite {
+                journal_theme.selection
+            } else {
+                journal_theme.default_background
+            };
+
             let modified_background = match status {
                 Status::Active => background_color,
                 Status::Hovered | Status::Pressed | Status::Disabled => {
-                    LIGHT.darken(background_color)
+                    journal_theme.darken(background_color)
                 }
             };
 
@@ -317,6 +406,9 @@ impl Default for Calender {
             bolded_days: [false; 42],
             current_date: Local::now().date_naive(),
             colormap: CalenderColormap::default(),
+            compact_weeks: false,
+            dimmed_days: [false; 42],
+            word_counts: None,
         }
     }
 }
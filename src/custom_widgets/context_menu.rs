@@ -5,7 +5,6 @@ use iced::{Element, Point};
 
 use crate::custom_widgets::rectangle::build_rectangle;
 use crate::ui::button_themes::context_menu_style;
-use crate::ui::journal_theme::LIGHT;
 use crate::ui::layout::{
     CONTEXT_MENU_BORDER_WIDTH, CONTEXT_MENU_HEIGHT, CONTEXT_MENU_MIN_WIDTH,
     CONTEXT_MENU_RADIOCHECK_WIDTH, CONTEXT_MENU_TEXT_PADDING, SCROLLBAR_WIDTH,
@@ -172,11 +171,10 @@ pub fn build_context_menu<'a, M: 'a + Clone>(
                     text_component
                 ];
 
-                let text_background = build_rectangle(
-                    full_menu_width,
-                    CONTEXT_MENU_HEIGHT,
-                    LIGHT.context_menu_background,
-                );
+                let text_background =
+                    build_rectangle(full_menu_width, CONTEXT_MENU_HEIGHT, |journal_theme| {
+                        journal_theme.context_menu_background
+                    });
 
                 let text_full = stack!(text_background, text_element);
 
@@ -231,11 +229,9 @@ pub fn build_context_menu<'a, M: 'a + Clone>(
         }
     }
 
-    let menu_border = build_rectangle(
-        bordered_menu_width,
-        bordered_menu_height,
-        LIGHT.context_menu_border,
-    );
+    let menu_border = build_rectangle(bordered_menu_width, bordered_menu_height, |journal_theme| {
+        journal_theme.context_menu_border
+    });
 
     let pinned_menu = widget::pin(menu).position(Point::new(
         CONTEXT_MENU_BORDER_WIDTH,
@@ -29,6 +29,9 @@ pub struct FilePicker {
     picker_type: PickerType,
     filepath_content: UpgradedContent,
     filepath: PathBuf,
+
+    /// the directory the file dialog opens in. if None, the dialog opens wherever the system defaults to
+    starting_directory: Option<PathBuf>,
 }
 
 impl<'a> FilePicker {
@@ -40,6 +43,7 @@ impl<'a> FilePicker {
             picker_type: PickerType::File(extension_filters.to_vec()),
             filepath_content: UpgradedContent::with_text(inital_path_str),
             filepath: inital_path,
+            starting_directory: None,
         }
     }
 
@@ -51,9 +55,15 @@ impl<'a> FilePicker {
             picker_type: PickerType::Directory,
             filepath_content: UpgradedContent::with_text(inital_path_str),
             filepath: inital_path,
+            starting_directory: None,
         }
     }
 
+    /// sets the directory the file dialog opens in
+    pub fn set_starting_directory(&mut self, starting_directory: Option<PathBuf>) {
+        self.starting_directory = starting_directory;
+    }
+
     /// returns the current path in the FilePicker
     pub fn path(&self) -> PathBuf {
         self.filepath.clone()
@@ -94,9 +104,15 @@ impl<'a> FilePicker {
                 self.filepath_content.perform(action);
             }
             FilePickerMessage::OpenFileDialog => {
+                let mut file_dialog = FileDialog::new();
+
+                if let Some(starting_directory) = &self.starting_directory {
+                    file_dialog = file_dialog.set_directory(starting_directory);
+                }
+
                 let file_path = match &self.picker_type {
                     PickerType::File(extension_filters) => {
-                        let mut file_dialog = FileDialog::new().set_title("Select File");
+                        file_dialog = file_dialog.set_title("Select File");
 
                         for (name, extensions) in extension_filters {
                             file_dialog = file_dialog.add_filter(name, extensions)
@@ -106,9 +122,9 @@ impl<'a> FilePicker {
 
                         file_dialog.pick_file()
                     }
-                    PickerType::Directory => FileDialog::new()
-                        .set_title("Select Directory")
-                        .pick_folder(),
+                    PickerType::Directory => {
+                        file_dialog.set_title("Select Directory").pick_folder()
+                    }
                 };
 
                 if let Some(path) = file_path {
@@ -30,6 +30,9 @@ pub enum EditMessage {
     Cut,
     Copy,
     Paste,
+    ContinueFromYesterday,
+    RevertEntry,
+    ClearEntry,
 }
 
 impl EditMessage {
@@ -41,6 +44,9 @@ impl EditMessage {
             EditMessage::Cut => "Cut",
             EditMessage::Copy => "Copy",
             EditMessage::Paste => "Paste",
+            EditMessage::ContinueFromYesterday => "Continue From Yesterday",
+            EditMessage::RevertEntry => "Revert to Last Save",
+            EditMessage::ClearEntry => "Clear Entry",
         }
     }
 }
@@ -48,14 +54,24 @@ impl EditMessage {
 #[derive(Debug, Clone)]
 /// Tools menu actions
 pub enum ToolsMessage {
+    NewTask,
+    QuickCapture,
+    FocusMode,
     Preferences,
+    Diagnostics,
+    CopyDiagnostics,
 }
 
 impl ToolsMessage {
     /// The text of the tools menu item
     pub fn name(&self) -> &'static str {
         match self {
+            ToolsMessage::NewTask => "New Task",
+            ToolsMessage::QuickCapture => "Quick Capture",
+            ToolsMessage::FocusMode => "Toggle Focus Mode",
             ToolsMessage::Preferences => "Preferences",
+            ToolsMessage::Diagnostics => "Diagnostics",
+            ToolsMessage::CopyDiagnostics => "Copy Diagnostics",
         }
     }
 }
@@ -113,11 +129,11 @@ pub fn build_menu_bar() -> MenuBar<MenuMessage> {
     file_dropdown.push_menu_item(ContextMenuItem::Break);
     file_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
         FileMessage::Import.name(),
-        Some(MenuMessage::File(FileMessage::Save)),
+        Some(MenuMessage::File(FileMessage::Import)),
     )));
     file_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
         FileMessage::Export.name(),
-        Some(MenuMessage::File(FileMessage::Save)),
+        Some(MenuMessage::File(FileMessage::Export)),
     )));
 
     let mut edit_dropdown = Dropdown::new(
@@ -146,16 +162,50 @@ pub fn build_menu_bar() -> MenuBar<MenuMessage> {
         EditMessage::Redo.name(),
         Some(MenuMessage::Edit(EditMessage::Redo)),
     )));
+    edit_dropdown.push_menu_item(ContextMenuItem::Break);
+    edit_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        EditMessage::ContinueFromYesterday.name(),
+        Some(MenuMessage::Edit(EditMessage::ContinueFromYesterday)),
+    )));
+    edit_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        EditMessage::RevertEntry.name(),
+        Some(MenuMessage::Edit(EditMessage::RevertEntry)),
+    )));
+    edit_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        EditMessage::ClearEntry.name(),
+        Some(MenuMessage::Edit(EditMessage::ClearEntry)),
+    )));
 
     let mut tools_dropdown = Dropdown::new(
         DropdownType::Tools.dropdown_name(),
         MenuMessage::ClickedDropdown(DropdownType::Tools.dropdown_index()),
     );
 
+    tools_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        ToolsMessage::NewTask.name(),
+        Some(MenuMessage::Tools(ToolsMessage::NewTask)),
+    )));
+    tools_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        ToolsMessage::QuickCapture.name(),
+        Some(MenuMessage::Tools(ToolsMessage::QuickCapture)),
+    )));
+    tools_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        ToolsMessage::FocusMode.name(),
+        Some(MenuMessage::Tools(ToolsMessage::FocusMode)),
+    )));
+    tools_dropdown.push_menu_item(ContextMenuItem::Break);
     tools_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
         ToolsMessage::Preferences.name(),
         Some(MenuMessage::Tools(ToolsMessage::Preferences)),
     )));
+    tools_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        ToolsMessage::Diagnostics.name(),
+        Some(MenuMessage::Tools(ToolsMessage::Diagnostics)),
+    )));
+    tools_dropdown.push_menu_item(ContextMenuItem::Button(ContextMenuElement::new(
+        ToolsMessage::CopyDiagnostics.name(),
+        Some(MenuMessage::Tools(ToolsMessage::CopyDiagnostics)),
+    )));
 
     menu_bar.push_dropdown(file_dropdown);
     menu_bar.push_dropdown(edit_dropdown);
@@ -0,0 +1,129 @@
+use crate::ui::{
+    journal_theme::{JournalTheme, journal_theme},
+    layout::MONTH_STRIP_HEIGHT,
+};
+
+use chrono::NaiveDate;
+use iced::{
+    Background, Border, Color, Element, Length, Shadow, Theme, Vector,
+    widget::{
+        Row, Text, button,
+        button::{Status, Style},
+    },
+};
+
+#[derive(Debug, Clone)]
+/// types of messages the month strip can generate
+pub enum MonthStripMessage {
+    MonthClicked(NaiveDate),
+}
+
+#[derive(Debug, Default)]
+/// a compact horizontal strip of recent months, each tinted by how many words were written in it. used to quickly jump
+/// between months without stepping through the calender
+pub struct MonthStrip {
+    /// the first of each displayed month along with its total word count, in chronological order
+    months: Vec<(NaiveDate, usize)>,
+
+    /// the first of the month currently being viewed
+    current_month: NaiveDate,
+}
+
+impl MonthStrip {
+    pub fn view(&self) -> Element<'_, MonthStripMessage> {
+        let max_word_count = self
+            .months
+            .iter()
+            .map(|(_month, word_count)| *word_count)
+            .max()
+            .unwrap_or_default();
+
+        let mut strip = Row::new().height(MONTH_STRIP_HEIGHT);
+
+        for (first_of_month, word_count) in &self.months {
+            let weight = if max_word_count == 0 {
+                None
+            } else {
+                Some((*word_count as f32) / (max_word_count as f32))
+            };
+
+            let is_current_month = *first_of_month == self.current_month;
+
+            let month_button = button(
+                Text::new(first_of_month.format("%b").to_string())
+                    .size(10)
+                    .center(),
+            )
+            .on_press(MonthStripMessage::MonthClicked(*first_of_month))
+            .padding(0)
+            .width(Length::FillPortion(1))
+            .height(MONTH_STRIP_HEIGHT)
+            .style(move |theme: &Theme, status: Status| {
+                Self::month_style(journal_theme(theme), weight, is_current_month, status)
+            });
+
+            strip = strip.push(month_button);
+        }
+
+        strip.into()
+    }
+
+    /// sets the months shown in the strip. the months should be given as the first of each month along with the total
+    /// word count of that month, in chronological order
+    pub fn set_months(&mut self, months: Vec<(NaiveDate, usize)>, current_month: NaiveDate) {
+        self.months = months;
+        self.current_month = current_month;
+    }
+
+    /// the styling of an individual month in the strip. the background is interpolated between the word count floor
+    /// and ceiling colors based on the weight, and the current month gets outlined
+    fn month_style(
+        journal_theme: &JournalTheme,
+        weight: Option<f32>,
+        is_current_month: bool,
+        status: Status,
+    ) -> Style {
+        let background_color = if let Some(weight) = weight {
+            let floor = journal_theme.char_count_floor;
+            let ceiling = journal_theme.char_count_ceiling;
+
+            Color::from_linear_rgba(
+                (ceiling.r - floor.r) * weight + floor.r,
+                (ceiling.g - floor.g) * weight + floor.g,
+                (ceiling.b - floor.b) * weight + floor.b,
+                (ceiling.a - floor.a) * weight + floor.a,
+            )
+        } else {
+            journal_theme.default_background
+        };
+
+        let background_color = match status {
+            Status::Active => background_color,
+            Status::Hovered | Status::Pressed | Status::Disabled => {
+                journal_theme.darken(background_color)
+            }
+        };
+
+        let border_color = if is_current_month {
+            journal_theme.selection
+        } else {
+            journal_theme.default_background
+        };
+
+        Style {
+            background: Some(Background::Color(background_color)),
+            text_color: journal_theme.default_text,
+            border: Border {
+                color: border_color,
+                width: 1.0,
+                radius: 2.0.into(),
+            },
+            shadow: Shadow {
+                color: Color::TRANSPARENT,
+                offset: Vector::ZERO,
+                blur_radius: 0.0,
+            },
+            snap: true,
+        }
+    }
+}
@@ -1,10 +1,17 @@
 use iced::{Background, Border, Color, Element, Shadow, Theme, Vector, widget::button};
 
-/// Constructs a rectangle widget of the given dimensions and color
-pub fn build_rectangle<'a, M: 'a + Clone>(width: f32, height: f32, color: Color) -> Element<'a, M> {
-    let button_style = move |_theme: &Theme, _status: button::Status| -> button::Style {
+use crate::ui::journal_theme::{JournalTheme, journal_theme};
+
+/// Constructs a rectangle widget of the given dimensions. The color is picked out of the journal theme in use, so the
+/// rectangle follows theme changes
+pub fn build_rectangle<'a, M: 'a + Clone>(
+    width: f32,
+    height: f32,
+    color: fn(&JournalTheme) -> Color,
+) -> Element<'a, M> {
+    let button_style = move |theme: &Theme, _status: button::Status| -> button::Style {
         button::Style {
-            background: Some(Background::Color(color)),
+            background: Some(Background::Color(color(journal_theme(theme)))),
             text_color: Color::TRANSPARENT,
             border: Border {
                 color: Color::TRANSPARENT,
@@ -19,6 +19,9 @@ struct SearchEntry {
     bolded_text: String,
     end_text: String,
     date: NaiveDate,
+
+    /// true if the result matched the name of a task rather than the text of an entry
+    is_task: bool,
 }
 
 #[derive(Debug, Default)]
@@ -42,9 +45,17 @@ impl SearchTable {
             .size(12)
             .on_link_click(never);
 
+            // task results are styled differently so they can't be mistaken for entries
+            let result_style = if entry.is_task {
+                widget::button::secondary
+            } else {
+                widget::button::primary
+            };
+
             table = table.push(
                 widget::button(rich_text)
                     .on_press(SearchTableMessage::EntryClicked(entry.date))
+                    .style(result_style)
                     .width(DASHBOARD_WIDTH - SCROLLBAR_WIDTH),
             );
         }
@@ -64,11 +75,41 @@ impl SearchTable {
             bolded_text,
             end_text,
             date,
+            is_task: false,
+        };
+
+        self.entries.push(new_entry);
+    }
+
+    /// adds a result for a task whose name matched, which jumps to the given date when clicked
+    pub fn insert_task_element(
+        &mut self,
+        start_text: String,
+        bolded_text: String,
+        end_text: String,
+        date: NaiveDate,
+    ) {
+        let new_entry = SearchEntry {
+            start_text,
+            bolded_text,
+            end_text,
+            date,
+            is_task: true,
         };
 
         self.entries.push(new_entry);
     }
 
+    /// the number of results in the table
+    pub fn result_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// the date of the result at the given position in the table, if it exists
+    pub fn result_date(&self, index: usize) -> Option<NaiveDate> {
+        self.entries.get(index).map(|entry| entry.date)
+    }
+
     pub fn clear(&mut self) {
         self.entries.clear();
     }
@@ -1,5 +1,5 @@
 use iced::{
-    Element,
+    Border, Element,
     widget::{self, Space, Text, button, column, row, text::Wrapping},
 };
 
@@ -19,12 +19,15 @@ use crate::ui::layout::{DASHBOARD_WIDTH, SCROLLBAR_WIDTH};
 ///
 /// options_menu_items: Some if the options menu is to be rendered. The String is the name of the option, and the
 /// correponding M is the message that option triggers
+///
+/// focused: True if the task has the keyboard focus, which outlines the task
 pub fn build_task<'a, M: 'a + Clone>(
     checkbox: Option<(bool, M)>,
     name: String,
     expanded: Option<(Option<Element<'a, M>>, M)>,
     options_menu_toggle: M,
     options_menu: Option<Element<'a, M>>,
+    focused: bool,
 ) -> Element<'a, M> {
     let main_checkbox = if let Some((checked, check_message)) = checkbox {
         column![widget::checkbox(checked).on_toggle(move |_ticked| { check_message.clone() })]
@@ -60,5 +63,20 @@ pub fn build_task<'a, M: 'a + Clone>(
 
     let full_ui = column![task_ui, expanded_ui, options_menu].width(DASHBOARD_WIDTH);
 
-    full_ui.into()
+    widget::container(full_ui)
+        .style(move |theme: &iced::Theme| {
+            if focused {
+                widget::container::Style {
+                    border: Border {
+                        color: theme.extended_palette().primary.strong.color,
+                        width: 1.0,
+                        radius: 3.0.into(),
+                    },
+                    ..Default::default()
+                }
+            } else {
+                widget::container::Style::default()
+            }
+        })
+        .into()
 }
@@ -0,0 +1,122 @@
+use super::DialogType;
+use crate::{
+    SharedAppState, UpstreamAction,
+    content::ContentAction,
+    store::Draft,
+    tasks::TaskType,
+    windows::{file_import_window::FileImportStrategy, window_manager::Windowable},
+};
+
+use chrono::NaiveDate;
+use iced::{
+    Element, Task,
+    widget::{Text, button, column, row},
+    window,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+/// actions that require the user to confirm them before they are performed
+pub enum ConfirmedAction {
+    /// pastes the text into the active entry
+    PasteText(String),
+
+    /// replaces the entry of each draft with the unsaved text recovered from the draft
+    RecoverDrafts(Vec<Draft>),
+
+    /// clears the entire entry of the given date
+    ClearEntry(NaiveDate),
+
+    /// imports the text into the active entry with the given strategy
+    ImportText(String, FileImportStrategy),
+
+    /// deletes the template task with the given name and type, along with every one of its entries
+    DeleteTemplate(String, TaskType),
+
+    /// replaces the entry of the given date with its text from the last save, discarding every edit made since
+    RevertEntry(NaiveDate),
+
+    /// navigates to the given date, even though it is far enough from today to create a new month
+    NavigateToDate(NaiveDate),
+}
+
+#[derive(Debug, Clone)]
+/// types of confirmation messages
+pub enum ConfirmationMessage {
+    Confirm,
+    Cancel,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// structure representing a dialog that asks the user to confirm an action before it is performed. if the user
+/// confirms, the action is passed upstream to be performed, otherwise it is dropped
+pub struct ConfirmationDialog {
+    /// text describing what is about to happen that is displayed to the user
+    confirmation_text: String,
+
+    /// the action that gets performed if the user confirms
+    action: ConfirmedAction,
+
+    /// window Id of the dialog box
+    window_id: window::Id,
+}
+
+impl ConfirmationDialog {
+    /// creates a new ConfirmationDialog structure with the given Id, the action awaiting confirmation, and a
+    /// description of what the action will do
+    pub fn new(window_id: window::Id, action: ConfirmedAction, confirmation_text: String) -> Self {
+        Self {
+            confirmation_text,
+            action,
+            window_id,
+        }
+    }
+
+    /// the DialogType of this dialog
+    fn dialog_type(&self) -> DialogType {
+        DialogType::Confirmation(self.action.clone())
+    }
+}
+
+impl Windowable<ConfirmationMessage> for ConfirmationDialog {
+    fn title(&self) -> String {
+        "Confirm".to_string()
+    }
+
+    fn view<'a>(&'a self, _state: &'a SharedAppState) -> Element<'a, ConfirmationMessage> {
+        let confirmation_message = Text::new(&self.confirmation_text);
+
+        let cancel_button = button("Cancel").on_press(ConfirmationMessage::Cancel);
+        let confirm_button = button("Continue").on_press(ConfirmationMessage::Confirm);
+
+        column![confirmation_message, row![cancel_button, confirm_button]].into()
+    }
+
+    fn update(
+        &mut self,
+        state: &mut SharedAppState,
+        message: ConfirmationMessage,
+    ) -> Task<ConfirmationMessage> {
+        match message {
+            ConfirmationMessage::Confirm => {
+                state
+                    .upstream_actions
+                    .push(UpstreamAction::PerformConfirmed(self.action.clone()));
+
+                state.upstream_actions.push(UpstreamAction::CloseDialog(
+                    self.window_id,
+                    self.dialog_type(),
+                ));
+            }
+            ConfirmationMessage::Cancel => {
+                state.upstream_actions.push(UpstreamAction::CloseDialog(
+                    self.window_id,
+                    self.dialog_type(),
+                ));
+            }
+        }
+
+        Task::none()
+    }
+
+    fn content_perform(&mut self, _state: &mut SharedAppState, _action: ContentAction) {}
+}
@@ -1,4 +1,7 @@
-use super::{WarningDialog, WarningMessage};
+use super::{
+    ConfirmationDialog, ConfirmationMessage, ConfirmedAction, PasswordDialog, PasswordMessage,
+    UnsavedChangesDialog, UnsavedChangesMessage, WarningDialog, WarningMessage,
+};
 use crate::{SharedAppState, ui::layout::DIALOG_WINDOW_SIZE, windows::window_manager::Windowable};
 
 use iced::{Element, Task, window};
@@ -8,12 +11,18 @@ use std::collections::BTreeMap;
 /// types of dialogs that can be triggered. a dialog is a popup window that notifies the user something has happened
 pub enum DialogType {
     Warning,
+    Confirmation(ConfirmedAction),
+    Password,
+    UnsavedChanges,
 }
 
 #[derive(Debug, Clone)]
 /// types of messages that each respective dialog box can generate
 pub enum DialogMessage {
     Warning(WarningMessage),
+    Confirmation(ConfirmationMessage),
+    Password(PasswordMessage),
+    UnsavedChanges(UnsavedChangesMessage),
 }
 
 #[derive(Debug, Default)]
@@ -22,6 +31,9 @@ pub enum DialogMessage {
 /// still allowing access to the individual types without a match
 pub struct DialogManager {
     warnings: BTreeMap<window::Id, WarningDialog>,
+    confirmations: BTreeMap<window::Id, ConfirmationDialog>,
+    passwords: BTreeMap<window::Id, PasswordDialog>,
+    unsaved_changes: BTreeMap<window::Id, UnsavedChangesDialog>,
 }
 
 impl DialogManager {
@@ -41,6 +53,21 @@ impl DialogManager {
         self.warnings
             .get(&dialog_id)
             .map(|warning_dialog| warning_dialog.title())
+            .or_else(|| {
+                self.confirmations
+                    .get(&dialog_id)
+                    .map(|confirmation_dialog| confirmation_dialog.title())
+            })
+            .or_else(|| {
+                self.passwords
+                    .get(&dialog_id)
+                    .map(|password_dialog| password_dialog.title())
+            })
+            .or_else(|| {
+                self.unsaved_changes
+                    .get(&dialog_id)
+                    .map(|unsaved_changes_dialog| unsaved_changes_dialog.title())
+            })
     }
 
     /// gets the view of the dialog window based on the given Id
@@ -52,6 +79,29 @@ impl DialogManager {
         self.warnings
             .get(&dialog_id)
             .map(|warning_dialog| warning_dialog.view(state).map(DialogMessage::Warning))
+            .or_else(|| {
+                self.confirmations
+                    .get(&dialog_id)
+                    .map(|confirmation_dialog| {
+                        confirmation_dialog
+                            .view(state)
+                            .map(DialogMessage::Confirmation)
+                    })
+            })
+            .or_else(|| {
+                self.passwords
+                    .get(&dialog_id)
+                    .map(|password_dialog| password_dialog.view(state).map(DialogMessage::Password))
+            })
+            .or_else(|| {
+                self.unsaved_changes
+                    .get(&dialog_id)
+                    .map(|unsaved_changes_dialog| {
+                        unsaved_changes_dialog
+                            .view(state)
+                            .map(DialogMessage::UnsavedChanges)
+                    })
+            })
     }
 
     /// adds a dialog of the given type to the DialogManager, with the given text and Id
@@ -66,6 +116,20 @@ impl DialogManager {
                 self.warnings
                     .insert(window_id, WarningDialog::new(window_id, dialog_text));
             }
+            DialogType::Confirmation(action) => {
+                self.confirmations.insert(
+                    window_id,
+                    ConfirmationDialog::new(window_id, action, dialog_text),
+                );
+            }
+            DialogType::Password => {
+                self.passwords
+                    .insert(window_id, PasswordDialog::new(window_id, dialog_text));
+            }
+            DialogType::UnsavedChanges => {
+                self.unsaved_changes
+                    .insert(window_id, UnsavedChangesDialog::new(window_id, dialog_text));
+            }
         }
     }
 
@@ -75,6 +139,15 @@ impl DialogManager {
             DialogType::Warning => {
                 self.warnings.remove(&window_id);
             }
+            DialogType::Confirmation(_action) => {
+                self.confirmations.remove(&window_id);
+            }
+            DialogType::Password => {
+                self.passwords.remove(&window_id);
+            }
+            DialogType::UnsavedChanges => {
+                self.unsaved_changes.remove(&window_id);
+            }
         }
     }
 
@@ -95,6 +168,33 @@ impl DialogManager {
                     Task::none()
                 }
             }
+            DialogMessage::Confirmation(confirmation_message) => {
+                if let Some(confirmation_dialog) = self.confirmations.get_mut(&window_id) {
+                    confirmation_dialog
+                        .update(state, confirmation_message)
+                        .map(DialogMessage::Confirmation)
+                } else {
+                    Task::none()
+                }
+            }
+            DialogMessage::Password(password_message) => {
+                if let Some(password_dialog) = self.passwords.get_mut(&window_id) {
+                    password_dialog
+                        .update(state, password_message)
+                        .map(DialogMessage::Password)
+                } else {
+                    Task::none()
+                }
+            }
+            DialogMessage::UnsavedChanges(unsaved_changes_message) => {
+                if let Some(unsaved_changes_dialog) = self.unsaved_changes.get_mut(&window_id) {
+                    unsaved_changes_dialog
+                        .update(state, unsaved_changes_message)
+                        .map(DialogMessage::UnsavedChanges)
+                } else {
+                    Task::none()
+                }
+            }
         }
     }
 }
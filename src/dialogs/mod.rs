@@ -1,9 +1,19 @@
+pub mod confirmation_dialog;
 pub mod dialog_manager;
+pub mod password_dialog;
+pub mod unsaved_changes_dialog;
 pub mod warning_dialog;
 
 // re-exports
+pub use confirmation_dialog::ConfirmationDialog;
+pub use confirmation_dialog::ConfirmationMessage;
+pub use confirmation_dialog::ConfirmedAction;
 pub use dialog_manager::DialogManager;
 pub use dialog_manager::DialogMessage;
 pub use dialog_manager::DialogType;
+pub use password_dialog::PasswordDialog;
+pub use password_dialog::PasswordMessage;
+pub use unsaved_changes_dialog::UnsavedChangesDialog;
+pub use unsaved_changes_dialog::UnsavedChangesMessage;
 pub use warning_dialog::WarningDialog;
 pub use warning_dialog::WarningMessage;
@@ -0,0 +1,113 @@
+use super::DialogType;
+use crate::{
+    SharedAppState, UpstreamAction, content::ContentAction, store::journal_cipher,
+    windows::window_manager::Windowable,
+};
+
+use iced::{
+    Element, Task,
+    widget::{Text, button, column, row, text_input},
+    window,
+};
+
+#[derive(Debug, Clone)]
+/// types of password messages
+pub enum PasswordMessage {
+    EditPassword(String),
+    Unlock,
+    Cancel,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// structure representing a dialog that asks for the password of an encrypted journal. the password is only handed to
+/// the journal cipher to derive the key, and is dropped along with the dialog
+pub struct PasswordDialog {
+    /// text explaining why the password is needed that is displayed to the user
+    password_text: String,
+
+    /// the password typed so far
+    password: String,
+
+    /// why the last unlock attempt failed, if it did
+    error_text: Option<String>,
+
+    /// window Id of the dialog box
+    window_id: window::Id,
+}
+
+impl PasswordDialog {
+    /// creates a new PasswordDialog structure with the given Id and a description of why the password is needed
+    pub fn new(window_id: window::Id, password_text: String) -> Self {
+        Self {
+            password_text,
+            password: String::new(),
+            error_text: None,
+            window_id,
+        }
+    }
+}
+
+impl Windowable<PasswordMessage> for PasswordDialog {
+    fn title(&self) -> String {
+        "Unlock Journal".to_string()
+    }
+
+    fn view<'a>(&'a self, _state: &'a SharedAppState) -> Element<'a, PasswordMessage> {
+        let password_message = Text::new(&self.password_text);
+
+        // the password is the one place a masked text_input is needed, which the text_editor can't do
+        let password_input = text_input("Password", &self.password)
+            .secure(true)
+            .on_input(PasswordMessage::EditPassword)
+            .on_submit(PasswordMessage::Unlock);
+
+        let error_text = self.error_text.as_ref().map(Text::new);
+
+        let cancel_button = button("Cancel").on_press(PasswordMessage::Cancel);
+        let unlock_button = button("Unlock").on_press(PasswordMessage::Unlock);
+
+        column![
+            password_message,
+            password_input,
+            error_text,
+            row![cancel_button, unlock_button]
+        ]
+        .into()
+    }
+
+    fn update(
+        &mut self,
+        state: &mut SharedAppState,
+        message: PasswordMessage,
+    ) -> Task<PasswordMessage> {
+        match message {
+            PasswordMessage::EditPassword(password) => {
+                self.password = password;
+            }
+            PasswordMessage::Unlock => match journal_cipher::unlock(&self.password) {
+                Ok(()) => {
+                    state.upstream_actions.push(UpstreamAction::JournalUnlocked);
+
+                    state.upstream_actions.push(UpstreamAction::CloseDialog(
+                        self.window_id,
+                        DialogType::Password,
+                    ));
+                }
+                Err(unlock_error) => {
+                    self.password.clear();
+                    self.error_text = Some(unlock_error.to_string());
+                }
+            },
+            PasswordMessage::Cancel => {
+                state.upstream_actions.push(UpstreamAction::CloseDialog(
+                    self.window_id,
+                    DialogType::Password,
+                ));
+            }
+        }
+
+        Task::none()
+    }
+
+    fn content_perform(&mut self, _state: &mut SharedAppState, _action: ContentAction) {}
+}
@@ -0,0 +1,89 @@
+use super::DialogType;
+use crate::{
+    SharedAppState, UpstreamAction, content::ContentAction, store::Draft,
+    windows::window_manager::Windowable,
+};
+
+use iced::{
+    Element, Task,
+    widget::{Text, button, column, row},
+    window,
+};
+
+#[derive(Debug, Clone)]
+/// types of unsaved changes messages
+pub enum UnsavedChangesMessage {
+    Save,
+    Discard,
+    Cancel,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// structure representing a dialog that is shown when the application is about to exit with unsaved changes. the
+/// changes can be saved before exiting, thrown away, or the exit can be called off
+pub struct UnsavedChangesDialog {
+    /// text describing what is unsaved that is displayed to the user
+    unsaved_text: String,
+
+    /// window Id of the dialog box
+    window_id: window::Id,
+}
+
+impl UnsavedChangesDialog {
+    /// creates a new UnsavedChangesDialog structure with the given Id and a description of what is unsaved
+    pub fn new(window_id: window::Id, unsaved_text: String) -> Self {
+        Self {
+            unsaved_text,
+            window_id,
+        }
+    }
+}
+
+impl Windowable<UnsavedChangesMessage> for UnsavedChangesDialog {
+    fn title(&self) -> String {
+        "Unsaved Changes".to_string()
+    }
+
+    fn view<'a>(&'a self, _state: &'a SharedAppState) -> Element<'a, UnsavedChangesMessage> {
+        let unsaved_message = Text::new(&self.unsaved_text);
+
+        let cancel_button = button("Cancel").on_press(UnsavedChangesMessage::Cancel);
+        let discard_button = button("Discard").on_press(UnsavedChangesMessage::Discard);
+        let save_button = button("Save").on_press(UnsavedChangesMessage::Save);
+
+        column![
+            unsaved_message,
+            row![cancel_button, discard_button, save_button]
+        ]
+        .into()
+    }
+
+    fn update(
+        &mut self,
+        state: &mut SharedAppState,
+        message: UnsavedChangesMessage,
+    ) -> Task<UnsavedChangesMessage> {
+        match message {
+            UnsavedChangesMessage::Save => {
+                state.upstream_actions.push(UpstreamAction::Autosave);
+                state.upstream_actions.push(UpstreamAction::ExitApplication);
+            }
+            UnsavedChangesMessage::Discard => {
+                // the changes were thrown away on purpose, so they shouldn't be offered for recovery on the next start
+                Draft::clear_from_disk();
+
+                state.upstream_actions.push(UpstreamAction::ExitApplication);
+            }
+            UnsavedChangesMessage::Cancel => {
+                state.upstream_actions.push(UpstreamAction::CloseDialog(
+                    self.window_id,
+                    DialogType::UnsavedChanges,
+                ));
+            }
+        }
+
+        Task::none()
+    }
+
+    fn content_perform(&mut self, _state: &mut SharedAppState, _action: ContentAction) {}
+}
@@ -1,7 +1,17 @@
-use crate::content::{ContentAction, CtrlEdit};
+use crate::{
+    config::preferences,
+    content::{ContentAction, CtrlEdit, CtrlMotion},
+    utils::logbox::logbox_mut,
+};
 
-use iced::widget::text_editor::{Action, Motion};
-use keybinds::Keybinds;
+use iced::{
+    keyboard,
+    widget::text_editor::{Action, Motion},
+};
+use keybinds::{Key, KeyInput, Keybinds};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use strum::Display;
 
 #[derive(Debug, Clone)]
 /// these actions are not bound to their shortcuts via the keybinds structure, since the text_editor takes care of
@@ -12,6 +22,14 @@ pub enum UnboundKey {
     Paste,
 }
 
+#[derive(Debug, Clone)]
+/// keys that move through and check off the task list while it has the keyboard focus
+pub enum TaskNavigation {
+    Previous,
+    Next,
+    Toggle,
+}
+
 #[derive(Debug, Clone)]
 /// keyboard actions specific to text_editors
 pub enum TextEdit {
@@ -19,6 +37,10 @@ pub enum TextEdit {
     BackspaceSentence,
     DeleteWord,
     DeleteSentence,
+    WordLeft,
+    WordRight,
+    SelectWordLeft,
+    SelectWordRight,
     Undo,
     Redo,
     JumpToContentStart,
@@ -33,6 +55,10 @@ impl TextEdit {
             TextEdit::BackspaceSentence => ContentAction::Ctrl(CtrlEdit::BackspaceSentence),
             TextEdit::DeleteWord => ContentAction::Ctrl(CtrlEdit::DeleteWord),
             TextEdit::DeleteSentence => ContentAction::Ctrl(CtrlEdit::DeleteSentence),
+            TextEdit::WordLeft => ContentAction::CtrlMotion(CtrlMotion::WordLeft),
+            TextEdit::WordRight => ContentAction::CtrlMotion(CtrlMotion::WordRight),
+            TextEdit::SelectWordLeft => ContentAction::CtrlMotion(CtrlMotion::SelectWordLeft),
+            TextEdit::SelectWordRight => ContentAction::CtrlMotion(CtrlMotion::SelectWordRight),
             TextEdit::Undo => ContentAction::Undo,
             TextEdit::Redo => ContentAction::Redo,
             TextEdit::JumpToContentStart => {
@@ -51,57 +77,231 @@ pub enum KeyboardAction {
     Content(TextEdit),
     Save,
     Debug,
+    OpenPreferences,
+    NewTask,
+    SearchCursorWord,
+    Paste,
+    RevertEntry,
+    JumpToDate,
+    SwitchFocusRegion,
+    ToggleFocusMode,
+    TaskNavigation(TaskNavigation),
     Unbound(UnboundKey),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Display)]
+/// every action that can be bound to a key chord through the keyboard preferences
+pub enum BindableAction {
+    Save,
+    Undo,
+    Redo,
+    #[strum(to_string = "Backspace Word")]
+    BackspaceWord,
+    #[strum(to_string = "Backspace Sentence")]
+    BackspaceSentence,
+    #[strum(to_string = "Delete Word")]
+    DeleteWord,
+    #[strum(to_string = "Delete Sentence")]
+    DeleteSentence,
+    #[strum(to_string = "Word Left")]
+    WordLeft,
+    #[strum(to_string = "Word Right")]
+    WordRight,
+    #[strum(to_string = "Select Word Left")]
+    SelectWordLeft,
+    #[strum(to_string = "Select Word Right")]
+    SelectWordRight,
+    #[strum(to_string = "Jump to Entry Start")]
+    JumpToContentStart,
+    #[strum(to_string = "Jump to Entry End")]
+    JumpToContentEnd,
+    Debug,
+    #[strum(to_string = "Open Preferences")]
+    OpenPreferences,
+    #[strum(to_string = "New Task")]
+    NewTask,
+    #[strum(to_string = "Search Word at Cursor")]
+    SearchCursorWord,
+    Paste,
+    #[strum(to_string = "Revert Entry")]
+    RevertEntry,
+    #[strum(to_string = "Jump to Date")]
+    JumpToDate,
+    #[strum(to_string = "Switch Focus Between Editor and Tasks")]
+    SwitchFocusRegion,
+    #[strum(to_string = "Toggle Focus Mode")]
+    ToggleFocusMode,
+    #[strum(to_string = "Previous Task")]
+    PreviousTask,
+    #[strum(to_string = "Next Task")]
+    NextTask,
+    #[strum(to_string = "Check Off Task")]
+    ToggleTask,
+}
+
+impl BindableAction {
+    pub const ALL: [BindableAction; 25] = [
+        BindableAction::Save,
+        BindableAction::Undo,
+        BindableAction::Redo,
+        BindableAction::BackspaceWord,
+        BindableAction::BackspaceSentence,
+        BindableAction::DeleteWord,
+        BindableAction::DeleteSentence,
+        BindableAction::WordLeft,
+        BindableAction::WordRight,
+        BindableAction::SelectWordLeft,
+        BindableAction::SelectWordRight,
+        BindableAction::JumpToContentStart,
+        BindableAction::JumpToContentEnd,
+        BindableAction::Debug,
+        BindableAction::OpenPreferences,
+        BindableAction::NewTask,
+        BindableAction::SearchCursorWord,
+        BindableAction::Paste,
+        BindableAction::RevertEntry,
+        BindableAction::JumpToDate,
+        BindableAction::SwitchFocusRegion,
+        BindableAction::ToggleFocusMode,
+        BindableAction::PreviousTask,
+        BindableAction::NextTask,
+        BindableAction::ToggleTask,
+    ];
+
+    /// the key chord the action is bound to when it hasn't been rebound
+    pub fn default_binding(&self) -> &'static str {
+        match self {
+            BindableAction::Save => "Ctrl+s",
+            BindableAction::Undo => "Ctrl+z",
+            BindableAction::Redo => "Ctrl+Z",
+            BindableAction::BackspaceWord => "Ctrl+Backspace",
+            BindableAction::BackspaceSentence => "Ctrl+Shift+Backspace",
+            BindableAction::DeleteWord => "Ctrl+Delete",
+            BindableAction::DeleteSentence => "Ctrl+Shift+Delete",
+            BindableAction::WordLeft => "Ctrl+Left",
+            BindableAction::WordRight => "Ctrl+Right",
+            BindableAction::SelectWordLeft => "Ctrl+Shift+Left",
+            BindableAction::SelectWordRight => "Ctrl+Shift+Right",
+            BindableAction::JumpToContentStart => "Ctrl+Up",
+            BindableAction::JumpToContentEnd => "Ctrl+Down",
+            BindableAction::Debug => "Ctrl+d",
+            BindableAction::OpenPreferences => "Ctrl+,",
+            BindableAction::NewTask => "Ctrl+t",
+            BindableAction::SearchCursorWord => "Ctrl+Shift+f",
+            BindableAction::Paste => "Ctrl+v",
+            BindableAction::RevertEntry => "Ctrl+Shift+r",
+            BindableAction::JumpToDate => "Ctrl+g",
+            BindableAction::SwitchFocusRegion => "F6",
+            BindableAction::ToggleFocusMode => "F8",
+            BindableAction::PreviousTask => "Up",
+            BindableAction::NextTask => "Down",
+            BindableAction::ToggleTask => "Space",
+        }
+    }
+
+    /// the key chord the action is bound to, taking the custom bindings into account
+    pub fn binding(&self, custom_bindings: &BTreeMap<BindableAction, String>) -> String {
+        custom_bindings
+            .get(self)
+            .cloned()
+            .unwrap_or_else(|| self.default_binding().to_string())
+    }
+
+    /// the action that is performed when the binding is pressed
+    pub fn keyboard_action(&self) -> KeyboardAction {
+        match self {
+            BindableAction::Save => KeyboardAction::Save,
+            BindableAction::Undo => KeyboardAction::Content(TextEdit::Undo),
+            BindableAction::Redo => KeyboardAction::Content(TextEdit::Redo),
+            BindableAction::BackspaceWord => KeyboardAction::Content(TextEdit::BackspaceWord),
+            BindableAction::BackspaceSentence => {
+                KeyboardAction::Content(TextEdit::BackspaceSentence)
+            }
+            BindableAction::DeleteWord => KeyboardAction::Content(TextEdit::DeleteWord),
+            BindableAction::DeleteSentence => KeyboardAction::Content(TextEdit::DeleteSentence),
+            BindableAction::WordLeft => KeyboardAction::Content(TextEdit::WordLeft),
+            BindableAction::WordRight => KeyboardAction::Content(TextEdit::WordRight),
+            BindableAction::SelectWordLeft => KeyboardAction::Content(TextEdit::SelectWordLeft),
+            BindableAction::SelectWordRight => KeyboardAction::Content(TextEdit::SelectWordRight),
+            BindableAction::JumpToContentStart => {
+                KeyboardAction::Content(TextEdit::JumpToContentStart)
+            }
+            BindableAction::JumpToContentEnd => KeyboardAction::Content(TextEdit::JumpToContentEnd),
+            BindableAction::Debug => KeyboardAction::Debug,
+            BindableAction::OpenPreferences => KeyboardAction::OpenPreferences,
+            BindableAction::NewTask => KeyboardAction::NewTask,
+            BindableAction::SearchCursorWord => KeyboardAction::SearchCursorWord,
+            BindableAction::Paste => KeyboardAction::Paste,
+            BindableAction::RevertEntry => KeyboardAction::RevertEntry,
+            BindableAction::JumpToDate => KeyboardAction::JumpToDate,
+            BindableAction::SwitchFocusRegion => KeyboardAction::SwitchFocusRegion,
+            BindableAction::ToggleFocusMode => KeyboardAction::ToggleFocusMode,
+            BindableAction::PreviousTask => {
+                KeyboardAction::TaskNavigation(TaskNavigation::Previous)
+            }
+            BindableAction::NextTask => KeyboardAction::TaskNavigation(TaskNavigation::Next),
+            BindableAction::ToggleTask => KeyboardAction::TaskNavigation(TaskNavigation::Toggle),
+        }
+    }
+}
+
+/// groups the actions that share a binding. each group is a binding bound to more than one action, along with those
+/// actions
+pub fn conflicting_bindings(
+    custom_bindings: &BTreeMap<BindableAction, String>,
+) -> Vec<(String, Vec<BindableAction>)> {
+    let mut actions_by_binding: BTreeMap<String, Vec<BindableAction>> = BTreeMap::new();
+
+    for action in BindableAction::ALL {
+        actions_by_binding
+            .entry(action.binding(custom_bindings))
+            .or_default()
+            .push(action);
+    }
+
+    actions_by_binding
+        .into_iter()
+        .filter(|(_binding, actions)| actions.len() > 1)
+        .collect()
+}
+
+/// the binding of the key press, written the same way bindings are, like "Ctrl+Shift+j". None if the event isn't a key
+/// press or only a modifier was pressed, since a modifier can't be a binding on its own
+pub fn captured_binding(event: &keyboard::Event) -> Option<String> {
+    if !matches!(event, keyboard::Event::KeyPressed { .. }) {
+        return None;
+    }
+
+    let key_input = KeyInput::from(event.clone());
+
+    if matches!(key_input.key(), Key::Ignored) {
+        return None;
+    }
+
+    Some(key_input.to_string())
+}
+
+/// binds every action to its key chord. custom bindings that can't be parsed fall back to the action's default
 pub fn bind_keybinds() -> Keybinds<KeyboardAction> {
+    let custom_bindings = preferences().keyboard.custom_bindings.clone();
+
     let mut keybinds = Keybinds::default();
 
-    keybinds
-        .bind("Ctrl+s", KeyboardAction::Save)
-        .expect("couldn't bind Ctrl+s");
-    keybinds
-        .bind("Ctrl+z", KeyboardAction::Content(TextEdit::Undo))
-        .expect("couldn't bind Ctrl+z");
-    keybinds
-        .bind("Ctrl+Z", KeyboardAction::Content(TextEdit::Redo))
-        .expect("couldn't bind Ctrl+Z");
-    keybinds
-        .bind(
-            "Ctrl+Backspace",
-            KeyboardAction::Content(TextEdit::BackspaceWord),
-        )
-        .expect("couldn't bind Ctrl+Backspace");
-    keybinds
-        .bind(
-            "Ctrl+Shift+Backspace",
-            KeyboardAction::Content(TextEdit::BackspaceSentence),
-        )
-        .expect("couldn't bind Ctrl+Shift+Backspace");
-    keybinds
-        .bind("Ctrl+Delete", KeyboardAction::Content(TextEdit::DeleteWord))
-        .expect("couldn't bind Ctrl+Delete");
-    keybinds
-        .bind(
-            "Ctrl+Shift+Delete",
-            KeyboardAction::Content(TextEdit::DeleteSentence),
-        )
-        .expect("couldn't bind Ctrl+Shift+Delete");
-    keybinds
-        .bind("Ctrl+d", KeyboardAction::Debug)
-        .expect("couldn't bind Ctrl+d");
-    keybinds
-        .bind(
-            "Ctrl+Up",
-            KeyboardAction::Content(TextEdit::JumpToContentStart),
-        )
-        .expect("couldn't bind Ctrl+Up");
-    keybinds
-        .bind(
-            "Ctrl+Down",
-            KeyboardAction::Content(TextEdit::JumpToContentEnd),
-        )
-        .expect("couldn't bind Ctrl+Down");
+    for action in BindableAction::ALL {
+        let binding = action.binding(&custom_bindings);
+
+        if keybinds.bind(&binding, action.keyboard_action()).is_err() {
+            let default_binding = action.default_binding();
+
+            logbox_mut().log(&format!(
+                "Couldn't bind {binding} to {action}, using {default_binding} instead"
+            ));
+
+            keybinds
+                .bind(default_binding, action.keyboard_action())
+                .unwrap_or_else(|_| panic!("couldn't bind {default_binding}"));
+        }
+    }
 
     keybinds
 }
@@ -1,15 +1,25 @@
 use crate::{
-    config::{UserPreferences, overwrite_preferences, preferences},
+    config::{
+        ThemePreference, UserPreferences, overwrite_preferences, preferences, take_first_run,
+    },
     content::UpgradedContent,
-    dialogs::{DialogManager, DialogMessage, DialogType},
-    keyboard_manager::{KeyboardAction, bind_keybinds},
-    store::{GlobalStore, WordCount},
+    dialogs::{ConfirmedAction, DialogManager, DialogMessage, DialogType},
+    keyboard_manager::{self, KeyboardAction, bind_keybinds},
+    store::{Draft, GlobalStore, WordCount, journal_cipher},
     tasks::TaskManager,
+    utils::backup,
     utils::dictionary::reload_dictionary,
+    utils::logbox::logbox_mut,
+    utils::summon_hotkey,
+    utils::system_tray::{self, TrayAction},
+    windows::diagnostics_window::{Diagnostics, DiagnosticsMessage},
     windows::file_export_window::{FileExport, FileExportMessage},
     windows::file_import_window::{FileImport, FileImportMessage},
     windows::main_window::{Main, MainMessage},
-    windows::preferences_window::{Preferences, PreferencesMessage},
+    windows::preferences_window::{
+        KeybindsMessage, Preferences, PreferencesMessage, PreferencesTab,
+    },
+    windows::quick_capture_window::{QuickCapture, QuickCaptureMessage},
     windows::task_creator_window::{TaskCreator, TaskCreatorMessage},
     windows::window_manager::{WindowType, Windowable},
 };
@@ -43,7 +53,7 @@ struct SharedAppState {
 impl Default for SharedAppState {
     fn default() -> Self {
         let mut global_store = GlobalStore::default();
-        global_store.load_all();
+        global_store.load_startup();
         global_store.update_word_count();
 
         let content = UpgradedContent::with_text(&global_store.day().get_day_text());
@@ -59,6 +69,25 @@ impl Default for SharedAppState {
     }
 }
 
+#[cfg(test)]
+impl SharedAppState {
+    /// creates the state of a journal kept in the given storage, without any tasks. nothing is read from the disk
+    fn with_storage(storage: Box<dyn store::JournalStorage>) -> Self {
+        let mut global_store = GlobalStore::with_storage(storage);
+        global_store.load_startup();
+        global_store.update_word_count();
+
+        let content = UpgradedContent::with_text(&global_store.day().get_day_text());
+
+        Self {
+            upstream_actions: Vec::default(),
+            content,
+            global_store,
+            task_manager: TaskManager::without_tasks(),
+        }
+    }
+}
+
 struct App {
     shared_state: SharedAppState,
     keybinds: Keybinds<KeyboardAction>,
@@ -69,8 +98,13 @@ struct App {
     file_export_window: FileExport,
     task_creator_window: TaskCreator,
     preferences_window: Preferences,
+    diagnostics_window: Diagnostics,
+    quick_capture_window: QuickCapture,
 
     dialog_manager: DialogManager,
+
+    /// true while a backup is being copied in the background, so a slow backup is never overlapped by the next one
+    backup_running: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -79,14 +113,19 @@ pub enum Message {
     KeyEvent((KeyboardAction, window::Id)),
     WindowEvent((window::Event, window::Id)),
     WindowOpened(window::Id, WindowType),
+    WindowCloseRequested(window::Id),
     WindowClosed(window::Id),
     RenderAll,
+    RunBackup,
+    BackupFinished(Result<String, String>),
 
     MainWindow(MainMessage),
     FileImportWindow(FileImportMessage),
     FileExportWindow(FileExportMessage),
     TaskCreatorWindow(TaskCreatorMessage),
     PreferencesWindow(PreferencesMessage),
+    DiagnosticsWindow(DiagnosticsMessage),
+    QuickCaptureWindow(QuickCaptureMessage),
 
     DialogOpened(window::Id, DialogType, String),
     DialogClosed(window::Id, DialogType),
@@ -97,13 +136,31 @@ pub enum Message {
 /// allows for windows to pass up requests to be done by the main application, since they don't have access to the main
 /// application Messages
 pub enum UpstreamAction {
+    /// opens a window of the given type, or focuses it if one is already open
     CreateWindow(WindowType),
+    /// closes all windows of the given type
     CloseWindow(WindowType),
+    /// saves the active entry, store, and tasks through the main window's save logic
     Autosave,
+    /// performs a software restart of the application from its bootup configuration
     RestartApplication,
-
+    /// applies the given preferences and reloads the journal from its new paths in place
+    ReloadJournal(UserPreferences),
+    /// appends the given note to the end of today's entry and saves, through the main window
+    AppendToToday(String),
+    /// reloads the journal now that it can be decrypted
+    JournalUnlocked,
+    /// rebinds the keyboard shortcuts from the current preferences
+    ReloadKeybinds,
+    /// exits the application right away, without checking for unsaved changes
+    ExitApplication,
+
+    /// opens a new dialog window of the given type with the given text
     OpenDialog(DialogType, String),
+    /// closes the dialog window with the given id
     CloseDialog(window::Id, DialogType),
+    /// performs an action the user has confirmed through a confirmation dialog
+    PerformConfirmed(ConfirmedAction),
 }
 
 impl App {
@@ -115,13 +172,94 @@ impl App {
         let mut app = Self::default();
 
         let generate_window = task.map(move |id| Message::WindowOpened(id, window_type.clone()));
-        let jump_today = app.update(Message::MainWindow(MainMessage::JumpToToday));
+        let load_today = app.update(Message::MainWindow(MainMessage::LoadToday));
+
+        let mut tasks = vec![generate_window, load_today];
+
+        if take_first_run() && !preferences().paths.system_dictionary_exists() {
+            tasks.push(app.first_run_onboarding());
+        }
 
-        let tasks = vec![generate_window, jump_today];
+        tasks.push(app.offer_journal_unlock());
+        tasks.push(app.offer_draft_recovery());
+
+        if preferences().general.minimize_to_tray {
+            system_tray::start_tray();
+        }
+
+        let general_prefs = preferences().general.clone();
+        summon_hotkey::sync_summon_hotkey(
+            general_prefs.summon_hotkey_enabled,
+            &general_prefs.summon_hotkey,
+        );
 
         (app, Task::batch(tasks))
     }
 
+    /// guides the user to set up the dictionary paths, since the default guesses often don't exist on a fresh install
+    /// and spellcheck would otherwise be silently unavailable
+    fn first_run_onboarding(&mut self) -> Task<Message> {
+        let dialog_text = "No spellcheck dictionary was found. Select the system dictionary .aff and .dic files in \
+            the Paths tab of the preferences to enable spellcheck"
+            .to_string();
+
+        self.shared_state
+            .upstream_actions
+            .push(UpstreamAction::CreateWindow(WindowType::Preferences));
+        self.shared_state
+            .upstream_actions
+            .push(UpstreamAction::OpenDialog(DialogType::Warning, dialog_text));
+
+        let onboarding_task = self.perform_upstream_actions();
+
+        let paths_tab_task = self
+            .preferences_window
+            .update(
+                &mut self.shared_state,
+                PreferencesMessage::TabSwitched(PreferencesTab::Paths),
+            )
+            .map(Message::PreferencesWindow);
+
+        Task::batch(vec![onboarding_task, paths_tab_task])
+    }
+
+    /// if drafts were left behind by an unclean exit and differ from the saved entries, asks the user if the drafts
+    /// should be recovered
+    fn offer_draft_recovery(&mut self) -> Task<Message> {
+        let mut drafts = Draft::load_from_disk();
+
+        drafts.retain(|draft| {
+            self.shared_state
+                .global_store
+                .get_day(draft.date())
+                .is_none_or(|day_store| day_store.get_day_text() != draft.text())
+        });
+
+        if drafts.is_empty() {
+            return Task::none();
+        }
+
+        let draft_dates = drafts
+            .iter()
+            .map(|draft| draft.date().to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let dialog_text = format!(
+            "Unsaved changes to the entries on {} were found from a previous session. Recover them?",
+            draft_dates
+        );
+
+        self.shared_state
+            .upstream_actions
+            .push(UpstreamAction::OpenDialog(
+                DialogType::Confirmation(ConfirmedAction::RecoverDrafts(drafts)),
+                dialog_text,
+            ));
+
+        self.perform_upstream_actions()
+    }
+
     /// performs a "software restart" of the application that closes all open windows and effectively relaunches the
     /// program from its bootup configuration, without actually quitting out of the application
     fn restart_without_saving(&mut self) -> Task<Message> {
@@ -140,9 +278,64 @@ impl App {
         overwrite_preferences(UserPreferences::load_from_disk_or_default());
         reload_dictionary();
 
+        logbox_mut().log("Restarted to apply new preferences");
+
         batch_close_windows.chain(init_message)
     }
 
+    /// applies the new preferences and reloads the journal, tasks, and dictionary from the new paths in place, without
+    /// closing any windows. any unsaved work should be saved before this is called, since the old state is discarded
+    fn reload_journal(&mut self, new_preferences: UserPreferences) -> Task<Message> {
+        overwrite_preferences(new_preferences);
+        reload_dictionary();
+
+        // the key belongs to the journal it was unlocked for, which may not be the journal being switched to
+        journal_cipher::lock();
+
+        let reload_task = self.reload_store();
+
+        Task::batch([reload_task, self.offer_journal_unlock()])
+    }
+
+    /// discards the journal and tasks held in memory and reads them back from the disk
+    fn reload_store(&mut self) -> Task<Message> {
+        self.shared_state = SharedAppState::default();
+        self.main_window = Main::default();
+
+        self.update(Message::MainWindow(MainMessage::LoadToday))
+    }
+
+    /// asks for the password of an encrypted journal. until it is given, the journal is shown empty and nothing in it
+    /// is written
+    fn offer_journal_unlock(&mut self) -> Task<Message> {
+        if !journal_cipher::journal_locked() {
+            return Task::none();
+        }
+
+        let dialog_text =
+            "The journal is encrypted. Enter its password to open it. Until it is unlocked, no changes are saved"
+                .to_string();
+
+        self.shared_state
+            .upstream_actions
+            .push(UpstreamAction::OpenDialog(
+                DialogType::Password,
+                dialog_text,
+            ));
+
+        self.perform_upstream_actions()
+    }
+
+    /// the theme of every window, as picked in the preferences. the system theme is left up to iced, which follows the
+    /// light or dark mode of the operating system
+    fn theme(&self, _id: window::Id) -> Option<Theme> {
+        match preferences().general.theme {
+            ThemePreference::Light => Some(Theme::Light),
+            ThemePreference::Dark => Some(Theme::Dark),
+            ThemePreference::System => None,
+        }
+    }
+
     fn title(&self, id: window::Id) -> String {
         if let Some(window_type) = self.windows.get(&id) {
             match window_type {
@@ -152,6 +345,8 @@ impl App {
                 WindowType::FileExport => self.file_export_window.title(),
                 WindowType::TaskCreator => self.task_creator_window.title(),
                 WindowType::Preferences => self.preferences_window.title(),
+                WindowType::Diagnostics => self.diagnostics_window.title(),
+                WindowType::QuickCapture => self.quick_capture_window.title(),
             }
         } else if let Some(dialog_title) = self.dialog_manager.get_title(id) {
             dialog_title
@@ -183,6 +378,14 @@ impl App {
                     .preferences_window
                     .view(&self.shared_state)
                     .map(Message::PreferencesWindow),
+                WindowType::Diagnostics => self
+                    .diagnostics_window
+                    .view(&self.shared_state)
+                    .map(Message::DiagnosticsWindow),
+                WindowType::QuickCapture => self
+                    .quick_capture_window
+                    .view(&self.shared_state)
+                    .map(Message::QuickCaptureWindow),
             }
         } else if let Some(dialog_view) = self.dialog_manager.get_view(id, &self.shared_state) {
             dialog_view.map(move |dialog_message| Message::DialogUpdate(id, dialog_message))
@@ -198,11 +401,49 @@ impl App {
             Message::WindowOpened(new_window_id, new_window_type) => {
                 self.windows.insert(new_window_id, new_window_type);
             }
+            Message::WindowCloseRequested(id) => {
+                // only the main window waits for its close request to be answered, every other window closes on its own
+                if self.windows.get(&id) == Some(&WindowType::Main) {
+                    // staying behind in the tray autosaves, so nothing would be lost by closing
+                    let staying_in_tray =
+                        preferences().general.minimize_to_tray && system_tray::tray_running();
+
+                    if !staying_in_tray && self.main_window.has_unsaved_changes(&self.shared_state)
+                    {
+                        self.shared_state
+                            .upstream_actions
+                            .push(UpstreamAction::OpenDialog(
+                                DialogType::UnsavedChanges,
+                                "There are unsaved changes. Save them before exiting?".to_string(),
+                            ));
+                    } else {
+                        tasks.push(window::close(id));
+                    }
+                }
+            }
             Message::WindowClosed(id) => {
                 if let Some(window_closed) = self.windows.get(&id)
                     && *window_closed == WindowType::Main
                 {
-                    tasks.push(iced::exit());
+                    // the application only stays behind in the tray if the tray actually exists, otherwise there
+                    // would be no way back into it
+                    if preferences().general.minimize_to_tray && system_tray::tray_running() {
+                        tasks.push(self.update(Message::MainWindow(MainMessage::Autosave)));
+
+                        logbox_mut().log("Still running in the system tray");
+                    } else {
+                        tasks.push(iced::exit());
+                    }
+                }
+
+                // the quick capture window is only a detour, so closing it goes back to the main window if it's open
+                if self.windows.get(&id) == Some(&WindowType::QuickCapture)
+                    && let Some((main_id, _window_type)) = self
+                        .windows
+                        .iter()
+                        .find(|(_window_id, window_type)| **window_type == WindowType::Main)
+                {
+                    tasks.push(window::gain_focus(*main_id));
                 }
 
                 self.windows.remove(&id);
@@ -211,9 +452,62 @@ impl App {
                 for window_id in self.windows.keys() {
                     self.view(*window_id);
                 }
+
+                if preferences().general.minimize_to_tray {
+                    system_tray::start_tray();
+                }
+
+                let general_prefs = preferences().general.clone();
+                summon_hotkey::sync_summon_hotkey(
+                    general_prefs.summon_hotkey_enabled,
+                    &general_prefs.summon_hotkey,
+                );
+
+                // opening the main window focuses it instead if it is already open
+                if summon_hotkey::summon_hotkey_pressed() {
+                    self.shared_state
+                        .upstream_actions
+                        .push(UpstreamAction::CreateWindow(WindowType::Main));
+                }
+
+                while let Some(tray_action) = system_tray::next_tray_action() {
+                    match tray_action {
+                        TrayAction::Open => {
+                            self.shared_state
+                                .upstream_actions
+                                .push(UpstreamAction::CreateWindow(WindowType::Main));
+                        }
+                        TrayAction::Quit => {
+                            tasks.push(self.update(Message::MainWindow(MainMessage::Autosave)));
+                            tasks.push(iced::exit());
+                        }
+                    }
+                }
+
+                if self
+                    .windows
+                    .values()
+                    .any(|window_type| *window_type == WindowType::Main)
+                {
+                    self.main_window.write_draft(&self.shared_state);
+
+                    if self.main_window.sentence_save_due() {
+                        tasks.push(self.update(Message::MainWindow(MainMessage::Autosave)));
+                    }
+                }
             }
             Message::CapturedKeyEvent((event, id)) => {
-                if let Some(action) = self.keybinds.dispatch(event) {
+                // while a new binding is being picked in the preferences, the key press is the binding itself rather
+                // than a shortcut
+                if self.windows.get(&id) == Some(&WindowType::Preferences)
+                    && self.preferences_window.capturing_binding()
+                {
+                    if let Some(binding) = keyboard_manager::captured_binding(&event) {
+                        tasks.push(self.update(Message::PreferencesWindow(
+                            PreferencesMessage::Keybinds(KeybindsMessage::BindingCaptured(binding)),
+                        )));
+                    }
+                } else if let Some(action) = self.keybinds.dispatch(event) {
                     let key_action = action.clone();
 
                     tasks.push(self.update(Message::KeyEvent((key_action, id))));
@@ -247,6 +541,16 @@ impl App {
                                 PreferencesMessage::KeyEvent(keyboard_action),
                             )));
                         }
+                        WindowType::Diagnostics => {
+                            tasks.push(self.update(Message::DiagnosticsWindow(
+                                DiagnosticsMessage::KeyEvent(keyboard_action),
+                            )));
+                        }
+                        WindowType::QuickCapture => {
+                            tasks.push(self.update(Message::QuickCaptureWindow(
+                                QuickCaptureMessage::KeyEvent(keyboard_action),
+                            )));
+                        }
                     }
                 }
             }
@@ -262,6 +566,8 @@ impl App {
                         WindowType::FileExport => {}
                         WindowType::TaskCreator => {}
                         WindowType::Preferences => {}
+                        WindowType::Diagnostics => {}
+                        WindowType::QuickCapture => {}
                     }
                 }
             }
@@ -304,6 +610,22 @@ impl App {
 
                 tasks.push(preferences_task);
             }
+            Message::DiagnosticsWindow(diagnostics_message) => {
+                let diagnostics_task = self
+                    .diagnostics_window
+                    .update(&mut self.shared_state, diagnostics_message)
+                    .map(Message::DiagnosticsWindow);
+
+                tasks.push(diagnostics_task);
+            }
+            Message::QuickCaptureWindow(quick_capture_message) => {
+                let quick_capture_task = self
+                    .quick_capture_window
+                    .update(&mut self.shared_state, quick_capture_message)
+                    .map(Message::QuickCaptureWindow);
+
+                tasks.push(quick_capture_task);
+            }
             Message::DialogOpened(window_id, dialog_type, dialog_text) => {
                 self.dialog_manager
                     .insert_dialog(window_id, dialog_type, dialog_text);
@@ -321,24 +643,101 @@ impl App {
             Message::DialogClosed(window_id, dialog_type) => {
                 self.dialog_manager.remove_dialog(window_id, dialog_type);
             }
+            Message::RunBackup => {
+                if !self.backup_running {
+                    self.backup_running = true;
+
+                    let path_prefs = preferences().paths.clone();
+                    let retention_count = preferences().general.backup_retention_count;
+                    let retention_days = preferences().general.backup_retention_days;
+
+                    // the copy runs on the executor's threads rather than in update, so a large journal doesn't
+                    // stall the UI while it is backed up
+                    tasks.push(Task::perform(
+                        async move {
+                            backup::backup_savedata(
+                                &path_prefs.savedata_dir(),
+                                &path_prefs.backups_dir(),
+                                retention_count,
+                                retention_days,
+                            )
+                            .map(|backup_dir| backup_dir.display().to_string())
+                            .map_err(|error| error.to_string())
+                        },
+                        Message::BackupFinished,
+                    ));
+                }
+            }
+            Message::BackupFinished(backup_result) => {
+                self.backup_running = false;
+
+                match backup_result {
+                    Ok(backup_dir) => {
+                        logbox_mut().log(&format!("Backed up journal to {backup_dir}"))
+                    }
+                    Err(error) => logbox_mut().log(&format!("Backup failed: {error}")),
+                }
+            }
         }
 
+        tasks.push(self.perform_upstream_actions());
+
+        Task::batch(tasks)
+    }
+
+    /// starts the window of the given type over, as if it was opened for the first time
+    fn reset_window(&mut self, window_type: &WindowType) -> Task<Message> {
+        match window_type {
+            WindowType::TaskCreator => self
+                .task_creator_window
+                .update(&mut self.shared_state, TaskCreatorMessage::Reset)
+                .map(Message::TaskCreatorWindow),
+            WindowType::Main
+            | WindowType::FileImport
+            | WindowType::FileExport
+            | WindowType::Preferences
+            | WindowType::Diagnostics
+            | WindowType::QuickCapture => Task::none(),
+        }
+    }
+
+    /// performs all of the actions the windows have requested since the last update, clearing them afterwards
+    fn perform_upstream_actions(&mut self) -> Task<Message> {
+        let mut tasks = vec![Task::none()];
+
         for upstream_action in self.shared_state.upstream_actions.clone() {
             match upstream_action {
                 UpstreamAction::CreateWindow(window_type) => {
                     let new_window_type = window_type.clone();
 
-                    let mut window_already_exists = false;
+                    // the diagnostics report is a snapshot, so it is rebuilt every time the window is brought up
+                    if new_window_type == WindowType::Diagnostics {
+                        tasks.push(
+                            self.diagnostics_window
+                                .update(&mut self.shared_state, DiagnosticsMessage::Refresh)
+                                .map(Message::DiagnosticsWindow),
+                        );
+                    }
 
-                    for (window_id, window_type) in &self.windows {
-                        if new_window_type == *window_type {
-                            tasks.push(window::gain_focus(*window_id));
-                            window_already_exists = true;
-                            break;
+                    let existing_window_id = self
+                        .windows
+                        .iter()
+                        .find(|(_window_id, window_type)| **window_type == new_window_type)
+                        .map(|(window_id, _window_type)| *window_id);
+
+                    if let Some(window_id) = existing_window_id {
+                        if new_window_type.resets_on_reopen() {
+                            tasks.push(self.reset_window(&new_window_type));
+                        }
+
+                        tasks.push(window::gain_focus(window_id));
+                    } else {
+                        // the preference editor works on a copy of the global preferences, so it needs to be
+                        // refreshed to pick up any changes made elsewhere since it was last opened
+                        if new_window_type == WindowType::Preferences {
+                            self.preferences_window = Preferences::default();
                         }
-                    }
 
-                    if !window_already_exists {
                         let (_new_id, task) = iced::window::open(new_window_type.settings());
                         tasks.push(
                             task.map(move |id| Message::WindowOpened(id, new_window_type.clone())),
@@ -353,18 +752,56 @@ impl App {
                     }
                 }
                 UpstreamAction::Autosave => {
-                    let autosave_task = self
-                        .main_window
-                        .update(&mut self.shared_state, MainMessage::Autosave)
-                        .map(Message::MainWindow);
-
-                    tasks.push(autosave_task);
+                    // the active entry only lives in the main window, so there is nothing to save without it
+                    let main_window_open = self
+                        .windows
+                        .values()
+                        .any(|window_type| *window_type == WindowType::Main);
+
+                    if main_window_open {
+                        let autosave_task = self
+                            .main_window
+                            .update(&mut self.shared_state, MainMessage::Autosave)
+                            .map(Message::MainWindow);
+
+                        tasks.push(autosave_task);
+                    }
                 }
                 UpstreamAction::RestartApplication => {
                     let restart_task = self.restart_without_saving();
 
                     tasks.push(restart_task);
                 }
+                UpstreamAction::ReloadJournal(new_preferences) => {
+                    let reload_task = self.reload_journal(new_preferences);
+
+                    tasks.push(reload_task);
+                }
+                UpstreamAction::JournalUnlocked => {
+                    let reload_task = self.reload_store();
+
+                    tasks.push(reload_task);
+
+                    // the draft is encrypted along with the journal, so it couldn't be offered on startup
+                    tasks.push(self.offer_draft_recovery());
+                }
+                UpstreamAction::ExitApplication => {
+                    tasks.push(iced::exit());
+                }
+                UpstreamAction::ReloadKeybinds => {
+                    self.keybinds = bind_keybinds();
+                }
+                UpstreamAction::AppendToToday(capture_text) => {
+                    let append_task = self
+                        .main_window
+                        .update(
+                            &mut self.shared_state,
+                            MainMessage::AppendToToday(capture_text),
+                        )
+                        .map(Message::MainWindow);
+
+                    tasks.push(append_task);
+                }
                 UpstreamAction::OpenDialog(dialog_type, dialog_text) => {
                     let (_window_id, task) = window::open(DialogManager::dialog_window_settings());
 
@@ -379,12 +816,26 @@ impl App {
                     tasks.push(open_dialog_task);
                 }
                 UpstreamAction::CloseDialog(window_id, dialog_type) => match dialog_type {
-                    DialogType::Warning => {
+                    DialogType::Warning
+                    | DialogType::Confirmation(_)
+                    | DialogType::Password
+                    | DialogType::UnsavedChanges => {
                         let close_task = window::close(window_id);
 
                         tasks.push(close_task);
                     }
                 },
+                UpstreamAction::PerformConfirmed(confirmed_action) => {
+                    let confirmed_task = self
+                        .main_window
+                        .update(
+                            &mut self.shared_state,
+                            MainMessage::PerformConfirmed(confirmed_action),
+                        )
+                        .map(Message::MainWindow);
+
+                    tasks.push(confirmed_task);
+                }
             }
         }
 
@@ -394,6 +845,7 @@ impl App {
     }
 
     fn subscription(&self) -> Subscription<Message> {
+        let close_requests = iced::window::close_requests().map(Message::WindowCloseRequested);
         let close_events = iced::window::close_events().map(Message::WindowClosed);
 
         let listener = listen_with(|event, _status, id| match event {
@@ -403,6 +855,7 @@ impl App {
         });
 
         let mut subscriptions = vec![
+            close_requests,
             close_events,
             listener,
             // ensure view() gets called at a minimum of 10 FPS
@@ -417,6 +870,13 @@ impl App {
             );
         }
 
+        if preferences().general.backups_enabled {
+            subscriptions.push(
+                iced::time::every(preferences().general.backup_interval)
+                    .map(|_instant| Message::RunBackup),
+            );
+        }
+
         Subscription::batch(subscriptions)
     }
 }
@@ -433,8 +893,12 @@ impl Default for App {
             file_export_window: FileExport::default(),
             task_creator_window: TaskCreator::default(),
             preferences_window: Preferences::default(),
+            diagnostics_window: Diagnostics::default(),
+            quick_capture_window: QuickCapture::default(),
 
             dialog_manager: DialogManager::default(),
+
+            backup_running: false,
         }
     }
 }
@@ -443,6 +907,6 @@ fn main() -> Result<(), iced::Error> {
     iced::daemon(App::new, App::update, App::view)
         .subscription(App::subscription)
         .title(App::title)
-        .theme(Theme::Light)
+        .theme(App::theme)
         .run()
 }
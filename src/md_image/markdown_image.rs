@@ -12,7 +12,10 @@ use image::{DynamicImage, imageops::FilterType};
 use regex::Regex;
 use std::{collections::HashMap, path::PathBuf, sync::LazyLock};
 
-use crate::{config::font_settings::markdown_settings, ui::styling::TOOLTIP_DELAY};
+use crate::{
+    config::{font_settings::markdown_settings, preferences},
+    ui::styling::TOOLTIP_DELAY,
+};
 
 #[derive(Debug)]
 /// Denotes what the markdown is, standard markdown text or a parsed image
@@ -200,9 +203,14 @@ pub fn parse(markdown: &str, image_cache: &mut ImageCache) -> Vec<ParsedMarkdown
                 continue;
             }
             MarkdownElement::Image(parsed_image) => {
-                let path_string = parsed_image.path.clone();
-
-                let image_path = PathBuf::from(path_string.clone());
+                // relative paths, like those of pasted attachments, are relative to the journal directory. joining
+                // an absolute path replaces the journal directory entirely, so absolute paths are opened as written
+                let image_path = preferences()
+                    .paths
+                    .journal_path
+                    .join(PathBuf::from(&parsed_image.path));
+
+                let path_string = image_path.to_string_lossy().into_owned();
 
                 if !image_cache.disk_images.contains_key(&path_string)
                     && let Ok(image) = image::open(image_path)
@@ -7,7 +7,18 @@ use chrono::NaiveDate;
 pub struct DayStore {
     date: NaiveDate,
     entry_text: String,
+
+    /// the entry text as it was when the day was last saved or loaded
+    saved_text: String,
+
     modified: bool,
+    unsaved: bool,
+    locked: bool,
+    tags: Vec<String>,
+
+    /// how many seconds were spent actively editing the entry
+    writing_seconds: u64,
+
     word_counts: WordCounts,
 }
 
@@ -16,7 +27,12 @@ impl DayStore {
         Self {
             date,
             entry_text: String::default(),
+            saved_text: String::default(),
             modified: false,
+            unsaved: false,
+            locked: false,
+            tags: Vec::new(),
+            writing_seconds: 0,
             word_counts: WordCounts::default(),
         }
     }
@@ -25,8 +41,13 @@ impl DayStore {
     pub fn with_day_text(date: NaiveDate, inital_text: String) -> Self {
         Self {
             date,
+            saved_text: inital_text.clone(),
             entry_text: inital_text,
             modified: false,
+            unsaved: false,
+            locked: false,
+            tags: Vec::new(),
+            writing_seconds: 0,
             word_counts: WordCounts::default(),
         }
     }
@@ -35,13 +56,34 @@ impl DayStore {
         self.entry_text.clone()
     }
 
+    /// replaces the entry text. writing back the same text, or blank text into a day that is already blank, isn't a
+    /// change, so merely visiting a day never marks it as modified and never gets it written on save
     pub fn set_day_text(&mut self, new_text: String) {
+        let blank_to_blank = !self.contains_entry() && (new_text.is_empty() || new_text == "\n");
+
+        if new_text == self.entry_text || blank_to_blank {
+            return;
+        }
+
         self.entry_text = new_text;
         self.modified = true;
+        self.unsaved = true;
 
         self.word_counts.set_sync(false);
     }
 
+    /// appends the text to the end of the entry. the separator is placed between the existing entry and the new text,
+    /// but only if the day already contains an entry. otherwise, the entry is replaced by the new text
+    pub fn append_text(&mut self, text: &str, separator: &str) {
+        let new_text = if self.contains_entry() {
+            self.entry_text.clone() + separator + text
+        } else {
+            text.to_string()
+        };
+
+        self.set_day_text(new_text);
+    }
+
     pub fn date(&self) -> NaiveDate {
         self.date
     }
@@ -53,6 +95,93 @@ impl DayStore {
     pub fn modified(&self) -> bool {
         self.modified
     }
+
+    /// a locked day is read only. the entry text can't be edited until the day is unlocked again
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn set_locked(&mut self, locked: bool) {
+        if locked != self.locked {
+            self.unsaved = true;
+        }
+
+        self.locked = locked;
+    }
+
+    /// true if the day changed since it was last saved or loaded. unlike modified, this is reset by every save, and
+    /// writing the same text back into the day doesn't count as a change
+    pub fn unsaved(&self) -> bool {
+        self.unsaved
+    }
+
+    /// marks the current state of the day as saved
+    pub fn mark_saved(&mut self) {
+        self.unsaved = false;
+        self.saved_text = self.entry_text.clone();
+    }
+
+    /// the entry text as it was when the day was last saved or loaded, for reverting any edits made since
+    pub fn saved_text(&self) -> &str {
+        &self.saved_text
+    }
+
+    /// how many seconds were spent actively editing the entry
+    pub fn writing_seconds(&self) -> u64 {
+        self.writing_seconds
+    }
+
+    /// adds the seconds to the time spent editing the entry
+    pub fn add_writing_seconds(&mut self, seconds: u64) {
+        if seconds > 0 {
+            self.writing_seconds += seconds;
+            self.unsaved = true;
+        }
+    }
+
+    /// the freeform tags attached to the day, in the order they were added
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// attaches the tag to the day. surrounding whitespace is trimmed, and empty tags or tags the day already has
+    /// (ignoring case) aren't added. returns true if the tag was added
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+
+        if tag.is_empty() || self.has_tag(tag) {
+            return false;
+        }
+
+        self.tags.push(tag.to_string());
+        self.unsaved = true;
+
+        true
+    }
+
+    /// removes the tag from the day, if the day has it. returns true if the tag was removed
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let tag_count = self.tags.len();
+        let removed_tag = tag.trim().to_lowercase();
+
+        self.tags
+            .retain(|existing_tag| existing_tag.to_lowercase() != removed_tag);
+
+        let removed = self.tags.len() != tag_count;
+
+        if removed {
+            self.unsaved = true;
+        }
+
+        removed
+    }
+
+    /// true if the day has the tag, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags
+            .iter()
+            .any(|existing_tag| existing_tag.to_lowercase() == tag.trim().to_lowercase())
+    }
 }
 
 impl WordCount for DayStore {
@@ -96,3 +225,41 @@ impl WordCount for DayStore {
         self.word_counts.total_char_count()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 14).expect("invalid date")
+    }
+
+    #[test]
+    fn appending_to_an_empty_entry_skips_the_separator() {
+        let mut day_store = DayStore::new(date());
+
+        day_store.append_text("first thought", "\n\n");
+
+        assert_eq!(day_store.get_day_text(), "first thought");
+        assert!(day_store.modified());
+    }
+
+    #[test]
+    fn appending_to_a_blank_line_entry_skips_the_separator() {
+        let mut day_store = DayStore::with_day_text(date(), "\n".to_string());
+
+        day_store.append_text("first thought", "\n\n");
+
+        assert_eq!(day_store.get_day_text(), "first thought");
+    }
+
+    #[test]
+    fn appending_to_an_existing_entry_adds_the_separator() {
+        let mut day_store = DayStore::with_day_text(date(), "first thought".to_string());
+
+        day_store.append_text("second thought", "\n\n");
+
+        assert_eq!(day_store.get_day_text(), "first thought\n\nsecond thought");
+        assert!(day_store.modified());
+    }
+}
@@ -0,0 +1,227 @@
+use super::journal_cipher;
+use crate::config::preferences;
+use crate::utils::logbox::logbox_mut;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// a recovery copy of an entry's unsaved text. the draft is written periodically while editing and removed whenever the
+/// journal is saved, so a draft existing on startup means the application exited before its last edits were saved.
+/// every day edited since the last save keeps its own draft, so moving on to edit another day doesn't lose the edits
+/// of the previous one
+pub struct Draft {
+    /// the date of the entry the draft belongs to
+    date: NaiveDate,
+
+    /// the unsaved text of the entry
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+/// the layouts the draft file has been written in. it used to hold only the draft of the last edited day
+enum DraftFile {
+    Drafts(Vec<Draft>),
+    SingleDraft(Draft),
+}
+
+impl Draft {
+    /// creates a new Draft of the given entry text
+    pub fn new(date: NaiveDate, text: String) -> Self {
+        Self { date, text }
+    }
+
+    /// the location of the draft file, savedata/entry.draft. since it doesn't match the month filename format, it is
+    /// never loaded as part of the store
+    fn draft_file() -> PathBuf {
+        let mut draft_path = preferences().paths.savedata_dir();
+        draft_path.push("entry.draft");
+
+        draft_path
+    }
+
+    /// reads every draft from the disk, sorted by date. empty if there is no draft file, or it isn't valid
+    pub fn load_from_disk() -> Vec<Self> {
+        let draft_file = journal_cipher::read_journal_file(&Self::draft_file())
+            .and_then(|draft_json| serde_json::from_str(&draft_json).ok());
+
+        let mut drafts = match draft_file {
+            Some(DraftFile::Drafts(drafts)) => drafts,
+            Some(DraftFile::SingleDraft(draft)) => vec![draft],
+            None => Vec::new(),
+        };
+
+        drafts.sort_by_key(|draft| draft.date);
+
+        drafts
+    }
+
+    /// writes the draft to the disk, replacing any existing draft of the same day. the drafts of other days are kept,
+    /// since their edits are still unsaved
+    pub fn write_to_disk(&self) -> io::Result<()> {
+        let mut drafts = Self::load_from_disk();
+        drafts.retain(|draft| draft.date != self.date);
+        drafts.push(self.clone());
+
+        Self::write_drafts(drafts)
+    }
+
+    /// writes every draft back to the disk, so the draft file matches the current encryption setting
+    pub fn rewrite_on_disk() -> io::Result<()> {
+        let drafts = Self::load_from_disk();
+
+        if drafts.is_empty() {
+            return Ok(());
+        }
+
+        Self::write_drafts(drafts)
+    }
+
+    /// removes the draft of the given day from the disk, if there is one. failing to remove it is only logged, since
+    /// the worst case is being offered edits that were already dealt with
+    pub fn remove_from_disk(date: NaiveDate) {
+        let mut drafts = Self::load_from_disk();
+        let draft_count = drafts.len();
+
+        drafts.retain(|draft| draft.date != date);
+
+        if drafts.len() == draft_count {
+            return;
+        }
+
+        if let Err(draft_error) = Self::write_drafts(drafts) {
+            logbox_mut().log(&format!(
+                "Couldn't remove the recovery draft of {}: {}",
+                date, draft_error
+            ));
+        }
+    }
+
+    /// removes every draft from the disk. failing to remove them is only logged, like in remove_from_disk
+    pub fn clear_from_disk() {
+        if let Err(draft_error) = Self::write_drafts(Vec::new()) {
+            logbox_mut().log(&format!(
+                "Couldn't remove the recovery drafts: {}",
+                draft_error
+            ));
+        }
+    }
+
+    /// replaces the draft file with the given drafts, removing the file if there are none. like every other journal
+    /// file, nothing is written or removed while the journal is locked, since the store is empty until it is unlocked
+    /// and the drafts may be the only copy of the unsaved edits
+    fn write_drafts(drafts: Vec<Self>) -> io::Result<()> {
+        let draft_file = Self::draft_file();
+
+        if journal_cipher::journal_locked() || !journal_cipher::journal_file_writable(&draft_file) {
+            return Ok(());
+        }
+
+        if drafts.is_empty() {
+            if draft_file.exists() {
+                fs::remove_file(draft_file)?;
+            }
+
+            return Ok(());
+        }
+
+        let draft_json = serde_json::to_string_pretty(&drafts)?;
+
+        journal_cipher::write_journal_file(&draft_file, &draft_json)
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{journal_pointer, with_test_preferences};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    /// runs the test with the drafts kept in a fresh savedata directory
+    fn with_draft_dir(dir_name: &str, test: impl FnOnce()) {
+        let journal_path = journal_pointer::test_dir().join(dir_name);
+        let _ = fs::remove_dir_all(&journal_path);
+
+        with_test_preferences(
+            |preferences| preferences.paths.journal_path = journal_path,
+            || {
+                fs::create_dir_all(preferences().paths.savedata_dir())
+                    .expect("couldn't create savedata directory");
+
+                test();
+            },
+        );
+    }
+
+    #[test]
+    fn drafts_of_different_days_are_kept_apart() {
+        with_draft_dir("separate-drafts", || {
+            Draft::new(date(2024, 5, 2), "second day".to_string())
+                .write_to_disk()
+                .expect("couldn't write draft");
+            Draft::new(date(2024, 5, 1), "first day".to_string())
+                .write_to_disk()
+                .expect("couldn't write draft");
+            Draft::new(date(2024, 5, 2), "second day, edited".to_string())
+                .write_to_disk()
+                .expect("couldn't write draft");
+
+            assert_eq!(
+                Draft::load_from_disk(),
+                vec![
+                    Draft::new(date(2024, 5, 1), "first day".to_string()),
+                    Draft::new(date(2024, 5, 2), "second day, edited".to_string()),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn removing_a_draft_keeps_the_other_days() {
+        with_draft_dir("remove-draft", || {
+            Draft::new(date(2024, 5, 1), "first day".to_string())
+                .write_to_disk()
+                .expect("couldn't write draft");
+            Draft::new(date(2024, 5, 2), "second day".to_string())
+                .write_to_disk()
+                .expect("couldn't write draft");
+
+            Draft::remove_from_disk(date(2024, 5, 1));
+
+            assert_eq!(
+                Draft::load_from_disk(),
+                vec![Draft::new(date(2024, 5, 2), "second day".to_string())]
+            );
+
+            Draft::clear_from_disk();
+
+            assert!(Draft::load_from_disk().is_empty());
+            assert!(!Draft::draft_file().exists());
+        });
+    }
+
+    #[test]
+    fn single_draft_files_are_still_read() {
+        with_draft_dir("single-draft", || {
+            let single_draft = Draft::new(date(2024, 5, 1), "from before".to_string());
+            let draft_json =
+                serde_json::to_string_pretty(&single_draft).expect("couldn't serialize");
+            fs::write(Draft::draft_file(), draft_json).expect("couldn't write draft file");
+
+            assert_eq!(Draft::load_from_disk(), vec![single_draft]);
+        });
+    }
+}
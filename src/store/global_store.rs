@@ -1,40 +1,80 @@
+use super::JournalStorage;
+use super::SearchIndex;
 use super::TimedWordCount;
 use super::WordCount;
 use super::WordCounts;
+use super::journal_cipher;
+use super::journal_storage;
+use super::journal_storage::MonthJson;
 use super::{DayStore, MonthStore};
-use crate::config::preferences;
+use crate::config::{StorageBackend, preferences};
+use crate::utils::logbox::logbox_mut;
 use crate::utils::misc_tools;
 
 use chrono::{Datelike, Days, Local, Months, NaiveDate};
-use regex::Regex;
-use std::sync::LazyLock;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
 
 #[derive(Debug)]
 pub struct GlobalStore {
     entries: Vec<MonthStore>,
     current_date: NaiveDate,
     word_counts: WordCounts,
+    storage: Box<dyn JournalStorage>,
+
+    /// the "YYYY-MM" keys of the months in the storage that haven't been loaded into the store yet
+    unloaded_months: BTreeSet<String>,
+
+    /// the "YYYY-MM" keys of the months in the storage that couldn't be read. they are shown empty, and are only saved
+    /// once something is written into them, at which point the storage sets the unreadable version aside
+    unreadable_months: BTreeSet<String>,
+
+    /// the words of every entry in the store, for narrowing down searches
+    search_index: SearchIndex,
+
+    /// the "YYYY-MM" keys of the months that may have changed since they were last indexed. a month is marked whenever
+    /// it is handed out mutably, and is reindexed before the index is next used
+    stale_index_months: BTreeSet<String>,
 }
 
 impl Default for GlobalStore {
     fn default() -> Self {
+        let storage = journal_storage::open_storage(
+            preferences().paths.storage_backend,
+            preferences().paths.savedata_dir(),
+        );
+
+        Self::with_storage(storage)
+    }
+}
+
+impl GlobalStore {
+    /// creates an empty store that loads from and saves to the given storage
+    pub fn with_storage(storage: Box<dyn JournalStorage>) -> Self {
         let mut global_store = Self {
             entries: Vec::default(),
             current_date: NaiveDate::default(),
             word_counts: WordCounts::default(),
+            storage,
+            unloaded_months: BTreeSet::new(),
+            unreadable_months: BTreeSet::new(),
+            search_index: SearchIndex::default(),
+            stale_index_months: BTreeSet::new(),
         };
 
         global_store.set_current_store_date(Local::now().date_naive());
 
         global_store
     }
-}
 
-impl GlobalStore {
-    /// changes the current date, adding the month if it doesn't exist
+    /// changes the current date, adding the month if it doesn't exist. the month and its neighbors are loaded from the
+    /// storage if they haven't been already, so the current month is always resident for month() and day(). months
+    /// that were only added by visiting them are removed again once they are left without anything written into them
     pub fn set_current_store_date(&mut self, new_date: NaiveDate) {
         self.current_date = new_date;
 
+        self.load_months_around(new_date);
+
         if !self.entries.iter().any(|month_store| {
             month_store.get_yyyy_mm() == self.current_date.format("%Y-%m").to_string()
         }) {
@@ -45,10 +85,50 @@ impl GlobalStore {
 
             self.push_month_store(MonthStore::new(first_of_month));
         }
+
+        self.prune_blank_months();
+    }
+
+    /// removes the blank months at either end of the store. these only exist because a month was visited, and any
+    /// months between it and the rest of the journal were filled in to keep the store continuous. months that hold
+    /// anything, haven't been loaded from the storage yet, or hold the current date are kept, along with every month
+    /// between them
+    fn prune_blank_months(&mut self) {
+        let current_year_month = self.current_date.format("%Y-%m").to_string();
+
+        let is_kept = |month_store: &MonthStore| {
+            let year_month = month_store.get_yyyy_mm();
+
+            year_month == current_year_month
+                || self.unloaded_months.contains(&year_month)
+                || !month_store.is_blank()
+        };
+
+        let first_kept = self.entries.iter().position(is_kept);
+        let last_kept = self.entries.iter().rposition(is_kept);
+
+        if let (Some(first_kept), Some(last_kept)) = (first_kept, last_kept) {
+            let pruned_months = self.entries[last_kept + 1..]
+                .iter()
+                .chain(&self.entries[..first_kept])
+                .map(|month_store| month_store.first_of_month())
+                .collect::<Vec<NaiveDate>>();
+
+            for first_of_month in pruned_months {
+                self.search_index.remove_month(first_of_month);
+            }
+
+            self.entries.truncate(last_kept + 1);
+            self.entries.drain(..first_kept);
+        }
     }
 
     /// mutable access to the current month store based on the current date in the GlobalStore
     pub fn month_mut(&mut self) -> &mut MonthStore {
+        // anything in the month could be changed through the reference
+        self.stale_index_months
+            .insert(self.current_date.format("%Y-%m").to_string());
+
         let month_index = self
             .entries
             .iter()
@@ -92,50 +172,276 @@ impl GlobalStore {
         self.current_date
     }
 
-    /// loads all entries from disk, overwriting any existing data in the store
+    /// opens the journal without reading all of it. entries left in other storage backends are migrated first, then
+    /// only the months around the current date and the most recent months shown in the month strip are loaded. the
+    /// rest of the months are loaded once something needs them
+    pub fn load_startup(&mut self) {
+        self.migrate_other_backends();
+
+        // only one file is ever used for a month, so any other file for the same month is pointed out rather than
+        // silently hidden
+        for duplicate_month in self.storage.duplicate_months() {
+            logbox_mut().log(&format!(
+                "Found {} files for {} ({}), only {} is used",
+                duplicate_month.filenames.len(),
+                duplicate_month.year_month,
+                duplicate_month.filenames.join(", "),
+                duplicate_month.used_filename
+            ));
+        }
+
+        self.unloaded_months = self.storage.list_months().into_iter().collect();
+        let stored_month_count = self.unloaded_months.len();
+
+        let recent_months = self
+            .unloaded_months
+            .iter()
+            .rev()
+            .take(preferences().general.month_strip_length)
+            .cloned()
+            .collect();
+
+        self.load_stored_months(recent_months);
+        self.load_months_around(self.current_date);
+
+        logbox_mut().log(&format!(
+            "Loaded {} of {} stored months, the rest are loaded when needed",
+            stored_month_count - self.unloaded_months.len(),
+            stored_month_count
+        ));
+    }
+
+    /// loads every month that hasn't been loaded from the storage yet. anything that reads the whole journal, like
+    /// searching, streaks, and stats, should call this first. a summary of what was loaded is written to the logbox so
+    /// any missing data is visible to the user
     pub fn load_all(&mut self) {
-        static FILENAME_REGEX: LazyLock<Regex> =
-            LazyLock::new(|| Regex::new(r"\d\d\d\d-\d\d\.json").expect("couldn't create regex"));
+        if self.unloaded_months.is_empty() || journal_cipher::journal_locked() {
+            return;
+        }
 
-        let savedata_dir = preferences().paths.savedata_dir();
+        let unloaded_months = self.unloaded_months.iter().cloned().collect();
+        let (loaded_month_count, skipped_month_count) = self.load_stored_months(unloaded_months);
 
-        if let Ok(savedata_entries) = savedata_dir.read_dir() {
-            for dir_entry in savedata_entries.flatten() {
-                if !dir_entry.path().is_file() {
-                    continue;
-                }
+        let date_range = match (self.first_edited_day(), self.last_edited_day()) {
+            (Some(first_day), Some(last_day)) => format!(", {} to {}", first_day, last_day),
+            _ => String::new(),
+        };
 
-                let filename = dir_entry
-                    .file_name()
-                    .into_string()
-                    .expect("couldn't convert filename to string");
+        logbox_mut().log(&format!(
+            "Loaded {} more months ({} edited days in total{}), skipped {} unreadable or invalid months",
+            loaded_month_count,
+            self.edited_day_count(),
+            date_range,
+            skipped_month_count
+        ));
+    }
 
-                if !FILENAME_REGEX.is_match(&filename) {
-                    continue;
-                }
+    /// true if the month of the given date is already in the store or the storage, so navigating to it wouldn't create a
+    /// new month
+    pub fn month_exists(&self, date: NaiveDate) -> bool {
+        let year_month = date.format("%Y-%m").to_string();
 
-                let file_date_first = filename[0..7].to_string() + "-01";
-                let first_of_month = misc_tools::yyyy_mm_dd_string_to_date(&file_date_first);
+        self.unloaded_months.contains(&year_month)
+            || self.unreadable_months.contains(&year_month)
+            || self
+                .entries
+                .iter()
+                .any(|month_store| month_store.get_yyyy_mm() == year_month)
+    }
 
-                let mut month_store = MonthStore::new(first_of_month);
-                month_store.load_month(first_of_month);
+    /// the number of months in the storage that haven't been loaded into the store yet
+    pub fn unloaded_month_count(&self) -> usize {
+        self.unloaded_months.len()
+    }
+
+    /// entries left behind in the other backends after the storage preference changes are brought over, otherwise
+    /// they would silently disappear from the journal
+    fn migrate_other_backends(&mut self) {
+        let storage_backend = preferences().paths.storage_backend;
+
+        for other_backend in StorageBackend::ALL {
+            if other_backend == storage_backend {
+                continue;
+            }
+
+            let other_storage =
+                journal_storage::open_storage(other_backend, preferences().paths.savedata_dir());
+
+            let (moved_month_count, conflicting_month_count) =
+                journal_storage::migrate_months(other_storage.as_ref(), self.storage.as_ref());
+
+            if moved_month_count > 0 {
+                logbox_mut().log(&format!(
+                    "Moved {} months from \"{}\" storage to \"{}\" storage",
+                    moved_month_count, other_backend, storage_backend
+                ));
+            }
+
+            if conflicting_month_count > 0 {
+                logbox_mut().log(&format!(
+                    "{} months exist in both \"{}\" and \"{}\" storage. Only the \"{}\" versions were loaded",
+                    conflicting_month_count, other_backend, storage_backend, storage_backend
+                ));
+            }
+        }
+    }
+
+    /// loads the month of the given date and the months on either side of it, if they are in the storage and haven't
+    /// been loaded yet
+    fn load_months_around(&mut self, date: NaiveDate) {
+        let nearby_months = [
+            date.checked_sub_months(Months::new(1)),
+            Some(date),
+            date.checked_add_months(Months::new(1)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|month_date| month_date.format("%Y-%m").to_string())
+        .filter(|year_month| self.unloaded_months.contains(year_month))
+        .collect::<Vec<String>>();
+
+        if !nearby_months.is_empty() {
+            self.load_stored_months(nearby_months);
+        }
+    }
 
-                self.add_month_to_store(month_store);
+    /// loads the given months from the storage, replacing the empty placeholder months that were added in their place.
+    /// months that couldn't be read are marked as unreadable, so they aren't read again and their placeholders are
+    /// never saved over them unless something is written into them. nothing can be read while the journal is locked,
+    /// so the months are left unloaded until it is unlocked. returns the number of months that were loaded and the
+    /// number that were skipped for being unreadable or not being a valid date
+    fn load_stored_months(&mut self, year_months: Vec<String>) -> (usize, usize) {
+        let mut loaded_month_count = 0;
+        let mut skipped_month_count = 0;
+
+        let loaded_months = self.storage.load_selected_months(&year_months);
+
+        if !journal_cipher::journal_locked() {
+            for year_month in year_months {
+                if !loaded_months.contains_key(&year_month)
+                    && self.unloaded_months.remove(&year_month)
+                {
+                    self.unreadable_months.insert(year_month);
+
+                    skipped_month_count += 1;
+                }
             }
         }
 
+        for (year_month, month_json) in loaded_months {
+            self.unloaded_months.remove(&year_month);
+
+            let Ok(first_of_month) = NaiveDate::parse_from_str(&(year_month + "-01"), "%Y-%m-%d")
+            else {
+                skipped_month_count += 1;
+                continue;
+            };
+
+            let mut month_store = MonthStore::new(first_of_month);
+            month_store.load_month(first_of_month, &month_json);
+
+            self.add_month_to_store(month_store);
+
+            loaded_month_count += 1;
+        }
+
         self.add_empty_months();
+
+        (loaded_month_count, skipped_month_count)
     }
 
-    /// writes the store to disk
-    pub fn save_all(&self) {
-        for month in &self.entries {
-            month.save_month();
+    /// true if anything in the store changed since it was last saved or loaded
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|month_store| month_store.has_unsaved_changes())
+    }
+
+    /// writes the store to the storage. months that were emptied of every entry are deleted from the storage. months
+    /// that haven't been loaded yet are left as they are, since the store only holds an empty placeholder for them. the
+    /// same goes for a month that is in the storage but couldn't be read, unless something was written into it. the
+    /// storage then sets the unreadable version aside, and only what was written is saved in its place
+    pub fn save_all(&mut self) {
+        let stored_months = self.storage.list_months();
+
+        let resident_months = self
+            .entries
+            .iter()
+            .map(|month| month.get_yyyy_mm())
+            .filter(|year_month| !self.unloaded_months.contains(year_month))
+            .collect::<Vec<String>>();
+
+        let mut saved_months = self.storage.load_selected_months(&resident_months);
+
+        let months = self
+            .entries
+            .iter()
+            .filter(|month| !self.unloaded_months.contains(&month.get_yyyy_mm()))
+            .filter_map(|month| {
+                let year_month = month.get_yyyy_mm();
+
+                let saved_month_json = saved_months.remove(&year_month);
+                let unreadable = saved_month_json.is_none() && stored_months.contains(&year_month);
+
+                if unreadable && !month.has_unsaved_changes() {
+                    return None;
+                }
+
+                let mut month_json = saved_month_json.unwrap_or_default();
+                month.save_month(&mut month_json);
+
+                if unreadable && month_json.is_empty() {
+                    return None;
+                }
+
+                Some((year_month, month_json))
+            })
+            .collect::<BTreeMap<String, MonthJson>>();
+
+        let written_months = months.keys().cloned().collect::<Vec<String>>();
+
+        // the months stay unsaved if they couldn't all be written, so they are saved again and the unsaved changes are
+        // still pointed out on exit
+        if let Err(save_error) = self.storage.save_months(months) {
+            logbox_mut().log(&format!("Couldn't save the journal: {}", save_error));
+
+            return;
+        }
+
+        self.unreadable_months
+            .retain(|year_month| !written_months.contains(year_month));
+
+        for month_store in &mut self.entries {
+            month_store.mark_saved();
         }
     }
 
-    /// since adding months can be discontinuous in time, the missing ones should be added to ensure time continuity
-    fn add_empty_months(&mut self) {
+    /// reads every month in the storage and writes it straight back, so the files pick up a change in how they are
+    /// written, like being encrypted. months that couldn't be read are left alone rather than being replaced with
+    /// nothing, and are returned as an error once the rest of the months were written, since they weren't rewritten
+    pub fn rewrite_storage(&self) -> io::Result<()> {
+        let stored_month_count = self.storage.list_months().len();
+
+        let mut stored_months = self.storage.load_months();
+        let unreadable_month_count = stored_month_count.saturating_sub(stored_months.len());
+
+        stored_months.retain(|_year_month, month_json| !month_json.is_empty());
+
+        self.storage.save_months(stored_months)?;
+
+        if unreadable_month_count > 0 {
+            return Err(io::Error::other(format!(
+                "{} months couldn't be read, so they were left as they are",
+                unreadable_month_count
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// since adding months can be discontinuous in time, the missing ones should be added to ensure time continuity.
+    /// returns the number of months that were added
+    fn add_empty_months(&mut self) -> usize {
         self.sort_month_stores();
 
         let current_months: Vec<NaiveDate> = self
@@ -145,7 +451,7 @@ impl GlobalStore {
             .collect();
 
         if current_months.len() < 2 {
-            return;
+            return 0;
         }
 
         let start_month = current_months.first().expect("couldn't get start month");
@@ -164,17 +470,23 @@ impl GlobalStore {
                 .expect("couldn't add month");
         }
 
+        let missing_month_count = missing_months.len();
+
         for month_date in missing_months {
             let first_of_month = month_date.with_day(1).expect("invalid first of month");
             self.add_month_to_store(MonthStore::new(first_of_month));
         }
 
         self.sort_month_stores();
+
+        missing_month_count
     }
 
     /// adds a month into the store. if the new month is dated the same as an existing entry, the existing one is
     /// overwritten by the new one
     fn add_month_to_store(&mut self, new_month_store: MonthStore) {
+        self.search_index.index_month(&new_month_store);
+
         self.entries.retain(|month_store| {
             month_store.get_yyyy_mm() != new_month_store.get_yyyy_mm().clone()
         });
@@ -183,6 +495,8 @@ impl GlobalStore {
     }
 
     pub fn push_month_store(&mut self, new_month_store: MonthStore) {
+        self.unloaded_months.remove(&new_month_store.get_yyyy_mm());
+
         self.add_month_to_store(new_month_store);
         self.add_empty_months();
         self.sort_month_stores();
@@ -197,6 +511,33 @@ impl GlobalStore {
         self.entries.iter()
     }
 
+    /// reindexes every month that may have changed since it was last indexed. this is put off until the next search, so
+    /// editing never pays for reindexing
+    fn refresh_search_index(&mut self) {
+        for year_month in std::mem::take(&mut self.stale_index_months) {
+            if let Some(month_store) = self
+                .entries
+                .iter()
+                .find(|month_store| month_store.get_yyyy_mm() == year_month)
+            {
+                self.search_index.index_month(month_store);
+            } else if let Ok(first_of_month) =
+                NaiveDate::parse_from_str(&(year_month + "-01"), "%Y-%m-%d")
+            {
+                self.search_index.remove_month(first_of_month);
+            }
+        }
+    }
+
+    /// the dates whose entries could contain the query, ignoring case. the index only narrows the dates down, so the
+    /// entries still have to be checked for the query itself. None if the index can't narrow down the query, like one
+    /// made only of punctuation, in which case every entry has to be checked
+    pub fn search_candidates(&mut self, query: &str) -> Option<BTreeSet<NaiveDate>> {
+        self.refresh_search_index();
+
+        self.search_index.candidate_dates(query)
+    }
+
     /// retrieves the day store at the given date, if it exists
     pub fn get_day(&self, date: NaiveDate) -> Option<DayStore> {
         let year_month = date.format("%Y-%m").to_string();
@@ -215,6 +556,11 @@ impl GlobalStore {
         self.month_stores().map(|ms| ms.edited_day_count()).sum()
     }
 
+    /// the total time spent writing across the whole journal
+    pub fn writing_seconds(&self) -> u64 {
+        self.month_stores().map(|ms| ms.writing_seconds()).sum()
+    }
+
     /// returns the date of the first edited day in the store, if it exists
     pub fn first_edited_day(&self) -> Option<NaiveDate> {
         for month in self.month_stores() {
@@ -300,8 +646,37 @@ impl GlobalStore {
         None
     }
 
-    /// gets the number of the longest streak of consecutively edited days
+    /// gets the number of edited days and their total word count for every weekday, indexed starting from Monday
+    pub fn weekday_breakdown(&self) -> [(usize, usize); 7] {
+        let mut weekday_totals = [(0, 0); 7];
+
+        for month in self.month_stores() {
+            for day in month.days().filter(|day| day.contains_entry()) {
+                let weekday_index = day.date().weekday().num_days_from_monday() as usize;
+
+                weekday_totals[weekday_index].0 += 1;
+                weekday_totals[weekday_index].1 += day.total_word_count();
+            }
+        }
+
+        weekday_totals
+    }
+
+    /// the days across the whole store that count towards the averages. days before the first edited day are never
+    /// counted, so including empty days doesn't count the empty months before the journal was started
+    fn averaged_days(&self) -> impl Iterator<Item = &DayStore> {
+        let first_edited_day = self.first_edited_day().unwrap_or(NaiveDate::MAX);
+
+        self.month_stores()
+            .flat_map(|month_store| month_store.averaged_days())
+            .filter(move |day_store| day_store.date() >= first_edited_day)
+    }
+
+    /// gets the number of the longest streak of consecutively edited days. if weekends are excluded in the stats
+    /// preferences, empty weekends don't break the streak
     pub fn longest_streak(&self) -> u32 {
+        let exclude_weekends = preferences().stats.exclude_weekends;
+
         let mut longest_found_streak = 0;
         let mut current_search_streak = 0;
 
@@ -309,6 +684,8 @@ impl GlobalStore {
             for day in month.days() {
                 if day.contains_entry() {
                     current_search_streak += 1;
+                } else if exclude_weekends && misc_tools::is_weekend(day.date()) {
+                    continue;
                 } else {
                     if current_search_streak > longest_found_streak {
                         longest_found_streak = current_search_streak;
@@ -319,16 +696,23 @@ impl GlobalStore {
             }
         }
 
-        longest_found_streak
+        // a streak running up to the last day in the store is never broken, so it is only counted here
+        longest_found_streak.max(current_search_streak)
     }
 
-    /// gets the number of consecutive edited days that connect to the last (most recent) edited day in the global store
+    /// gets the number of consecutive edited days that connect to the last (most recent) edited day in the global store.
+    /// if weekends are excluded in the stats preferences, empty weekends don't break the streak
     pub fn current_streak(&self) -> u32 {
+        let exclude_weekends = preferences().stats.exclude_weekends;
+
         let mut current_streak = 0;
         let mut found_most_recent_day = false;
 
         for month in self.month_stores().rev() {
             for day in month.days().rev() {
+                if !day.contains_entry() && exclude_weekends && misc_tools::is_weekend(day.date()) {
+                    continue;
+                }
                 if !day.contains_entry() && !found_most_recent_day {
                     continue;
                 }
@@ -345,6 +729,37 @@ impl GlobalStore {
 
         current_streak
     }
+
+    /// gets the number of consecutive days meeting the word goal that connect to the last (most recent) day in the
+    /// global store that met it. if weekends are excluded in the stats preferences, weekends short of the goal don't
+    /// break the streak. the word counts are only as current as the last word count update
+    pub fn goal_streak(&self, goal: usize) -> u32 {
+        let exclude_weekends = preferences().stats.exclude_weekends;
+
+        let mut goal_streak = 0;
+        let mut found_most_recent_day = false;
+
+        for month in self.month_stores().rev() {
+            for day in month.days().rev() {
+                let met_goal = day.contains_entry() && day.total_word_count() >= goal;
+
+                if !met_goal && exclude_weekends && misc_tools::is_weekend(day.date()) {
+                    continue;
+                }
+                if !met_goal && !found_most_recent_day {
+                    continue;
+                }
+                if !met_goal && found_most_recent_day {
+                    return goal_streak;
+                }
+
+                found_most_recent_day = true;
+                goal_streak += 1;
+            }
+        }
+
+        goal_streak
+    }
 }
 
 impl WordCount for GlobalStore {
@@ -411,7 +826,13 @@ impl WordCount for GlobalStore {
 
 impl TimedWordCount for GlobalStore {
     fn average_words(&self) -> f64 {
-        let average_words = (self.total_word_count() as f64) / (self.edited_day_count() as f64);
+        let (word_count, day_count) =
+            self.averaged_days()
+                .fold((0, 0), |(word_count, day_count), day_store| {
+                    (word_count + day_store.total_word_count(), day_count + 1)
+                });
+
+        let average_words = (word_count as f64) / (day_count as f64);
 
         if average_words.is_finite() {
             average_words
@@ -421,7 +842,13 @@ impl TimedWordCount for GlobalStore {
     }
 
     fn average_chars(&self) -> f64 {
-        let average_chars = (self.total_char_count() as f64) / (self.edited_day_count() as f64);
+        let (char_count, day_count) =
+            self.averaged_days()
+                .fold((0, 0), |(char_count, day_count), day_store| {
+                    (char_count + day_store.total_char_count(), day_count + 1)
+                });
+
+        let average_chars = (char_count as f64) / (day_count as f64);
 
         if average_chars.is_finite() {
             average_chars
@@ -430,3 +857,453 @@ impl TimedWordCount for GlobalStore {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::with_test_preferences;
+    use crate::store::journal_storage::MemoryStorage;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    /// a store backed by the given storage, opened the way it is on startup
+    fn open_store(storage: &MemoryStorage) -> GlobalStore {
+        let mut global_store = GlobalStore::with_storage(Box::new(storage.clone()));
+        global_store.load_startup();
+
+        global_store
+    }
+
+    /// a store holding the given entries, with the word counts up to date. the current date is left on the last entry
+    fn store_with_entries(entries: &[(NaiveDate, &str)]) -> GlobalStore {
+        let mut global_store = GlobalStore::with_storage(Box::new(MemoryStorage::default()));
+
+        for (entry_date, entry_text) in entries {
+            global_store.set_current_store_date(*entry_date);
+            global_store.day_mut().set_day_text(entry_text.to_string());
+        }
+
+        global_store.update_word_count();
+
+        global_store
+    }
+
+    #[test]
+    fn saved_entries_load_into_a_new_store() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let storage = MemoryStorage::default();
+
+                let mut global_store = open_store(&storage);
+                global_store.set_current_store_date(date(2024, 2, 10));
+                global_store
+                    .day_mut()
+                    .set_day_text("a saved entry".to_string());
+                global_store.save_all();
+
+                assert_eq!(storage.list_months(), vec!["2024-02".to_string()]);
+
+                let mut reopened_store = open_store(&storage);
+                reopened_store.set_current_store_date(date(2024, 2, 10));
+
+                assert_eq!(reopened_store.day().get_day_text(), "a saved entry");
+                assert!(!reopened_store.has_unsaved_changes());
+            },
+        );
+    }
+
+    #[test]
+    fn emptied_months_are_deleted_from_the_storage() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let storage = MemoryStorage::default();
+
+                let mut global_store = open_store(&storage);
+                global_store.set_current_store_date(date(2024, 2, 10));
+                global_store
+                    .day_mut()
+                    .set_day_text("soon to be deleted".to_string());
+                global_store.save_all();
+
+                let mut reopened_store = open_store(&storage);
+                reopened_store.set_current_store_date(date(2024, 2, 10));
+                reopened_store.day_mut().set_day_text(String::new());
+                reopened_store.save_all();
+
+                assert!(storage.months().is_empty());
+            },
+        );
+    }
+
+    /// a storage holding an entry on the 10th of each of the given months of 2024
+    fn storage_with_months(months: &[u32]) -> MemoryStorage {
+        let storage = MemoryStorage::default();
+
+        let mut global_store = open_store(&storage);
+        for month in months {
+            global_store.set_current_store_date(date(2024, *month, 10));
+            global_store
+                .day_mut()
+                .set_day_text(format!("entry in month {}", month));
+        }
+        global_store.save_all();
+
+        storage
+    }
+
+    #[test]
+    fn startup_only_loads_the_recent_months() {
+        with_test_preferences(
+            |preferences| preferences.general.month_strip_length = 2,
+            || {
+                let storage = storage_with_months(&[1, 3, 5, 7]);
+
+                let global_store = open_store(&storage);
+
+                assert_eq!(global_store.unloaded_month_count(), 2);
+                assert_eq!(global_store.edited_day_count(), 2);
+                assert!(global_store.month_exists(date(2024, 1, 1)));
+            },
+        );
+    }
+
+    #[test]
+    fn load_all_loads_the_remaining_months() {
+        with_test_preferences(
+            |preferences| preferences.general.month_strip_length = 1,
+            || {
+                let storage = storage_with_months(&[1, 3, 5, 7]);
+
+                let mut global_store = open_store(&storage);
+                global_store.load_all();
+
+                assert_eq!(global_store.unloaded_month_count(), 0);
+                assert_eq!(global_store.edited_day_count(), 4);
+
+                global_store.set_current_store_date(date(2024, 3, 10));
+                assert_eq!(global_store.day().get_day_text(), "entry in month 3");
+            },
+        );
+    }
+
+    #[test]
+    fn saving_leaves_unloaded_months_untouched() {
+        with_test_preferences(
+            |preferences| preferences.general.month_strip_length = 1,
+            || {
+                let storage = storage_with_months(&[1, 3, 5, 7]);
+                let months_before_saving = storage.months();
+
+                let mut global_store = open_store(&storage);
+                global_store.set_current_store_date(date(2024, 7, 10));
+                global_store
+                    .day_mut()
+                    .set_day_text("an edited entry".to_string());
+                global_store.save_all();
+
+                assert!(global_store.unloaded_month_count() > 0);
+
+                let months_after_saving = storage.months();
+                assert_eq!(months_after_saving.len(), 4);
+                for year_month in ["2024-01", "2024-03", "2024-05"] {
+                    assert_eq!(
+                        months_after_saving[year_month],
+                        months_before_saving[year_month]
+                    );
+                }
+                assert_ne!(
+                    months_after_saving["2024-07"],
+                    months_before_saving["2024-07"]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn unreadable_month_is_only_saved_once_written_into() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let storage = storage_with_months(&[1]);
+                storage.add_unreadable_month("2024-02");
+
+                let mut global_store = open_store(&storage);
+                global_store.load_all();
+
+                assert_eq!(global_store.unloaded_month_count(), 0);
+                assert!(global_store.month_exists(date(2024, 2, 1)));
+
+                global_store.set_current_store_date(date(2024, 2, 5));
+                global_store.save_all();
+
+                assert_eq!(storage.unreadable_months(), vec!["2024-02".to_string()]);
+
+                global_store
+                    .day_mut()
+                    .set_day_text("written after all".to_string());
+                assert!(global_store.has_unsaved_changes());
+                global_store.save_all();
+
+                assert!(!global_store.has_unsaved_changes());
+                assert!(storage.unreadable_months().is_empty());
+
+                let mut reopened_store = open_store(&storage);
+                reopened_store.set_current_store_date(date(2024, 2, 5));
+                assert_eq!(reopened_store.day().get_day_text(), "written after all");
+            },
+        );
+    }
+
+    #[test]
+    fn unreadable_months_count_as_skipped() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let storage = storage_with_months(&[1]);
+                storage.add_unreadable_month("2024-02");
+
+                let mut global_store = GlobalStore::with_storage(Box::new(storage.clone()));
+                global_store.unloaded_months = storage.list_months().into_iter().collect();
+
+                let loaded_and_skipped = global_store
+                    .load_stored_months(vec!["2024-01".to_string(), "2024-02".to_string()]);
+
+                assert_eq!(loaded_and_skipped, (1, 1));
+            },
+        );
+    }
+
+    /// entries on Thursday, Friday, Monday, and Tuesday, with the weekend between them left empty
+    fn entries_around_a_weekend() -> Vec<(NaiveDate, &'static str)> {
+        vec![
+            (date(2024, 3, 7), "thursday"),
+            (date(2024, 3, 8), "friday"),
+            (date(2024, 3, 11), "monday"),
+            (date(2024, 3, 12), "tuesday"),
+        ]
+    }
+
+    #[test]
+    fn excluded_weekends_do_not_break_streaks() {
+        with_test_preferences(
+            |preferences| preferences.stats.exclude_weekends = true,
+            || {
+                let global_store = store_with_entries(&entries_around_a_weekend());
+
+                assert_eq!(global_store.longest_streak(), 4);
+                assert_eq!(global_store.current_streak(), 4);
+            },
+        );
+    }
+
+    #[test]
+    fn included_weekends_break_streaks() {
+        with_test_preferences(
+            |preferences| preferences.stats.exclude_weekends = false,
+            || {
+                let global_store = store_with_entries(&entries_around_a_weekend());
+
+                assert_eq!(global_store.longest_streak(), 2);
+                assert_eq!(global_store.current_streak(), 2);
+            },
+        );
+    }
+
+    #[test]
+    fn empty_weekdays_still_break_streaks_with_weekends_excluded() {
+        with_test_preferences(
+            |preferences| preferences.stats.exclude_weekends = true,
+            || {
+                let global_store = store_with_entries(&[
+                    (date(2024, 3, 8), "friday"),
+                    (date(2024, 3, 12), "tuesday"),
+                ]);
+
+                assert_eq!(global_store.longest_streak(), 1);
+                assert_eq!(global_store.current_streak(), 1);
+            },
+        );
+    }
+
+    #[test]
+    fn streaks_reaching_the_end_of_the_store_are_counted() {
+        with_test_preferences(
+            |preferences| preferences.stats.exclude_weekends = true,
+            || {
+                // the weekend ending the month is skipped, so nothing after friday breaks the streak
+                let global_store = store_with_entries(&[
+                    (date(2024, 3, 28), "thursday"),
+                    (date(2024, 3, 29), "friday"),
+                ]);
+
+                assert_eq!(global_store.longest_streak(), 2);
+            },
+        );
+    }
+
+    #[test]
+    fn goal_streak_ends_at_the_first_day_under_the_goal() {
+        with_test_preferences(
+            |preferences| preferences.stats.exclude_weekends = false,
+            || {
+                let global_store = store_with_entries(&[
+                    (date(2024, 3, 4), "one two three"),
+                    (date(2024, 3, 5), "one two three four"),
+                    (date(2024, 3, 6), "one"),
+                    (date(2024, 3, 7), "one two three"),
+                    (date(2024, 3, 8), "one two three four"),
+                ]);
+
+                assert_eq!(global_store.goal_streak(1), 5);
+                assert_eq!(global_store.goal_streak(3), 2);
+                assert_eq!(global_store.goal_streak(4), 1);
+                assert_eq!(global_store.goal_streak(5), 0);
+            },
+        );
+    }
+
+    #[test]
+    fn goal_streak_follows_the_excluded_weekends() {
+        for (exclude_weekends, expected_streak) in [(true, 4), (false, 2)] {
+            with_test_preferences(
+                |preferences| preferences.stats.exclude_weekends = exclude_weekends,
+                || {
+                    let global_store = store_with_entries(&entries_around_a_weekend());
+
+                    assert_eq!(global_store.goal_streak(1), expected_streak);
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn fresh_store_has_no_goal_streak() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let global_store = store_with_entries(&[]);
+
+                assert_eq!(global_store.goal_streak(0), 0);
+                assert_eq!(global_store.goal_streak(100), 0);
+            },
+        );
+    }
+
+    #[test]
+    fn fresh_store_averages_are_zero() {
+        for include_empty_days in [false, true] {
+            with_test_preferences(
+                |preferences| preferences.stats.include_empty_days = include_empty_days,
+                || {
+                    let global_store = store_with_entries(&[]);
+
+                    assert_eq!(global_store.average_words(), 0.0);
+                    assert_eq!(global_store.average_chars(), 0.0);
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn averages_only_count_edited_days() {
+        with_test_preferences(
+            |preferences| {
+                preferences.stats.include_empty_days = false;
+                preferences.stats.exclude_weekends = false;
+            },
+            || {
+                let global_store = store_with_entries(&[
+                    (date(2024, 3, 4), "one two"),
+                    (date(2024, 3, 20), "three"),
+                ]);
+
+                assert_eq!(global_store.average_words(), 1.5);
+                assert_eq!(global_store.average_chars(), 6.0);
+            },
+        );
+    }
+
+    #[test]
+    fn navigating_without_editing_persists_no_months() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let storage = MemoryStorage::default();
+
+                let mut global_store = open_store(&storage);
+                global_store.set_current_store_date(date(2024, 2, 10));
+                global_store
+                    .day_mut()
+                    .set_day_text("the only entry".to_string());
+                global_store.save_all();
+
+                global_store.set_current_store_date(date(2024, 9, 15));
+                global_store.save_all();
+
+                assert!(!global_store.has_unsaved_changes());
+                assert_eq!(storage.list_months(), vec!["2024-02".to_string()]);
+
+                global_store.set_current_store_date(date(2024, 2, 10));
+                global_store.save_all();
+
+                assert_eq!(global_store.month_stores().count(), 1);
+                assert_eq!(storage.list_months(), vec!["2024-02".to_string()]);
+            },
+        );
+    }
+
+    #[test]
+    fn search_candidates_follow_edits_through_day_mut() {
+        let mut global_store = store_with_entries(&[(date(2024, 5, 10), "an apple a day")]);
+
+        assert_eq!(
+            global_store.search_candidates("apple"),
+            Some(BTreeSet::from([date(2024, 5, 10)]))
+        );
+
+        global_store
+            .day_mut()
+            .set_day_text("a banana instead".to_string());
+
+        assert_eq!(
+            global_store.search_candidates("apple"),
+            Some(BTreeSet::new())
+        );
+        assert_eq!(
+            global_store.search_candidates("banana"),
+            Some(BTreeSet::from([date(2024, 5, 10)]))
+        );
+    }
+
+    #[test]
+    fn pruned_months_leave_the_search_index() {
+        let mut global_store = store_with_entries(&[
+            (date(2024, 5, 10), "an apple a day"),
+            (date(2024, 8, 1), "a passing banana"),
+        ]);
+
+        assert_eq!(
+            global_store.search_candidates("banana"),
+            Some(BTreeSet::from([date(2024, 8, 1)]))
+        );
+
+        // once the far month is emptied and saved, leaving it prunes it along with the blank months before it
+        global_store.day_mut().set_day_text(String::new());
+        global_store.month_mut().mark_saved();
+        global_store.set_current_store_date(date(2024, 5, 10));
+
+        assert!(!global_store.month_exists(date(2024, 8, 1)));
+        assert_eq!(
+            global_store.search_candidates("banana"),
+            Some(BTreeSet::new())
+        );
+        assert_eq!(
+            global_store.search_candidates("apple"),
+            Some(BTreeSet::from([date(2024, 5, 10)]))
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use crate::config::preferences;
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    XChaCha20Poly1305, XNonce,
+    aead::{Aead, AeadCore, KeyInit, OsRng, rand_core::RngCore},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::Path,
+    path::PathBuf,
+    sync::RwLock,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use strum::Display;
+
+/// every encrypted file starts with these bytes, so encrypted and plain text files can always be told apart
+const ENCRYPTED_MAGIC: &[u8] = b"IRONNOTE-ENCRYPTED-1\n";
+
+/// the text the verifier holds. only the key derived from the right password decrypts the verifier back into it
+const VERIFIER_TEXT: &[u8] = b"ironnote journal verifier";
+
+const NONCE_LENGTH: usize = 24;
+const SALT_LENGTH: usize = 16;
+
+/// the key the journal is encrypted with. it is derived from the password when the journal is unlocked, and is only
+/// ever held in memory
+static JOURNAL_KEY: RwLock<Option<[u8; 32]>> = RwLock::new(None);
+
+/// true while the journal is being rewritten as plain text to turn encryption off. the verifier has to stay on the disk
+/// until every file was rewritten, so files are written as plain text even though encryption is still enabled
+static WRITING_PLAIN_TEXT: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Deserialize)]
+/// kept next to the encrypted months, so a password can be checked without the password itself ever being stored
+struct EncryptionVerifier {
+    /// the salt the key is derived from the password with
+    salt: Vec<u8>,
+
+    /// VERIFIER_TEXT, encrypted with the key
+    verifier: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Display)]
+/// ways unlocking the journal can fail
+pub enum UnlockError {
+    #[strum(to_string = "Wrong password")]
+    WrongPassword,
+
+    #[strum(to_string = "The encryption settings of the journal couldn't be read")]
+    UnreadableVerifier,
+}
+
+/// the location of the verifier, savedata/encryption.json. the journal is encrypted if and only if this file exists
+fn verifier_file() -> PathBuf {
+    preferences().paths.savedata_dir().join("encryption.json")
+}
+
+fn journal_key() -> Option<[u8; 32]> {
+    *JOURNAL_KEY.read().expect("couldn't read journal key")
+}
+
+fn set_journal_key(key: Option<[u8; 32]>) {
+    *JOURNAL_KEY.write().expect("couldn't write journal key") = key;
+}
+
+/// derives the key from the password. the same password and salt always derive the same key
+fn derive_key(password: &str, salt: &[u8]) -> Option<[u8; 32]> {
+    let mut key = [0; 32];
+
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .ok()?;
+
+    Some(key)
+}
+
+/// encrypts the data into the magic bytes, followed by a random nonce and the ciphertext
+fn encrypt(key: &[u8; 32], data: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher.encrypt(&nonce, data).expect("couldn't encrypt");
+
+    [ENCRYPTED_MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+/// decrypts data written by encrypt. None if the data wasn't encrypted with the key, or was tampered with
+fn decrypt(key: &[u8; 32], data: &[u8]) -> Option<Vec<u8>> {
+    let nonce_and_ciphertext = data.strip_prefix(ENCRYPTED_MAGIC)?;
+
+    if nonce_and_ciphertext.len() < NONCE_LENGTH {
+        return None;
+    }
+
+    let (nonce, ciphertext) = nonce_and_ciphertext.split_at(NONCE_LENGTH);
+
+    let cipher = XChaCha20Poly1305::new(key.into());
+
+    cipher.decrypt(XNonce::from_slice(nonce), ciphertext).ok()
+}
+
+/// true if the data was written encrypted
+fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(ENCRYPTED_MAGIC)
+}
+
+/// true if the journal is set up to be encrypted
+pub fn encryption_enabled() -> bool {
+    verifier_file().exists()
+}
+
+/// true if the journal is encrypted, but hasn't been unlocked with its password yet. nothing in the journal can be read
+/// or written while it is locked
+pub fn journal_locked() -> bool {
+    encryption_enabled() && journal_key().is_none()
+}
+
+/// checks the password against the verifier, holding onto the key if it is right
+pub fn unlock(password: &str) -> Result<(), UnlockError> {
+    let verifier: EncryptionVerifier = fs::read_to_string(verifier_file())
+        .ok()
+        .and_then(|verifier_json| serde_json::from_str(&verifier_json).ok())
+        .ok_or(UnlockError::UnreadableVerifier)?;
+
+    let key = derive_key(password, &verifier.salt).ok_or(UnlockError::WrongPassword)?;
+
+    if decrypt(&key, &verifier.verifier).as_deref() != Some(VERIFIER_TEXT) {
+        return Err(UnlockError::WrongPassword);
+    }
+
+    set_journal_key(Some(key));
+
+    Ok(())
+}
+
+/// forgets the key, so the journal has to be unlocked again before it can be read
+pub fn lock() {
+    set_journal_key(None);
+}
+
+/// sets up encryption with the given password, leaving the journal unlocked. the months already on the disk stay as
+/// they are until they are written again
+pub fn enable_encryption(password: &str) -> io::Result<()> {
+    let mut salt = vec![0; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+
+    let key = derive_key(password, &salt)
+        .ok_or_else(|| io::Error::other("couldn't derive a key from the password"))?;
+
+    let verifier = EncryptionVerifier {
+        salt,
+        verifier: encrypt(&key, VERIFIER_TEXT),
+    };
+
+    let verifier_json =
+        serde_json::to_string_pretty(&verifier).expect("couldn't serialize verifier");
+
+    fs::create_dir_all(preferences().paths.savedata_dir())?;
+    fs::write(verifier_file(), verifier_json)?;
+
+    set_journal_key(Some(key));
+
+    Ok(())
+}
+
+/// turns encryption off, rewriting the journal as plain text with rewrite_journal first. the verifier holds the salt
+/// the key is derived with, so it is only removed once every file was rewritten. if anything couldn't be rewritten, the
+/// journal stays encrypted, since the files still encrypted could never be decrypted again without the salt. the key is
+/// kept either way, lock() should be called once the journal was decrypted
+pub fn disable_encryption(rewrite_journal: impl FnOnce() -> io::Result<()>) -> io::Result<()> {
+    WRITING_PLAIN_TEXT.store(true, Ordering::SeqCst);
+    let rewrite_result = rewrite_journal();
+    WRITING_PLAIN_TEXT.store(false, Ordering::SeqCst);
+
+    rewrite_result?;
+
+    let verifier_file = verifier_file();
+
+    if verifier_file.exists() {
+        fs::remove_file(verifier_file)?;
+    }
+
+    Ok(())
+}
+
+/// reads a journal file, decrypting it if it is encrypted. None if the file doesn't exist, or is encrypted and the key
+/// can't decrypt it
+pub fn read_journal_file(path: &Path) -> Option<String> {
+    let data = fs::read(path).ok()?;
+
+    if is_encrypted(&data) {
+        let decrypted_data = decrypt(&journal_key()?, &data)?;
+
+        String::from_utf8(decrypted_data).ok()
+    } else {
+        String::from_utf8(data).ok()
+    }
+}
+
+/// true if the journal file can be written or removed. while the journal is locked, nothing is written, and a file that
+/// is encrypted is never replaced without the key, since whatever would replace it was never able to read it
+pub fn journal_file_writable(path: &Path) -> bool {
+    if journal_key().is_some() {
+        return true;
+    }
+
+    !encryption_enabled() && !fs::read(path).is_ok_and(|data| is_encrypted(&data))
+}
+
+/// writes the text to the journal file, encrypted if encryption is enabled. nothing is written while the file isn't
+/// writable, like while the journal is locked
+pub fn write_journal_file(path: &Path, text: &str) -> io::Result<()> {
+    if !journal_file_writable(path) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "the journal is locked",
+        ));
+    }
+
+    let data = match journal_key() {
+        Some(key) if encryption_enabled() && !WRITING_PLAIN_TEXT.load(Ordering::SeqCst) => {
+            encrypt(&key, text.as_bytes())
+        }
+        _ => text.as_bytes().to_vec(),
+    };
+
+    fs::write(path, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{journal_pointer, with_test_preferences};
+
+    /// runs the test against a fresh journal encrypted with a password, locking the journal again afterwards
+    fn with_encrypted_journal(dir_name: &str, test: impl FnOnce(PathBuf)) {
+        let journal_path = journal_pointer::test_dir().join(dir_name);
+        let _ = fs::remove_dir_all(&journal_path);
+
+        with_test_preferences(
+            |preferences| preferences.paths.journal_path = journal_path,
+            || {
+                enable_encryption("correct horse").expect("couldn't enable encryption");
+
+                let month_file = preferences().paths.savedata_dir().join("2024-01.json");
+                write_journal_file(&month_file, "secret entry").expect("couldn't write month");
+
+                test(month_file);
+
+                lock();
+            },
+        );
+    }
+
+    #[test]
+    fn failed_decryption_keeps_the_verifier() {
+        with_encrypted_journal("failed-decryption", |month_file| {
+            let decryption_result = disable_encryption(|| Err(io::Error::other("disk full")));
+
+            assert!(decryption_result.is_err());
+            assert!(encryption_enabled());
+            assert!(is_encrypted(
+                &fs::read(&month_file).expect("month was removed")
+            ));
+
+            lock();
+            assert_eq!(unlock("correct horse"), Ok(()));
+            assert_eq!(
+                read_journal_file(&month_file).as_deref(),
+                Some("secret entry")
+            );
+        });
+    }
+
+    #[test]
+    fn decryption_rewrites_the_journal_before_removing_the_verifier() {
+        with_encrypted_journal("decryption", |month_file| {
+            let decryption_result = disable_encryption(|| {
+                assert!(encryption_enabled());
+
+                let month_text = read_journal_file(&month_file).expect("month wasn't readable");
+                write_journal_file(&month_file, &month_text)
+            });
+
+            assert!(decryption_result.is_ok());
+            assert!(!encryption_enabled());
+            assert_eq!(
+                fs::read_to_string(&month_file).expect("month wasn't plain text"),
+                "secret entry"
+            );
+        });
+    }
+}
@@ -0,0 +1,720 @@
+use super::journal_cipher;
+use crate::config::{StorageBackend, preferences};
+use crate::utils::logbox::logbox_mut;
+
+use regex::Regex;
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+/// the json of a single month: the entry text keyed by date, along with any extra month data like the locked days
+pub type MonthJson = serde_json::Map<String, Value>;
+
+/// somewhere the months of the journal can be saved to and loaded from. months are keyed by their "YYYY-MM" string.
+/// the store only ever goes through this trait, so it never has to know how or where the months are kept
+pub trait JournalStorage: Debug {
+    /// the "YYYY-MM" keys of every month held in the storage
+    fn list_months(&self) -> Vec<String>;
+
+    /// reads the json of the given month. None if the month isn't in the storage, or couldn't be read
+    fn load_month(&self, year_month: &str) -> Option<MonthJson>;
+
+    /// writes the json of the given month, replacing any existing version of it
+    fn save_month(&self, year_month: &str, month_json: &MonthJson) -> io::Result<()>;
+
+    /// removes the given month from the storage, if it exists
+    fn delete_month(&self, year_month: &str) -> io::Result<()>;
+
+    /// every month that more than one file in the storage resolves to. storages that can't hold more than one copy of a
+    /// month never have any
+    fn duplicate_months(&self) -> Vec<DuplicateMonth> {
+        Vec::new()
+    }
+
+    /// reads every month held in the storage. months that couldn't be read are left out
+    fn load_months(&self) -> BTreeMap<String, MonthJson> {
+        self.list_months()
+            .into_iter()
+            .filter_map(|year_month| {
+                let month_json = self.load_month(&year_month)?;
+
+                Some((year_month, month_json))
+            })
+            .collect()
+    }
+
+    /// reads only the given months. months that aren't in the storage or couldn't be read are left out
+    fn load_selected_months(&self, year_months: &[String]) -> BTreeMap<String, MonthJson> {
+        let stored_months = self.list_months();
+
+        year_months
+            .iter()
+            .filter(|year_month| stored_months.contains(year_month))
+            .filter_map(|year_month| Some((year_month.clone(), self.load_month(year_month)?)))
+            .collect()
+    }
+
+    /// writes each of the given months to the storage. months with empty json are deleted instead, so removing every
+    /// entry in a month doesn't leave an empty month behind. a month that fails to be written doesn't stop the rest
+    /// from being written, the first error is returned once they all have been tried
+    fn save_months(&self, months: BTreeMap<String, MonthJson>) -> io::Result<()> {
+        let mut save_result = Ok(());
+
+        for (year_month, month_json) in months {
+            let month_result = if month_json.is_empty() {
+                self.delete_month(&year_month)
+            } else {
+                self.save_month(&year_month, &month_json)
+            };
+
+            save_result = save_result.and(month_result);
+        }
+
+        save_result
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// a month that more than one file resolves to. only one of the files is ever used, so anything in the others is
+/// hidden from the journal
+pub struct DuplicateMonth {
+    /// the "YYYY-MM" key of the month
+    pub(crate) year_month: String,
+
+    /// the name of every file that resolves to the month
+    pub(crate) filenames: Vec<String>,
+
+    /// the name of the file that is loaded and saved for the month
+    pub(crate) used_filename: String,
+}
+
+/// opens the storage for the given backend inside the given savedata directory
+pub fn open_storage(
+    storage_backend: StorageBackend,
+    savedata_dir: PathBuf,
+) -> Box<dyn JournalStorage> {
+    match storage_backend {
+        StorageBackend::MonthFiles => Box::new(FileSystemStorage { savedata_dir }),
+        StorageBackend::SingleFile => Box::new(SingleFileStorage { savedata_dir }),
+    }
+}
+
+/// moves every month out of the source storage and into the destination. months that already exist in the destination
+/// are left in the source untouched, since neither copy can safely replace the other. months that couldn't be read
+/// are never loaded, so they stay in the source as they are. returns the number of months that were moved and the
+/// number that were left behind
+pub fn migrate_months(
+    source: &dyn JournalStorage,
+    destination: &dyn JournalStorage,
+) -> (usize, usize) {
+    let source_months = source.load_months();
+
+    if source_months.is_empty() {
+        return (0, 0);
+    }
+
+    let destination_months = destination.list_months();
+
+    let (conflicting_months, moved_months): (BTreeMap<_, _>, BTreeMap<_, _>) = source_months
+        .into_iter()
+        .partition(|(year_month, _month_json)| destination_months.contains(year_month));
+
+    let moved_month_count = moved_months.len();
+
+    let emptied_months = moved_months
+        .keys()
+        .map(|year_month| (year_month.clone(), MonthJson::new()))
+        .collect();
+
+    // the destination is written before anything is removed from the source, so an interrupted migration can only
+    // ever leave a month in both places rather than in neither. for the same reason, nothing is removed from the
+    // source unless every month was written to the destination
+    if let Err(save_error) = destination.save_months(moved_months) {
+        logbox_mut().log(&format!(
+            "Couldn't move the months into the new storage: {}",
+            save_error
+        ));
+
+        return (0, conflicting_months.len());
+    }
+
+    if let Err(save_error) = source.save_months(emptied_months) {
+        logbox_mut().log(&format!(
+            "Couldn't remove the moved months from the old storage: {}",
+            save_error
+        ));
+    }
+
+    (moved_month_count, conflicting_months.len())
+}
+
+#[derive(Debug)]
+/// the original storage layout, where each month is its own "YYYY-MM.json" file in the savedata directory
+pub struct FileSystemStorage {
+    savedata_dir: PathBuf,
+}
+
+impl FileSystemStorage {
+    /// the name of every month file in the savedata directory, grouped by the month it resolves to. the extension is
+    /// matched regardless of case, so "2024-01.JSON" is the same month as "2024-01.json". each month's filenames are
+    /// sorted, with the expected lowercase name first if it exists
+    fn month_filenames(&self) -> BTreeMap<String, Vec<String>> {
+        static FILENAME_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"(?i)^\d\d\d\d-\d\d\.json$").expect("couldn't create regex")
+        });
+
+        let mut month_filenames: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut skipped_file_count = 0;
+
+        if let Ok(savedata_entries) = self.savedata_dir.read_dir() {
+            for dir_entry in savedata_entries.flatten() {
+                if !dir_entry.path().is_file() {
+                    continue;
+                }
+
+                let Ok(filename) = dir_entry.file_name().into_string() else {
+                    skipped_file_count += 1;
+                    continue;
+                };
+
+                if FILENAME_REGEX.is_match(&filename) {
+                    month_filenames
+                        .entry(filename[0..7].to_string())
+                        .or_default()
+                        .push(filename);
+                }
+            }
+        }
+
+        if skipped_file_count > 0 {
+            logbox_mut().log(&format!(
+                "Skipped {} files in the savedata directory with unreadable names",
+                skipped_file_count
+            ));
+        }
+
+        for (year_month, filenames) in &mut month_filenames {
+            let expected_filename = year_month.clone() + ".json";
+
+            filenames.sort_by_key(|filename| (*filename != expected_filename, filename.clone()));
+        }
+
+        month_filenames
+    }
+
+    /// the file the given month is loaded from and saved to. this is normally "YYYY-MM.json", unless the month only
+    /// exists under a differently cased extension. the savedata directory is only scanned if the expected file
+    /// doesn't exist
+    fn month_file(&self, year_month: &str) -> PathBuf {
+        let expected_file = self.savedata_dir.join(year_month.to_string() + ".json");
+
+        if expected_file.exists() {
+            return expected_file;
+        }
+
+        self.month_file_in(year_month, &self.month_filenames())
+    }
+
+    /// the file of the given month, looked up in filenames already gathered by month_filenames(). this lets a batch of
+    /// months share a single scan of the savedata directory
+    fn month_file_in(
+        &self,
+        year_month: &str,
+        month_filenames: &BTreeMap<String, Vec<String>>,
+    ) -> PathBuf {
+        match month_filenames.get(year_month) {
+            Some(filenames) if !filenames.is_empty() => self.savedata_dir.join(&filenames[0]),
+            _ => self.savedata_dir.join(year_month.to_string() + ".json"),
+        }
+    }
+
+    /// reads the json of a month out of the given month file
+    fn read_month_file(&self, month_file: &Path) -> Option<MonthJson> {
+        if !month_file.exists() {
+            return None;
+        }
+
+        let month_json = journal_cipher::read_journal_file(month_file)
+            .and_then(|month_json| serde_json::from_str(&month_json).ok());
+
+        // nothing can be read while the journal is locked, which isn't a problem with the file itself
+        if month_json.is_none() && !journal_cipher::journal_locked() {
+            logbox_mut().log(&format!(
+                "{} couldn't be read, so it was left as it is",
+                month_file.display()
+            ));
+        }
+
+        month_json
+    }
+
+    /// writes the json of the given month into the given month file
+    fn write_month_file(
+        &self,
+        year_month: &str,
+        month_file: &Path,
+        month_json: &MonthJson,
+    ) -> io::Result<()> {
+        let new_json = preferences().paths.json_format.to_json(month_json)?;
+
+        // the savedata directory is normally created on startup, but it may have been removed while running
+        fs::create_dir_all(&self.savedata_dir)?;
+
+        // an unreadable month would otherwise be replaced by only the days being written, so it is set aside for
+        // recovery rather than overwritten
+        if month_file.exists()
+            && !journal_cipher::journal_locked()
+            && journal_cipher::read_journal_file(month_file)
+                .is_none_or(|month_json| serde_json::from_str::<MonthJson>(&month_json).is_err())
+        {
+            let unreadable_file = self
+                .savedata_dir
+                .join(year_month.to_string() + ".json.unreadable");
+
+            if fs::rename(month_file, &unreadable_file).is_ok() {
+                logbox_mut().log(&format!(
+                    "{} couldn't be read and was moved to {}",
+                    month_file.display(),
+                    unreadable_file.display()
+                ));
+            }
+        }
+
+        journal_cipher::write_journal_file(month_file, &new_json)
+    }
+
+    /// removes the given month file, if it exists and can be written
+    fn remove_month_file(&self, month_file: &Path) -> io::Result<()> {
+        if month_file.exists() && journal_cipher::journal_file_writable(month_file) {
+            fs::remove_file(month_file)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl JournalStorage for FileSystemStorage {
+    fn list_months(&self) -> Vec<String> {
+        self.month_filenames().into_keys().collect()
+    }
+
+    fn duplicate_months(&self) -> Vec<DuplicateMonth> {
+        self.month_filenames()
+            .into_iter()
+            .filter(|(_year_month, filenames)| filenames.len() > 1)
+            .map(|(year_month, filenames)| DuplicateMonth {
+                year_month,
+                used_filename: filenames[0].clone(),
+                filenames,
+            })
+            .collect()
+    }
+
+    fn load_month(&self, year_month: &str) -> Option<MonthJson> {
+        self.read_month_file(&self.month_file(year_month))
+    }
+
+    fn save_month(&self, year_month: &str, month_json: &MonthJson) -> io::Result<()> {
+        self.write_month_file(year_month, &self.month_file(year_month), month_json)
+    }
+
+    fn delete_month(&self, year_month: &str) -> io::Result<()> {
+        self.remove_month_file(&self.month_file(year_month))
+    }
+
+    /// the savedata directory is scanned once, rather than once per month
+    fn load_months(&self) -> BTreeMap<String, MonthJson> {
+        let month_filenames = self.month_filenames();
+
+        month_filenames
+            .keys()
+            .filter_map(|year_month| {
+                let month_file = self.month_file_in(year_month, &month_filenames);
+
+                Some((year_month.clone(), self.read_month_file(&month_file)?))
+            })
+            .collect()
+    }
+
+    /// the savedata directory is scanned once, rather than once per month
+    fn load_selected_months(&self, year_months: &[String]) -> BTreeMap<String, MonthJson> {
+        let month_filenames = self.month_filenames();
+
+        year_months
+            .iter()
+            .filter(|year_month| month_filenames.contains_key(*year_month))
+            .filter_map(|year_month| {
+                let month_file = self.month_file_in(year_month, &month_filenames);
+
+                Some((year_month.clone(), self.read_month_file(&month_file)?))
+            })
+            .collect()
+    }
+
+    /// the savedata directory is scanned once, rather than once for every month that doesn't have a file yet
+    fn save_months(&self, months: BTreeMap<String, MonthJson>) -> io::Result<()> {
+        let month_filenames = self.month_filenames();
+        let mut save_result = Ok(());
+
+        for (year_month, month_json) in months {
+            let month_file = self.month_file_in(&year_month, &month_filenames);
+
+            let month_result = if month_json.is_empty() {
+                self.remove_month_file(&month_file)
+            } else {
+                self.write_month_file(&year_month, &month_file, &month_json)
+            };
+
+            save_result = save_result.and(month_result);
+        }
+
+        save_result
+    }
+}
+
+#[derive(Debug)]
+/// stores the whole journal in a single "journal.json" file in the savedata directory, holding every month keyed by
+/// its "YYYY-MM" string
+pub struct SingleFileStorage {
+    savedata_dir: PathBuf,
+}
+
+impl SingleFileStorage {
+    fn journal_file(&self) -> PathBuf {
+        self.savedata_dir.join("journal.json")
+    }
+
+    /// replaces the contents of the journal file with the given months. if there are no months left, the file is
+    /// removed
+    fn write_journal(&self, journal_months: BTreeMap<String, MonthJson>) -> io::Result<()> {
+        let journal_file = self.journal_file();
+
+        if !journal_cipher::journal_file_writable(&journal_file) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "the journal is locked",
+            ));
+        }
+
+        // an unreadable journal would otherwise be replaced by only the months being written, so it is set aside for
+        // recovery rather than overwritten
+        if journal_file.exists()
+            && journal_cipher::read_journal_file(&journal_file).is_none_or(|journal_json| {
+                serde_json::from_str::<BTreeMap<String, MonthJson>>(&journal_json).is_err()
+            })
+        {
+            let unreadable_file = self.savedata_dir.join("journal.json.unreadable");
+
+            if fs::rename(&journal_file, &unreadable_file).is_ok() {
+                logbox_mut().log(&format!(
+                    "journal.json couldn't be read and was moved to {}",
+                    unreadable_file.display()
+                ));
+            }
+        }
+
+        if !journal_months.is_empty() {
+            let new_json = preferences().paths.json_format.to_json(&journal_months)?;
+
+            fs::create_dir_all(&self.savedata_dir)?;
+
+            journal_cipher::write_journal_file(&journal_file, &new_json)
+        } else if journal_file.exists() {
+            fs::remove_file(journal_file)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl JournalStorage for SingleFileStorage {
+    fn list_months(&self) -> Vec<String> {
+        self.load_months().into_keys().collect()
+    }
+
+    fn load_month(&self, year_month: &str) -> Option<MonthJson> {
+        self.load_months().remove(year_month)
+    }
+
+    fn save_month(&self, year_month: &str, month_json: &MonthJson) -> io::Result<()> {
+        let mut journal_months = self.load_months();
+        journal_months.insert(year_month.to_string(), month_json.clone());
+
+        self.write_journal(journal_months)
+    }
+
+    fn delete_month(&self, year_month: &str) -> io::Result<()> {
+        let mut journal_months = self.load_months();
+
+        if journal_months.remove(year_month).is_some() {
+            self.write_journal(journal_months)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// the whole file is read at once, rather than once per month
+    fn load_months(&self) -> BTreeMap<String, MonthJson> {
+        if let Some(journal_json) = journal_cipher::read_journal_file(&self.journal_file())
+            && let Ok(months) = serde_json::from_str(&journal_json)
+        {
+            months
+        } else {
+            BTreeMap::new()
+        }
+    }
+
+    /// the whole file is read once, rather than once per month
+    fn load_selected_months(&self, year_months: &[String]) -> BTreeMap<String, MonthJson> {
+        self.load_months()
+            .into_iter()
+            .filter(|(year_month, _month_json)| year_months.contains(year_month))
+            .collect()
+    }
+
+    /// every month is written in a single pass, rather than rewriting the whole journal for each month
+    fn save_months(&self, months: BTreeMap<String, MonthJson>) -> io::Result<()> {
+        let mut journal_months = self.load_months();
+
+        for (year_month, month_json) in months {
+            if month_json.is_empty() {
+                journal_months.remove(&year_month);
+            } else {
+                journal_months.insert(year_month, month_json);
+            }
+        }
+
+        self.write_journal(journal_months)
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug, Clone, Default)]
+/// keeps the months in memory rather than on the disk, so the store can be tested without touching any files. clones
+/// share the same months, so a test can hold on to one to look at what the store saved
+pub struct MemoryStorage {
+    months: std::rc::Rc<std::cell::RefCell<BTreeMap<String, MonthJson>>>,
+
+    /// months that are listed in the storage, but can't be read, like a month file holding invalid json
+    unreadable_months: std::rc::Rc<std::cell::RefCell<std::collections::BTreeSet<String>>>,
+}
+
+#[cfg(test)]
+impl MemoryStorage {
+    /// a copy of every month currently held in the storage
+    pub fn months(&self) -> BTreeMap<String, MonthJson> {
+        self.months.borrow().clone()
+    }
+
+    /// adds a month that is listed in the storage, but can't be read. saving the month replaces it with what was saved,
+    /// the same way the unreadable file is set aside on the disk
+    pub fn add_unreadable_month(&self, year_month: &str) {
+        self.months.borrow_mut().remove(year_month);
+        self.unreadable_months
+            .borrow_mut()
+            .insert(year_month.to_string());
+    }
+
+    /// the months that are still unreadable
+    pub fn unreadable_months(&self) -> Vec<String> {
+        self.unreadable_months.borrow().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+impl JournalStorage for MemoryStorage {
+    fn list_months(&self) -> Vec<String> {
+        let mut year_months = self
+            .months
+            .borrow()
+            .keys()
+            .cloned()
+            .collect::<Vec<String>>();
+        year_months.extend(self.unreadable_months.borrow().iter().cloned());
+        year_months.sort();
+
+        year_months
+    }
+
+    fn load_month(&self, year_month: &str) -> Option<MonthJson> {
+        self.months.borrow().get(year_month).cloned()
+    }
+
+    fn save_month(&self, year_month: &str, month_json: &MonthJson) -> io::Result<()> {
+        self.unreadable_months.borrow_mut().remove(year_month);
+        self.months
+            .borrow_mut()
+            .insert(year_month.to_string(), month_json.clone());
+
+        Ok(())
+    }
+
+    fn delete_month(&self, year_month: &str) -> io::Result<()> {
+        self.unreadable_months.borrow_mut().remove(year_month);
+        self.months.borrow_mut().remove(year_month);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{JsonFormat, journal_pointer, with_test_preferences};
+
+    /// month file storage over a fresh savedata directory holding the given files
+    fn storage_with_files(dir_name: &str, files: &[(&str, &str)]) -> FileSystemStorage {
+        let savedata_dir = journal_pointer::test_dir().join(dir_name);
+
+        let _ = fs::remove_dir_all(&savedata_dir);
+        fs::create_dir_all(&savedata_dir).expect("couldn't create savedata directory");
+
+        for (filename, file_contents) in files {
+            fs::write(savedata_dir.join(filename), file_contents)
+                .expect("couldn't write month file");
+        }
+
+        FileSystemStorage { savedata_dir }
+    }
+
+    #[test]
+    fn differently_cased_files_resolve_to_the_same_month() {
+        let storage = storage_with_files(
+            "duplicate-months",
+            &[
+                ("2024-01.JSON", r#"{"2024-01-02":"hidden"}"#),
+                ("2024-01.json", r#"{"2024-01-01":"used"}"#),
+                ("2024-02.json", r#"{"2024-02-01":"only"}"#),
+            ],
+        );
+
+        let month_filenames = storage.month_filenames();
+        assert_eq!(month_filenames.len(), 2);
+        assert_eq!(
+            month_filenames["2024-01"],
+            vec!["2024-01.json".to_string(), "2024-01.JSON".to_string()]
+        );
+
+        assert_eq!(
+            storage.duplicate_months(),
+            vec![DuplicateMonth {
+                year_month: "2024-01".to_string(),
+                filenames: vec!["2024-01.json".to_string(), "2024-01.JSON".to_string()],
+                used_filename: "2024-01.json".to_string(),
+            }]
+        );
+
+        assert_eq!(storage.list_months(), vec!["2024-01", "2024-02"]);
+
+        let loaded_month = storage.load_month("2024-01").expect("month wasn't loaded");
+        assert!(loaded_month.contains_key("2024-01-01"));
+        assert!(!loaded_month.contains_key("2024-01-02"));
+    }
+
+    #[test]
+    fn month_with_only_an_uppercase_extension_is_not_a_duplicate() {
+        let storage = storage_with_files(
+            "uppercase-month",
+            &[("2024-03.JSON", r#"{"2024-03-01":"uppercase"}"#)],
+        );
+
+        assert!(storage.duplicate_months().is_empty());
+        assert_eq!(storage.list_months(), vec!["2024-03"]);
+
+        let loaded_month = storage.load_month("2024-03").expect("month wasn't loaded");
+        assert!(loaded_month.contains_key("2024-03-01"));
+    }
+
+    #[test]
+    fn months_saved_in_either_json_format_load_the_same() {
+        for json_format in JsonFormat::ALL {
+            with_test_preferences(
+                |preferences| preferences.paths.json_format = json_format,
+                || {
+                    let storage = storage_with_files(&format!("json-format-{}", json_format), &[]);
+
+                    let mut month_json = MonthJson::new();
+                    month_json.insert(
+                        "2024-01-01".to_string(),
+                        Value::String("first line\nsecond line".to_string()),
+                    );
+
+                    storage
+                        .save_month("2024-01", &month_json)
+                        .expect("couldn't save the month");
+
+                    assert_eq!(storage.load_month("2024-01"), Some(month_json));
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn unreadable_month_is_set_aside_before_saving() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let storage = storage_with_files(
+                    "unreadable-month",
+                    &[("2024-04.json", "{\"2024-04-01\": \"cut off")],
+                );
+
+                let mut month_json = MonthJson::new();
+                month_json.insert(
+                    "2024-04-02".to_string(),
+                    Value::String("new entry".to_string()),
+                );
+
+                storage
+                    .save_month("2024-04", &month_json)
+                    .expect("couldn't save the month");
+
+                assert_eq!(storage.load_month("2024-04"), Some(month_json));
+
+                let unreadable_file = storage.savedata_dir.join("2024-04.json.unreadable");
+                assert_eq!(
+                    fs::read_to_string(unreadable_file).expect("the unreadable month wasn't kept"),
+                    "{\"2024-04-01\": \"cut off"
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn saving_months_keeps_each_month_in_its_existing_file() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let storage = storage_with_files(
+                    "batch-save",
+                    &[("2024-03.JSON", r#"{"2024-03-01":"uppercase"}"#)],
+                );
+
+                let mut month_json = MonthJson::new();
+                month_json.insert("2024-03-02".to_string(), Value::String("saved".to_string()));
+
+                storage
+                    .save_months(BTreeMap::from([
+                        ("2024-03".to_string(), month_json.clone()),
+                        ("2024-05".to_string(), month_json.clone()),
+                    ]))
+                    .expect("couldn't save the months");
+
+                assert!(!storage.savedata_dir.join("2024-03.json").exists());
+                assert!(storage.savedata_dir.join("2024-05.json").exists());
+                assert_eq!(
+                    storage.load_months(),
+                    BTreeMap::from([
+                        ("2024-03".to_string(), month_json.clone()),
+                        ("2024-05".to_string(), month_json),
+                    ])
+                );
+            },
+        );
+    }
+}
@@ -1,12 +1,19 @@
 pub mod day_store;
+pub mod draft;
 pub mod global_store;
+pub mod journal_cipher;
+pub mod journal_storage;
 pub mod month_store;
+pub mod search_index;
 pub mod word_count;
 
 // re-exports
 pub use day_store::DayStore;
+pub use draft::Draft;
 pub use global_store::GlobalStore;
+pub use journal_storage::JournalStorage;
 pub use month_store::MonthStore;
+pub use search_index::SearchIndex;
 pub use word_count::TimedWordCount;
 pub use word_count::WordCount;
 pub use word_count::WordCounts;
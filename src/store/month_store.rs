@@ -1,12 +1,28 @@
 use crate::config::preferences;
+use crate::utils::misc_tools;
 
 use super::DayStore;
+use super::journal_storage::MonthJson;
 use super::word_count::{TimedWordCount, WordCount, WordCounts};
 
-use chrono::{Datelike, Days, NaiveDate};
-use serde_json::Value;
-use std::fs;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use std::collections::BTreeMap;
 
+/// the key in the month json holding the dates of every locked day in the month. since the key isn't a date, older
+/// versions ignore it when loading entries
+const LOCKED_DAYS_KEY: &str = "locked_days";
+
+/// the key in the month json holding the tags of every tagged day in the month, keyed by date. like the locked days, it
+/// is only written when at least one day in the month has a tag, so an untagged month with no entries stays empty
+const TAGS_KEY: &str = "tags";
+
+/// the key in the month json holding how many seconds were spent writing each day, keyed by date. months saved before
+/// writing time was tracked don't have it, so every day in them starts at 0
+const WRITING_SECONDS_KEY: &str = "writing_seconds";
+
+/// a month's worth of day stores. there is intentionally no Default implementation: a month store is only ever built
+/// through new, which always generates a properly dated day store for every day in the month. this prevents undated
+/// placeholder days from leaking into edited_days or any other date-based iteration
 #[derive(Debug, Clone)]
 pub struct MonthStore {
     days: Vec<DayStore>,
@@ -51,14 +67,75 @@ impl MonthStore {
         edited_days
     }
 
+    /// the word count of every day in the month, indexed by day of the month starting from 0. the counts are only as
+    /// current as the last word count update
+    pub fn day_word_counts(&self) -> [usize; 31] {
+        let mut day_word_counts = [0; 31];
+        for (i, day_store) in self.days.iter().enumerate().take(31) {
+            day_word_counts[i] = day_store.total_word_count();
+        }
+
+        day_word_counts
+    }
+
     pub fn edited_day_count(&self) -> usize {
         self.edited_days().iter().filter(|day| **day).count()
     }
 
+    /// the total time spent writing across every day of the month. days without any recorded time count as 0
+    pub fn writing_seconds(&self) -> u64 {
+        self.days
+            .iter()
+            .map(|day_store| day_store.writing_seconds())
+            .sum()
+    }
+
     pub fn days(&self) -> impl DoubleEndedIterator<Item = &DayStore> {
         self.days.iter()
     }
 
+    /// true if nothing was ever written into the month: no day has an entry, tags, or a lock, and nothing is waiting
+    /// to be saved
+    pub fn is_blank(&self) -> bool {
+        self.days.iter().all(|day_store| {
+            !day_store.contains_entry()
+                && day_store.tags().is_empty()
+                && !day_store.locked()
+                && !day_store.unsaved()
+        })
+    }
+
+    /// true if any day in the month changed since the month was last saved or loaded
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.days.iter().any(|day_store| day_store.unsaved())
+    }
+
+    /// marks every day in the month as saved
+    pub fn mark_saved(&mut self) {
+        for day_store in &mut self.days {
+            day_store.mark_saved();
+        }
+    }
+
+    /// the days that count towards the averages. by default these are the edited days, but if empty days are included
+    /// in the stats preferences, every day up to today is counted. if weekends are excluded, weekend days are skipped
+    pub fn averaged_days(&self) -> impl Iterator<Item = &DayStore> {
+        let exclude_weekends = preferences().stats.exclude_weekends;
+        let include_empty_days = preferences().stats.include_empty_days;
+
+        let today = Local::now().date_naive();
+
+        self.days.iter().filter(move |day_store| {
+            let counted_day = if include_empty_days {
+                day_store.date() <= today
+            } else {
+                day_store.contains_entry()
+            };
+
+            counted_day && !(exclude_weekends && misc_tools::is_weekend(day_store.date()))
+        })
+    }
+
     /// creates the collection of properly initialized day stores for the month based on the given date
     fn generate_day_stores(first_of_month: NaiveDate) -> Vec<DayStore> {
         let mut day_stores = Vec::new();
@@ -77,40 +154,27 @@ impl MonthStore {
         day_stores
     }
 
-    /// attempts to load the month store of the given date from disk. if a valid month store is found matching the
-    /// date, it is loaded, otherwise an empty month store is generated
-    pub fn load_month(&mut self, first_of_month: NaiveDate) {
+    /// fills the month store with the entries of the given month json. days without an entry in the json are left
+    /// empty
+    pub fn load_month(&mut self, first_of_month: NaiveDate, json_data: &MonthJson) {
         self.first_of_month = first_of_month;
 
-        let filename = self.get_yyyy_mm() + ".json";
-
-        let mut save_file_path = preferences().paths.savedata_dir();
-        save_file_path.push(filename);
-
         self.days.clear();
 
-        match fs::exists(&save_file_path) {
-            Err(_) => {
-                panic!("couldn't determine if file exists");
-            }
-            Ok(file_exists) => {
-                if !file_exists {
-                    self.days = Self::generate_day_stores(self.first_of_month);
-
-                    return;
-                }
-            }
-        }
+        let locked_days: Vec<String> = json_data
+            .get(LOCKED_DAYS_KEY)
+            .and_then(|locked_value| serde_json::from_value(locked_value.clone()).ok())
+            .unwrap_or_default();
 
-        let month_json =
-            fs::read_to_string(&save_file_path).expect("couldn't read json into string");
+        let mut day_tags: BTreeMap<String, Vec<String>> = json_data
+            .get(TAGS_KEY)
+            .and_then(|tags_value| serde_json::from_value(tags_value.clone()).ok())
+            .unwrap_or_default();
 
-        let json_data: serde_json::Map<String, Value> =
-            if let Ok(data) = serde_json::from_str(&month_json) {
-                data
-            } else {
-                serde_json::Map::new()
-            };
+        let writing_seconds: BTreeMap<String, u64> = json_data
+            .get(WRITING_SECONDS_KEY)
+            .and_then(|seconds_value| serde_json::from_value(seconds_value.clone()).ok())
+            .unwrap_or_default();
 
         let mut iterative_date = self.first_of_month;
 
@@ -125,35 +189,31 @@ impl MonthStore {
                 "".to_string()
             };
 
-            let new_day_store = DayStore::with_day_text(iterative_date, entry_text);
+            let mut new_day_store = DayStore::with_day_text(iterative_date, entry_text);
+            if locked_days.contains(&new_date) {
+                new_day_store.set_locked(true);
+            }
+            for tag in day_tags.remove(&new_date).unwrap_or_default() {
+                new_day_store.add_tag(&tag);
+            }
+            if let Some(seconds) = writing_seconds.get(&new_date) {
+                new_day_store.add_writing_seconds(*seconds);
+            }
             self.days.push(new_day_store);
 
             iterative_date = iterative_date
                 .checked_add_days(Days::new(1))
                 .expect("couldn't add day");
         }
-    }
 
-    /// writes the month store to the disk with the filename "YYYY-MM.json"
-    pub fn save_month(&self) {
-        let filename = self.get_yyyy_mm() + ".json";
-
-        let mut save_file_path = preferences().paths.savedata_dir();
-        save_file_path.push(filename);
-
-        let month_json = if let Ok(existing_savedata) = fs::read_to_string(&save_file_path) {
-            existing_savedata
-        } else {
-            "{}".to_string()
-        };
-
-        let mut json_data: serde_json::Map<String, Value> =
-            if let Ok(data) = serde_json::from_str(&month_json) {
-                data
-            } else {
-                serde_json::Map::new()
-            };
+        // the locks, tags, and writing time are restored through the same setters used while editing, which would
+        // otherwise leave the freshly loaded month looking unsaved
+        self.mark_saved();
+    }
 
+    /// writes the month store into the month json as it was last saved. only the modified days are written, so
+    /// anything else in the existing json is kept. if every entry was deleted, the resulting json is empty
+    pub fn save_month(&self, json_data: &mut MonthJson) {
         for i in 0..(self.first_of_month.num_days_in_month() as usize) {
             let new_entry = self.days[i].clone();
 
@@ -171,17 +231,54 @@ impl MonthStore {
             }
         }
 
-        let new_json =
-            serde_json::to_string_pretty(&json_data).expect("couldn't serialize on save");
+        let locked_days: Vec<String> = self
+            .days
+            .iter()
+            .filter(|day_store| day_store.locked())
+            .map(|day_store| day_store.date().to_string())
+            .collect();
+
+        if locked_days.is_empty() {
+            json_data.remove_entry(LOCKED_DAYS_KEY);
+        } else {
+            json_data.insert(
+                LOCKED_DAYS_KEY.to_string(),
+                serde_json::to_value(locked_days).expect("unable to serialize"),
+            );
+        }
+
+        let day_tags: BTreeMap<String, &[String]> = self
+            .days
+            .iter()
+            .filter(|day_store| !day_store.tags().is_empty())
+            .map(|day_store| (day_store.date().to_string(), day_store.tags()))
+            .collect();
 
-        if new_json != "{}" {
-            fs::write(&save_file_path, new_json).expect("couldn't save new json");
+        if day_tags.is_empty() {
+            json_data.remove_entry(TAGS_KEY);
         } else {
-            // if there previously were entries that got deleted on the current save, resulting in the month store
-            // becoming empty, delete the file
-            if save_file_path.exists() {
-                fs::remove_file(save_file_path).expect("couldn't remove existing json");
-            }
+            json_data.insert(
+                TAGS_KEY.to_string(),
+                serde_json::to_value(day_tags).expect("unable to serialize"),
+            );
+        }
+
+        // only days with an entry keep their writing time, so a month that was written in and then cleared still ends
+        // up empty
+        let writing_seconds: BTreeMap<String, u64> = self
+            .days
+            .iter()
+            .filter(|day_store| day_store.contains_entry() && day_store.writing_seconds() > 0)
+            .map(|day_store| (day_store.date().to_string(), day_store.writing_seconds()))
+            .collect();
+
+        if writing_seconds.is_empty() {
+            json_data.remove_entry(WRITING_SECONDS_KEY);
+        } else {
+            json_data.insert(
+                WRITING_SECONDS_KEY.to_string(),
+                serde_json::to_value(writing_seconds).expect("unable to serialize"),
+            );
         }
     }
 }
@@ -250,7 +347,13 @@ impl WordCount for MonthStore {
 
 impl TimedWordCount for MonthStore {
     fn average_words(&self) -> f64 {
-        let average_words = (self.total_word_count() as f64) / (self.edited_day_count() as f64);
+        let (word_count, day_count) =
+            self.averaged_days()
+                .fold((0, 0), |(word_count, day_count), day_store| {
+                    (word_count + day_store.total_word_count(), day_count + 1)
+                });
+
+        let average_words = (word_count as f64) / (day_count as f64);
 
         if average_words.is_finite() {
             average_words
@@ -260,7 +363,13 @@ impl TimedWordCount for MonthStore {
     }
 
     fn average_chars(&self) -> f64 {
-        let average_chars = (self.total_char_count() as f64) / (self.edited_day_count() as f64);
+        let (char_count, day_count) =
+            self.averaged_days()
+                .fold((0, 0), |(char_count, day_count), day_store| {
+                    (char_count + day_store.total_char_count(), day_count + 1)
+                });
+
+        let average_chars = (char_count as f64) / (day_count as f64);
 
         if average_chars.is_finite() {
             average_chars
@@ -269,3 +378,70 @@ impl TimedWordCount for MonthStore {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    #[test]
+    fn new_month_only_holds_dated_days_of_the_month() {
+        let month_store = MonthStore::new(date(2024, 2, 1));
+
+        let dates = month_store
+            .days()
+            .map(|day_store| day_store.date())
+            .collect::<Vec<NaiveDate>>();
+
+        assert_eq!(dates.len(), 29);
+        assert!(
+            dates
+                .iter()
+                .enumerate()
+                .all(|(index, day_date)| *day_date == date(2024, 2, index as u32 + 1))
+        );
+        assert_eq!(month_store.edited_days(), [false; 31]);
+        assert!(month_store.is_blank());
+    }
+
+    #[test]
+    fn loading_into_a_new_month_leaves_no_phantom_days() {
+        let mut month_json = MonthJson::new();
+        month_json.insert(
+            "2024-02-05".to_string(),
+            serde_json::to_value("an entry").expect("unable to serialize"),
+        );
+
+        // a 31 day month loaded with a shorter month must not keep any of its extra days
+        let mut month_store = MonthStore::new(date(2024, 1, 1));
+        month_store.load_month(date(2024, 2, 1), &month_json);
+
+        assert_eq!(month_store.days().count(), 29);
+        assert!(
+            month_store
+                .days()
+                .all(|day_store| day_store.date().month() == 2 && day_store.date().year() == 2024)
+        );
+
+        let mut expected_edited_days = [false; 31];
+        expected_edited_days[4] = true;
+
+        assert_eq!(month_store.edited_days(), expected_edited_days);
+        assert_eq!(month_store.edited_day_count(), 1);
+    }
+
+    #[test]
+    fn writing_time_is_summed_over_the_month() {
+        let mut month_store = MonthStore::new(date(2024, 5, 1));
+        assert_eq!(month_store.writing_seconds(), 0);
+
+        month_store.day_mut(0).add_writing_seconds(90);
+        month_store.day_mut(30).add_writing_seconds(3600);
+
+        // days without any recorded time don't affect the total
+        assert_eq!(month_store.writing_seconds(), 3690);
+    }
+}
@@ -0,0 +1,294 @@
+use super::MonthStore;
+
+use chrono::{Datelike, NaiveDate};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// an inverted index of every word in the journal, mapping each word to the dates whose entries contain it. words are
+/// runs of alphanumeric characters, stored in lowercase. searching only narrows the dates down to the ones that could
+/// hold a match, so the entries of the candidate dates still have to be checked for the actual match
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    /// every indexed word, and the dates whose entries contain it
+    word_dates: BTreeMap<String, BTreeSet<NaiveDate>>,
+
+    /// the words each date was last indexed with, so they can be taken back out when the entry changes
+    date_words: BTreeMap<NaiveDate, BTreeSet<String>>,
+}
+
+/// how a word in the index has to relate to a word of the query for the index to count it as a match
+enum WordMatch {
+    /// the query word is surrounded by other characters, so it can only match a whole word
+    Exact,
+
+    /// the query word starts the query, so it may be the end of a longer word
+    Suffix,
+
+    /// the query word ends the query, so it may be the start of a longer word
+    Prefix,
+
+    /// the query word is the whole query, so it may be anywhere inside a longer word
+    Contains,
+}
+
+impl SearchIndex {
+    /// splits the text into its lowercase words
+    fn words(text: &str) -> BTreeSet<String> {
+        text.split(|character: char| !character.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_lowercase())
+            .collect()
+    }
+
+    /// replaces whatever the date was indexed with by the words of the text
+    pub fn index_day(&mut self, date: NaiveDate, text: &str) {
+        self.remove_day(date);
+
+        let words = Self::words(text);
+
+        if words.is_empty() {
+            return;
+        }
+
+        for word in &words {
+            self.word_dates
+                .entry(word.clone())
+                .or_default()
+                .insert(date);
+        }
+
+        self.date_words.insert(date, words);
+    }
+
+    /// takes the date out of the index
+    pub fn remove_day(&mut self, date: NaiveDate) {
+        let Some(words) = self.date_words.remove(&date) else {
+            return;
+        };
+
+        for word in words {
+            if let Some(dates) = self.word_dates.get_mut(&word) {
+                dates.remove(&date);
+
+                if dates.is_empty() {
+                    self.word_dates.remove(&word);
+                }
+            }
+        }
+    }
+
+    /// reindexes every day of the month, replacing anything the month was indexed with before
+    pub fn index_month(&mut self, month_store: &MonthStore) {
+        self.remove_month(month_store.first_of_month());
+
+        for day_store in month_store.days() {
+            self.index_day(day_store.date(), &day_store.get_day_text());
+        }
+    }
+
+    /// takes every day of the month starting on the given date out of the index
+    pub fn remove_month(&mut self, first_of_month: NaiveDate) {
+        let month_dates = self
+            .date_words
+            .keys()
+            .filter(|date| {
+                date.year() == first_of_month.year() && date.month() == first_of_month.month()
+            })
+            .copied()
+            .collect::<Vec<NaiveDate>>();
+
+        for date in month_dates {
+            self.remove_day(date);
+        }
+    }
+
+    /// the dates of every indexed word that matches the query word
+    fn matching_dates(&self, query_word: &str, word_match: WordMatch) -> BTreeSet<NaiveDate> {
+        let mut dates = BTreeSet::new();
+
+        match word_match {
+            WordMatch::Exact => {
+                if let Some(word_dates) = self.word_dates.get(query_word) {
+                    dates.extend(word_dates);
+                }
+            }
+            // words sharing a prefix are next to each other in the index, so only they have to be looked at
+            WordMatch::Prefix => {
+                for (_word, word_dates) in self
+                    .word_dates
+                    .range(query_word.to_string()..)
+                    .take_while(|(word, _word_dates)| word.starts_with(query_word))
+                {
+                    dates.extend(word_dates);
+                }
+            }
+            WordMatch::Suffix => {
+                for (_word, word_dates) in self
+                    .word_dates
+                    .iter()
+                    .filter(|(word, _word_dates)| word.ends_with(query_word))
+                {
+                    dates.extend(word_dates);
+                }
+            }
+            WordMatch::Contains => {
+                for (_word, word_dates) in self
+                    .word_dates
+                    .iter()
+                    .filter(|(word, _word_dates)| word.contains(query_word))
+                {
+                    dates.extend(word_dates);
+                }
+            }
+        }
+
+        dates
+    }
+
+    /// the dates whose entries could contain the query, ignoring case. every date that does contain it is included,
+    /// but some that don't may be as well. returns None if the query has no words to look up, in which case every
+    /// date has to be searched
+    pub fn candidate_dates(&self, query: &str) -> Option<BTreeSet<NaiveDate>> {
+        let mut candidate_dates: Option<BTreeSet<NaiveDate>> = None;
+
+        let mut word_start = None;
+
+        for (index, character) in query.char_indices().chain([(query.len(), ' ')]) {
+            if character.is_alphanumeric() && index < query.len() {
+                word_start.get_or_insert(index);
+                continue;
+            }
+
+            let Some(start) = word_start.take() else {
+                continue;
+            };
+
+            let word_match = match (start > 0, index < query.len()) {
+                (true, true) => WordMatch::Exact,
+                (false, true) => WordMatch::Suffix,
+                (true, false) => WordMatch::Prefix,
+                (false, false) => WordMatch::Contains,
+            };
+
+            let word_dates = self.matching_dates(&query[start..index].to_lowercase(), word_match);
+
+            let dates = match candidate_dates {
+                Some(dates) => dates.intersection(&word_dates).copied().collect(),
+                None => word_dates,
+            };
+
+            if dates.is_empty() {
+                return Some(dates);
+            }
+
+            candidate_dates = Some(dates);
+        }
+
+        candidate_dates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    /// an index of two days that share some of their words
+    fn two_day_index() -> SearchIndex {
+        let mut search_index = SearchIndex::default();
+        search_index.index_day(date(2024, 5, 1), "The quick brown fox. (Fine, mostly!)");
+        search_index.index_day(date(2024, 5, 2), "Quick thinking saved the day");
+
+        search_index
+    }
+
+    #[test]
+    fn punctuation_around_the_query_is_ignored() {
+        let search_index = two_day_index();
+
+        assert_eq!(
+            search_index.candidate_dates("(fine,"),
+            Some(BTreeSet::from([date(2024, 5, 1)]))
+        );
+        assert_eq!(
+            search_index.candidate_dates("mostly!)"),
+            Some(BTreeSet::from([date(2024, 5, 1)]))
+        );
+        assert_eq!(search_index.candidate_dates("...!"), None);
+    }
+
+    #[test]
+    fn every_word_of_the_query_has_to_be_found() {
+        let search_index = two_day_index();
+
+        assert_eq!(
+            search_index.candidate_dates("quick brown"),
+            Some(BTreeSet::from([date(2024, 5, 1)]))
+        );
+        assert_eq!(
+            search_index.candidate_dates("the quick"),
+            Some(BTreeSet::from([date(2024, 5, 1), date(2024, 5, 2)]))
+        );
+        assert_eq!(
+            search_index.candidate_dates("brown thinking"),
+            Some(BTreeSet::new())
+        );
+    }
+
+    #[test]
+    fn queries_can_start_and_end_mid_word() {
+        let search_index = two_day_index();
+
+        // "ick" can only end a word and "bro" can only start one, while a lone word can be anywhere in a word
+        assert_eq!(
+            search_index.candidate_dates("ick bro"),
+            Some(BTreeSet::from([date(2024, 5, 1)]))
+        );
+        assert_eq!(
+            search_index.candidate_dates("uic"),
+            Some(BTreeSet::from([date(2024, 5, 1), date(2024, 5, 2)]))
+        );
+        assert_eq!(
+            search_index.candidate_dates("ick thin"),
+            Some(BTreeSet::from([date(2024, 5, 2)]))
+        );
+        assert_eq!(
+            search_index.candidate_dates("k brown f"),
+            Some(BTreeSet::from([date(2024, 5, 1)]))
+        );
+    }
+
+    #[test]
+    fn differently_cased_queries_still_find_their_dates() {
+        let search_index = two_day_index();
+
+        // the index ignores case, so a case sensitive search gets the same candidates and checks the case itself
+        assert_eq!(
+            search_index.candidate_dates("QUICK"),
+            Some(BTreeSet::from([date(2024, 5, 1), date(2024, 5, 2)]))
+        );
+        assert_eq!(
+            search_index.candidate_dates("Brown"),
+            Some(BTreeSet::from([date(2024, 5, 1)]))
+        );
+    }
+
+    #[test]
+    fn reindexing_a_day_replaces_its_words() {
+        let mut search_index = two_day_index();
+
+        search_index.index_day(date(2024, 5, 1), "something else entirely");
+
+        assert_eq!(search_index.candidate_dates("brown"), Some(BTreeSet::new()));
+        assert_eq!(
+            search_index.candidate_dates("quick"),
+            Some(BTreeSet::from([date(2024, 5, 2)]))
+        );
+
+        search_index.remove_month(date(2024, 5, 1));
+
+        assert_eq!(search_index.candidate_dates("quick"), Some(BTreeSet::new()));
+    }
+}
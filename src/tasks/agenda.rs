@@ -0,0 +1,40 @@
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone)]
+/// a read only summary of a single task on a single day, as shown in the agenda
+pub struct AgendaItem {
+    name: String,
+    date: NaiveDate,
+    completed: bool,
+}
+
+impl AgendaItem {
+    /// creates a new AgendaItem for the task with the given name, scheduled on the given date
+    pub fn new(name: String, date: NaiveDate, completed: bool) -> Self {
+        Self {
+            name,
+            date,
+            completed,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn completed(&self) -> bool {
+        self.completed
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+/// everything on the agenda for a single day: the tasks scheduled on the day, and the tasks from previous days that
+/// were never completed
+pub struct Agenda {
+    pub(crate) scheduled: Vec<AgendaItem>,
+    pub(crate) overdue: Vec<AgendaItem>,
+}
@@ -1,9 +1,9 @@
-use chrono::{Days, NaiveDate};
+use chrono::{Days, NaiveDate, NaiveTime};
 use iced::{Element, widget::column};
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fs};
 
-use super::{MultiBinaryMessage, MultiBinaryTask, StandardMessage, TaskData, TaskId};
+use super::{AgendaItem, MultiBinaryMessage, MultiBinaryTask, StandardMessage, TaskData, TaskId};
 use crate::{
     config::preferences,
     custom_widgets::{
@@ -27,9 +27,26 @@ pub struct TaskEventTask {
     task: TaskData,
     #[serde(skip)]
     expanded: bool,
+
+    /// True once the task was expanded or collapsed by hand this session, after which the expansion is no longer
+    /// decided by the auto expand preference
+    #[serde(skip)]
+    expansion_overridden: bool,
     end_behavior: PastDate,
 }
 
+impl TaskEventTask {
+    /// Returns true if the task should be shown expanded. Unless the expansion was toggled by hand this session, tasks
+    /// with text are expanded when the auto expand preference is enabled
+    fn is_expanded(&self) -> bool {
+        if self.expansion_overridden || !preferences().general.auto_expand_tasks {
+            return self.expanded;
+        }
+
+        self.expanded || self.task.has_text()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum EventTaskAction {
     PressMenu,
@@ -101,8 +118,37 @@ impl EventTask {
         false
     }
 
+    /// Returns the last day the event spans
+    fn last_day(&self) -> NaiveDate {
+        self.date
+            .checked_add_days(Days::new(self.length_days.unwrap_or(0) as u64))
+            .unwrap_or(self.date)
+    }
+
+    /// Returns true if the event has a task that wasn't completed by the last day of the event
+    pub fn is_overdue(&self, active_date: NaiveDate) -> bool {
+        self.task_data
+            .as_ref()
+            .is_some_and(|task| !task.task.is_completed())
+            && active_date > self.last_day()
+    }
+
+    /// Returns the agenda item of the event
+    fn agenda_item(&self) -> AgendaItem {
+        let completed = self
+            .task_data
+            .as_ref()
+            .is_some_and(|task| task.task.is_completed());
+
+        AgendaItem::new(self.name.clone(), self.date, completed)
+    }
+
     /// Constructs the event task ui element
-    pub fn build_event<'a>(&'a self, options_expanded: bool) -> Element<'a, EventTaskAction> {
+    pub fn build_event<'a>(
+        &'a self,
+        options_expanded: bool,
+        focused: bool,
+    ) -> Element<'a, EventTaskAction> {
         let checkbox = if let Some(task) = &self.task_data {
             match &task.task {
                 TaskData::Standard(standard_task) => Some((
@@ -130,7 +176,7 @@ impl EventTask {
                 .map(EventTaskAction::MultiBinaryTask),
             };
 
-            if task.expanded {
+            if task.is_expanded() {
                 Some((Some(expanded_ui), EventTaskAction::ToggleDown))
             } else {
                 Some((None, EventTaskAction::ToggleDown))
@@ -158,6 +204,7 @@ impl EventTask {
             expanded,
             EventTaskAction::PressMenu,
             menu,
+            focused,
         )
     }
 }
@@ -166,6 +213,10 @@ impl EventTask {
 /// Collection of the event tasks
 pub struct EventTasks {
     events: BTreeMap<TaskId, EventTask>,
+
+    /// true if any event was changed or deleted since the events were last saved
+    #[serde(skip)]
+    dirty: bool,
 }
 
 impl EventTasks {
@@ -188,7 +239,7 @@ impl EventTasks {
     }
 
     /// Write all events to disk
-    pub fn save_events(&self) {
+    pub fn save_events(&mut self) {
         let mut template_path = preferences().paths.event_tasks_dir();
         template_path.push("events.json");
 
@@ -196,6 +247,13 @@ impl EventTasks {
             serde_json::to_string_pretty(self).expect("couldn't serialize event tasks");
 
         fs::write(template_path, template_json).expect("couldn't save event task json");
+
+        self.dirty = false;
+    }
+
+    /// Returns true if any event was changed or deleted since the events were last saved
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
     }
 
     /// Updates interal state of the EventTasks based on the message
@@ -205,12 +263,14 @@ impl EventTasks {
                 EventTaskAction::PressMenu => {}
                 EventTaskAction::DeleteEvent => {
                     self.events.remove(&message.task_id);
+                    self.dirty = true;
                 }
                 EventTaskAction::StandardTask(standard_task_message) => {
                     if let Some(once_task) = &mut event_task.task_data
                         && let TaskData::Standard(standard_task) = &mut once_task.task
                     {
                         standard_task.update(standard_task_message);
+                        self.dirty = true;
                     }
                 }
                 EventTaskAction::MultiBinaryTask(multi_binary_task_message) => {
@@ -218,11 +278,13 @@ impl EventTasks {
                         && let TaskData::MultiBinary(multi_binary_task) = &mut once_task.task
                     {
                         multi_binary_task.element.update(multi_binary_task_message);
+                        self.dirty = true;
                     }
                 }
                 EventTaskAction::ToggleDown => {
                     if let Some(once_task) = &mut event_task.task_data {
-                        once_task.expanded = !once_task.expanded;
+                        once_task.expanded = !once_task.is_expanded();
+                        once_task.expansion_overridden = true;
                     }
                 }
             }
@@ -234,10 +296,11 @@ impl EventTasks {
         &'a self,
         task_id: TaskId,
         options_expanded: bool,
+        focused: bool,
     ) -> Element<'a, EventTaskMessage> {
         if let Some(event_task) = self.events.get(&task_id) {
             event_task
-                .build_event(options_expanded)
+                .build_event(options_expanded, focused)
                 .map(move |event_message| EventTaskMessage {
                     message: event_message,
                     task_id,
@@ -247,6 +310,66 @@ impl EventTasks {
         }
     }
 
+    /// Returns true if the TaskId belongs to an event
+    pub fn contains(&self, task_id: TaskId) -> bool {
+        self.events.contains_key(&task_id)
+    }
+
+    /// Toggles the completion of the event's task, the same as clicking its checkbox
+    pub fn toggle_completion(&mut self, task_id: TaskId) {
+        let message = match self
+            .events
+            .get(&task_id)
+            .and_then(|event| event.task_data.as_ref())
+            .map(|event_task| &event_task.task)
+        {
+            Some(TaskData::Standard(_standard_task)) => {
+                EventTaskAction::StandardTask(StandardMessage::ToggledCheckbox)
+            }
+            Some(TaskData::MultiBinary(_multi_binary_task)) => {
+                EventTaskAction::MultiBinaryTask(MultiBinaryMessage::ToggledOverride)
+            }
+            None => return,
+        };
+
+        self.update(EventTaskMessage { message, task_id });
+    }
+
+    /// Returns the agenda items of the events active on the given date that aren't overdue
+    pub fn scheduled_agenda_items(&self, active_date: NaiveDate) -> Vec<AgendaItem> {
+        self.events
+            .values()
+            .filter(|event| event.is_active(active_date) && !event.is_overdue(active_date))
+            .map(|event| event.agenda_item())
+            .collect()
+    }
+
+    /// Returns the agenda items of the events whose tasks weren't completed before the given date, and whose last day
+    /// was within the lookback_days before it
+    pub fn overdue_agenda_items(
+        &self,
+        active_date: NaiveDate,
+        lookback_days: u64,
+    ) -> Vec<AgendaItem> {
+        let lookback_start = active_date
+            .checked_sub_days(Days::new(lookback_days))
+            .unwrap_or(NaiveDate::MIN);
+
+        self.events
+            .values()
+            .filter(|event| event.is_overdue(active_date) && event.last_day() >= lookback_start)
+            .map(|event| event.agenda_item())
+            .collect()
+    }
+
+    /// Returns the time of day the event at the given TaskId is due, if it has one
+    pub fn due_time(&self, task_id: TaskId) -> Option<NaiveTime> {
+        self.events
+            .get(&task_id)
+            .and_then(|event| event.task_data.as_ref())
+            .and_then(|event_task| event_task.task.due_time())
+    }
+
     /// Returns the events that are active on the given date
     pub fn get_active_event_ids(&self, active_date: NaiveDate) -> Vec<TaskId> {
         self.events
@@ -256,3 +379,52 @@ impl EventTasks {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::task_data::StandardTask;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    /// An event with an uncompleted task, kept indefinitely until it is completed
+    fn uncompleted_event(name: &str, event_date: NaiveDate, length_days: Option<u32>) -> EventTask {
+        EventTask {
+            name: name.to_string(),
+            date: event_date,
+            length_days,
+            preview_days: None,
+            task_data: Some(TaskEventTask {
+                task: TaskData::Standard(StandardTask::default()),
+                expanded: false,
+                expansion_overridden: false,
+                end_behavior: PastDate::RetainIndefinite,
+            }),
+        }
+    }
+
+    #[test]
+    fn overdue_events_are_limited_to_the_lookback() {
+        let mut event_tasks = EventTasks::default();
+
+        for event in [
+            uncompleted_event("last week", date(2024, 5, 3), None),
+            uncompleted_event("long ago", date(2024, 4, 1), None),
+            uncompleted_event("ended last week", date(2024, 4, 20), Some(13)),
+            uncompleted_event("today", date(2024, 5, 10), None),
+        ] {
+            event_tasks.events.insert(TaskId::new_unique_id(), event);
+        }
+
+        let mut overdue_names: Vec<String> = event_tasks
+            .overdue_agenda_items(date(2024, 5, 10), 7)
+            .iter()
+            .map(|agenda_item| agenda_item.name().to_string())
+            .collect();
+        overdue_names.sort();
+
+        assert_eq!(overdue_names, vec!["ended last week", "last week"]);
+    }
+}
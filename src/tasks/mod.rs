@@ -1,3 +1,4 @@
+pub mod agenda;
 pub mod event_tasks;
 pub mod task_data;
 pub mod task_id;
@@ -5,6 +6,10 @@ pub mod task_manager;
 pub mod template_tasks;
 
 // re-exports
+pub use agenda::Agenda;
+pub use agenda::AgendaItem;
+pub use task_data::CounterData;
+pub use task_data::CounterMessage;
 pub use task_data::MultiBinaryMessage;
 pub use task_data::MultiBinaryTask;
 pub use task_data::MultiBinaryTaskElement;
@@ -1,8 +1,10 @@
+use chrono::NaiveTime;
 use iced::{
     Element,
     widget::{self, Space, Text, column, row},
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Display;
 
 use crate::content::{ContentAction, UpgradedContent};
 
@@ -11,6 +13,7 @@ use crate::content::{ContentAction, UpgradedContent};
 pub enum TaskType {
     Standard,
     MultiBinary,
+    Counter,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -18,6 +21,7 @@ pub enum TaskType {
 pub enum StandardMessage {
     ToggledCheckbox,
     TextEdit(ContentAction),
+    SetDueTime(Option<NaiveTime>),
 }
 
 #[derive(Debug, Default)]
@@ -25,6 +29,7 @@ pub enum StandardMessage {
 pub struct StandardTask {
     completed: bool,
     text: UpgradedContent,
+    due_time: Option<NaiveTime>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -32,6 +37,8 @@ pub struct StandardTask {
 struct StandardTaskDisk {
     completed: bool,
     text: String,
+    #[serde(default)]
+    due_time: Option<NaiveTime>,
 }
 
 impl StandardTask {
@@ -44,20 +51,35 @@ impl StandardTask {
             StandardMessage::TextEdit(content_action) => {
                 self.text.perform(content_action);
             }
+            StandardMessage::SetDueTime(due_time) => {
+                self.due_time = due_time;
+            }
         }
     }
 
+    /// Returns the time of day the task is due, if it has one
+    pub fn due_time(&self) -> Option<NaiveTime> {
+        self.due_time
+    }
+
     /// Returns the completion status of the task element
     pub fn is_completed(&self) -> bool {
         self.completed
     }
 
+    /// Returns true if the task's text box contains anything other than whitespace
+    pub fn has_text(&self) -> bool {
+        !self.text.text().trim().is_empty()
+    }
+
     /// The expanded ui that drops down for the standard task
     pub fn expanded_ui<'a>(&'a self) -> Element<'a, StandardMessage> {
+        let due_time = due_time_picker(self.due_time, StandardMessage::SetDueTime);
+
         let text = widget::text_editor(self.text.raw_content())
             .on_action(move |action| StandardMessage::TextEdit(ContentAction::Standard(action)));
 
-        text.into()
+        column![due_time, text].into()
     }
 }
 
@@ -66,6 +88,7 @@ impl From<&StandardTask> for StandardTaskDisk {
         StandardTaskDisk {
             completed: non_disk.completed,
             text: non_disk.text.text(),
+            due_time: non_disk.due_time,
         }
     }
 }
@@ -75,6 +98,7 @@ impl From<&StandardTaskDisk> for StandardTask {
         StandardTask {
             completed: disk.completed,
             text: UpgradedContent::with_text(&disk.text),
+            due_time: disk.due_time,
         }
     }
 }
@@ -108,6 +132,7 @@ pub enum MultiBinaryMessage {
     ToggledNth(usize),
     ToggledOverride,
     TextEdit(ContentAction),
+    SetDueTime(Option<NaiveTime>),
 }
 
 #[derive(Debug, Default)]
@@ -116,6 +141,7 @@ pub struct MultiBinaryTaskElement {
     subtask_completion: Vec<bool>,
     completion_override: bool,
     text: UpgradedContent,
+    due_time: Option<NaiveTime>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -124,6 +150,8 @@ struct MultiBinaryTaskDisk {
     subtask_completion: Vec<bool>,
     completion_override: bool,
     text: String,
+    #[serde(default)]
+    due_time: Option<NaiveTime>,
 }
 
 impl MultiBinaryTaskElement {
@@ -143,6 +171,9 @@ impl MultiBinaryTaskElement {
             MultiBinaryMessage::TextEdit(content_action) => {
                 self.text.perform(content_action);
             }
+            MultiBinaryMessage::SetDueTime(due_time) => {
+                self.due_time = due_time;
+            }
         }
     }
 
@@ -152,6 +183,7 @@ impl MultiBinaryTaskElement {
             subtask_completion: vec![false; subtask_count],
             completion_override: false,
             text: UpgradedContent::default(),
+            due_time: None,
         }
     }
 
@@ -159,6 +191,11 @@ impl MultiBinaryTaskElement {
         &self.subtask_completion
     }
 
+    /// Returns the time of day the task is due, if it has one
+    pub fn due_time(&self) -> Option<NaiveTime> {
+        self.due_time
+    }
+
     /// Returns if the task as a whole is completed. If all subtasks are complete this returns true. If the completion
     /// has been manually overriden, this will return true, even if not all subtasks have been completed
     pub fn is_completed(&self) -> bool {
@@ -171,6 +208,11 @@ impl MultiBinaryTaskElement {
             .all(|&subtask_completed| subtask_completed)
     }
 
+    /// Returns true if the task's text box contains anything other than whitespace
+    pub fn has_text(&self) -> bool {
+        !self.text.text().trim().is_empty()
+    }
+
     /// Performs the action on the underlying UpgradedContent
     pub fn content_perform(&mut self, content_action: ContentAction) {
         self.text.perform(content_action);
@@ -183,6 +225,7 @@ impl From<&MultiBinaryTaskElement> for MultiBinaryTaskDisk {
             subtask_completion: non_disk.subtask_completion.clone(),
             completion_override: non_disk.completion_override,
             text: non_disk.text.text(),
+            due_time: non_disk.due_time,
         }
     }
 }
@@ -193,6 +236,7 @@ impl From<&MultiBinaryTaskDisk> for MultiBinaryTaskElement {
             subtask_completion: disk.subtask_completion.clone(),
             completion_override: disk.completion_override,
             text: UpgradedContent::with_text(&disk.text),
+            due_time: disk.due_time,
         }
     }
 }
@@ -249,10 +293,86 @@ impl MultiBinaryTask {
             ]);
         }
 
+        let due_time = due_time_picker(element.due_time, MultiBinaryMessage::SetDueTime);
+
         let text = widget::text_editor(element.text.raw_content())
             .on_action(move |action| MultiBinaryMessage::TextEdit(ContentAction::Standard(action)));
 
-        column![subtask_checkboxes, text].into()
+        column![subtask_checkboxes, due_time, text].into()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Types of messages generated by Counter tasks
+pub enum CounterMessage {
+    Increment,
+    Decrement,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+/// A task that counts how many times something was done on a day, like glasses of water
+pub struct CounterData {
+    count: u32,
+
+    /// The count that completes the task. It is kept with each day, so changing the target doesn't rewrite the history
+    /// of the task
+    target: Option<u32>,
+}
+
+impl CounterData {
+    /// Creates a new counter at 0 with the given target
+    pub fn with_target(target: Option<u32>) -> Self {
+        Self { count: 0, target }
+    }
+
+    /// Update task state based on the message
+    pub fn update(&mut self, message: CounterMessage) {
+        match message {
+            CounterMessage::Increment => {
+                self.count = self.count.saturating_add(1);
+            }
+            CounterMessage::Decrement => {
+                self.count = self.count.saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns if the counter is completed. A counter with a target is completed once the count reaches it, and one
+    /// without a target is completed once anything was counted
+    pub fn is_completed(&self) -> bool {
+        match self.target {
+            Some(target) => self.count >= target,
+            None => self.count > 0,
+        }
+    }
+
+    /// The count as displayed next to the task name, along with the target if there is one
+    pub fn count_text(&self) -> String {
+        match self.target {
+            Some(target) => format!("{} / {}", self.count, target),
+            None => self.count.to_string(),
+        }
+    }
+
+    /// The expanded ui that drops down for the counter task
+    pub fn expanded_ui<'a>(&self) -> Element<'a, CounterMessage> {
+        let decrement_button = widget::button(Text::new("-"))
+            .on_press_maybe((self.count > 0).then_some(CounterMessage::Decrement));
+        let increment_button = widget::button(Text::new("+")).on_press(CounterMessage::Increment);
+
+        row![
+            Space::new().width(15),
+            decrement_button,
+            Space::new().width(5),
+            Text::new(self.count_text()),
+            Space::new().width(5),
+            increment_button
+        ]
+        .into()
     }
 }
 
@@ -262,3 +382,71 @@ pub enum TaskData {
     Standard(StandardTask),
     MultiBinary(MultiBinaryTask),
 }
+
+impl TaskData {
+    /// Returns true if the task has been completed
+    pub fn is_completed(&self) -> bool {
+        match self {
+            TaskData::Standard(standard_task) => standard_task.is_completed(),
+            TaskData::MultiBinary(multi_binary_task) => multi_binary_task.element.is_completed(),
+        }
+    }
+
+    /// Returns the time of day the task is due, if it has one
+    pub fn due_time(&self) -> Option<NaiveTime> {
+        match self {
+            TaskData::Standard(standard_task) => standard_task.due_time(),
+            TaskData::MultiBinary(multi_binary_task) => multi_binary_task.element.due_time(),
+        }
+    }
+
+    /// Returns true if the task's text box contains anything other than whitespace
+    pub fn has_text(&self) -> bool {
+        match self {
+            TaskData::Standard(standard_task) => standard_task.has_text(),
+            TaskData::MultiBinary(multi_binary_task) => multi_binary_task.element.has_text(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// A time of day that can be picked as a task's due time
+struct DueTimeOption(NaiveTime);
+
+impl Display for DueTimeOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.format("%H:%M"))
+    }
+}
+
+/// Builds the row for picking the time of day a task is due, in half hour steps, along with a button to remove the due
+/// time. The message is created with the newly picked time, or None when the due time is removed
+fn due_time_picker<'a, Message: Clone + 'a>(
+    due_time: Option<NaiveTime>,
+    on_pick: fn(Option<NaiveTime>) -> Message,
+) -> Element<'a, Message> {
+    const MINUTES_PER_STEP: u32 = 30;
+
+    let time_options = (0..24 * 60)
+        .step_by(MINUTES_PER_STEP as usize)
+        .filter_map(|minutes| NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0))
+        .map(DueTimeOption)
+        .collect::<Vec<DueTimeOption>>();
+
+    let time_picker = widget::pick_list(time_options, due_time.map(DueTimeOption), move |picked| {
+        on_pick(Some(picked.0))
+    })
+    .placeholder("No time");
+
+    let clear_button = widget::button(Text::new("Clear"))
+        .on_press_maybe(due_time.is_some().then_some(on_pick(None)));
+
+    row![
+        Space::new().width(15),
+        Text::new("Due at "),
+        time_picker,
+        Space::new().width(5),
+        clear_button
+    ]
+    .into()
+}
@@ -3,7 +3,7 @@ use iced::{Element, widget::column};
 
 use super::template_tasks::{TemplateTaskMessage, TemplateTasks};
 use super::{
-    TaskId,
+    Agenda, TaskId,
     event_tasks::{EventTaskMessage, EventTasks},
 };
 use crate::tasks::event_tasks::EventTaskAction;
@@ -44,6 +44,17 @@ impl TaskMessage {
         self.task_id
     }
 
+    /// Returns true if the message asks for its template task to be deleted
+    pub fn is_delete_template_message(&self) -> bool {
+        match &self.message {
+            TaskMessageAction::Template(template_task_message) => matches!(
+                template_task_message.message,
+                TemplateMessage::Common(CommonMessage::DeleteTemplate)
+            ),
+            TaskMessageAction::Event(_event_task_message) => false,
+        }
+    }
+
     pub fn is_options_menu_message(&self) -> bool {
         match &self.message {
             TaskMessageAction::Template(template_task_message) => matches!(
@@ -87,47 +98,106 @@ impl TaskManager {
         self.menu_open = None;
     }
 
-    /// Constructs all tasks scheduled to be active on the given date
-    pub fn build_tasks<'a>(&'a self, active_date: NaiveDate) -> Element<'a, TaskMessage> {
-        let mut tasks = column![];
-
-        let event_ids = self.event_tasks.get_active_event_ids(active_date);
+    /// Returns the ids of all tasks scheduled to be active on the given date. Tasks are ordered by their due times,
+    /// with the tasks that don't have a due time after them
+    pub fn ordered_task_ids(&self, active_date: NaiveDate) -> Vec<TaskId> {
+        let mut task_ids = vec![];
 
-        for id in event_ids {
-            let options_expanded = Some(id) == self.menu_open;
+        for id in self.event_tasks.get_active_event_ids(active_date) {
+            task_ids.push((self.event_tasks.due_time(id), id));
+        }
 
-            tasks = tasks.push(self.event_tasks.build_event(id, options_expanded).map(
-                move |event_message| TaskMessage {
-                    message: TaskMessageAction::Event(event_message),
-                    task_id: id,
-                },
-            ));
+        for id in self.template_tasks.get_active_template_ids(active_date) {
+            task_ids.push((self.template_tasks.due_time(id, active_date), id));
         }
 
-        let template_ids = self.template_tasks.get_active_template_ids(active_date);
+        // the sort is stable, so tasks due at the same time (or not at all) keep their usual order
+        task_ids.sort_by_key(|(due_time, _id)| (due_time.is_none(), *due_time));
+
+        task_ids.into_iter().map(|(_due_time, id)| id).collect()
+    }
 
-        for id in template_ids {
+    /// Constructs all tasks scheduled to be active on the given date, in the order of ordered_task_ids. The focused
+    /// task, if any, is outlined
+    pub fn build_tasks<'a>(
+        &'a self,
+        active_date: NaiveDate,
+        focused_task: Option<TaskId>,
+    ) -> Element<'a, TaskMessage> {
+        let built_tasks = self.ordered_task_ids(active_date).into_iter().map(|id| {
             let options_expanded = Some(id) == self.menu_open;
+            let focused = Some(id) == focused_task;
 
-            tasks = tasks.push(
+            if self.event_tasks.contains(id) {
+                self.event_tasks
+                    .build_event(id, options_expanded, focused)
+                    .map(move |event_message| TaskMessage {
+                        message: TaskMessageAction::Event(event_message),
+                        task_id: id,
+                    })
+            } else {
                 self.template_tasks
-                    .build_template(id, active_date, options_expanded)
+                    .build_template(id, active_date, options_expanded, focused)
                     .map(move |template_message| TaskMessage {
                         message: TaskMessageAction::Template(template_message),
                         task_id: id,
-                    }),
-            );
+                    })
+            }
+        });
+
+        column(built_tasks).into()
+    }
+
+    /// Toggles the completion of the task on the given date, the same as clicking its checkbox
+    pub fn toggle_completion(&mut self, active_date: NaiveDate, task_id: TaskId) {
+        if self.event_tasks.contains(task_id) {
+            self.event_tasks.toggle_completion(task_id);
+        } else {
+            self.template_tasks.toggle_completion(active_date, task_id);
         }
+    }
+
+    /// Collects the agenda of the given date: everything scheduled on the date and the uncompleted tasks of the
+    /// previous week
+    pub fn agenda(&self, active_date: NaiveDate) -> Agenda {
+        const OVERDUE_LOOKBACK_DAYS: u64 = 7;
+
+        let mut scheduled = self.event_tasks.scheduled_agenda_items(active_date);
+        scheduled.extend(self.template_tasks.scheduled_agenda_items(active_date));
+
+        let mut overdue = self
+            .event_tasks
+            .overdue_agenda_items(active_date, OVERDUE_LOOKBACK_DAYS);
+        overdue.extend(
+            self.template_tasks
+                .overdue_agenda_items(active_date, OVERDUE_LOOKBACK_DAYS),
+        );
+        overdue.sort_by_key(|agenda_item| agenda_item.date());
 
-        tasks.into()
+        Agenda { scheduled, overdue }
     }
 
-    /// Saves all tasks to disk
-    pub fn save_all(&self) {
+    /// Saves all tasks to disk. Template tasks are only written if they changed since the last save
+    pub fn save_all(&mut self) {
         self.template_tasks.save_templates();
         self.event_tasks.save_events();
     }
 
+    /// Returns true if any task changed since the tasks were last saved, including checking tasks off
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.template_tasks.is_dirty() || self.event_tasks.is_dirty()
+    }
+
+    /// Returns a TaskManager that doesn't contain any tasks, without reading anything from disk
+    #[cfg(test)]
+    pub fn without_tasks() -> Self {
+        Self {
+            template_tasks: TemplateTasks::default(),
+            event_tasks: EventTasks::default(),
+            menu_open: None,
+        }
+    }
+
     /// Returns a TaskManager containing all tasks stored on disk
     pub fn load_all() -> Self {
         let template_tasks = TemplateTasks::load_templates();
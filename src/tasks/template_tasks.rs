@@ -1,11 +1,11 @@
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, Days, NaiveDate, NaiveTime, Weekday};
 use iced::{Element, widget::column};
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fs};
 
 use super::{
-    MultiBinaryMessage, MultiBinaryTaskElement, StandardMessage, StandardTask, TaskId, TaskType,
-    task_data::MultiBinaryTask,
+    AgendaItem, CounterData, CounterMessage, MultiBinaryMessage, MultiBinaryTaskElement,
+    StandardMessage, StandardTask, TaskId, TaskType, task_data::MultiBinaryTask,
 };
 use crate::{
     config::preferences,
@@ -23,9 +23,16 @@ pub struct StandardTaskTemplate {
 }
 
 impl StandardTaskTemplate {
-    /// Adds an empty element with the given date to the task elements if it does not exist
-    pub fn add_empty_element(&mut self, active_date: NaiveDate) {
-        self.elements.entry(active_date).or_default();
+    /// Adds an empty element with the given date to the task elements if it does not exist. Returns true if the
+    /// element was added
+    pub fn add_empty_element(&mut self, active_date: NaiveDate) -> bool {
+        if self.elements.contains_key(&active_date) {
+            return false;
+        }
+
+        self.elements.insert(active_date, StandardTask::default());
+
+        true
     }
 
     /// Returns mutable access to a element at the given date, if it exists
@@ -50,11 +57,18 @@ impl MultiBinaryTaskTemplate {
         }
     }
 
-    /// Adds an empty element with the given date to the task elements if it does not exist
-    pub fn add_empty_element(&mut self, active_date: NaiveDate) {
+    /// Adds an empty element with the given date to the task elements if it does not exist. Returns true if the
+    /// element was added
+    pub fn add_empty_element(&mut self, active_date: NaiveDate) -> bool {
+        if self.elements.contains_key(&active_date) {
+            return false;
+        }
+
         let empty_element = MultiBinaryTaskElement::with_empty_subtasks(self.subtasks.len());
 
-        self.elements.entry(active_date).or_insert(empty_element);
+        self.elements.insert(active_date, empty_element);
+
+        true
     }
 
     /// Returns mutable access to the task if it exists
@@ -66,6 +80,44 @@ impl MultiBinaryTaskTemplate {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+/// Contains the target and individual day counts in a Counter task
+pub struct CounterTaskTemplate {
+    target: Option<u32>,
+    elements: BTreeMap<NaiveDate, CounterData>,
+}
+
+impl CounterTaskTemplate {
+    /// Creates a new CounterTaskTemplate that is completed each day the count reaches the target
+    pub fn new(target: Option<u32>) -> Self {
+        Self {
+            target,
+            elements: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an empty element with the given date to the task elements if it does not exist. Returns true if the
+    /// element was added
+    pub fn add_empty_element(&mut self, active_date: NaiveDate) -> bool {
+        if self.elements.contains_key(&active_date) {
+            return false;
+        }
+
+        self.elements
+            .insert(active_date, CounterData::with_target(self.target));
+
+        true
+    }
+
+    /// Returns the sum of the counts of every element from the first date up to and including the last date
+    pub fn total_between(&self, first_date: NaiveDate, last_date: NaiveDate) -> u32 {
+        self.elements
+            .range(first_date..=last_date)
+            .map(|(_date, counter)| counter.count())
+            .sum()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// the Frequency represents the schedule of how often the templates trigger
 pub enum Frequency {
@@ -73,6 +125,9 @@ pub enum Frequency {
     Weekly([bool; 7]),
     Monthly([bool; 31]),
     Dated(MonthDay),
+
+    /// like Weekly, but only every other week, starting with the week the task was created in
+    Biweekly([bool; 7]),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -82,11 +137,13 @@ pub enum FrequencyType {
     Weekly,
     Monthly,
     Dated,
+    Biweekly,
 }
 
 impl Frequency {
-    /// returns if the frequency would be scheduled to be active on the given date
-    pub fn is_active(&self, active_date: NaiveDate) -> bool {
+    /// returns if the frequency would be scheduled to be active on the given date. the creation date of the task is
+    /// what every other week is counted from for Biweekly frequencies
+    pub fn is_active(&self, active_date: NaiveDate, creation_date: NaiveDate) -> bool {
         match self {
             Frequency::Daily => {
                 return true;
@@ -112,11 +169,32 @@ impl Frequency {
                     return true;
                 }
             }
+            Frequency::Biweekly(daymap) => {
+                let current_day = active_date.weekday();
+
+                if Self::weekly_is(current_day, daymap, Weekday::Sun)
+                    && Self::weeks_between(creation_date, active_date) % 2 == 0
+                {
+                    return true;
+                }
+            }
         }
 
         false
     }
 
+    /// the number of whole weeks between the weeks of the two dates, where weeks start on Sunday. the dates are compared
+    /// directly as days rather than by week numbers, so the count doesn't shift at the end of a year or in leap years
+    fn weeks_between(first_date: NaiveDate, second_date: NaiveDate) -> i64 {
+        let week_start =
+            |date: NaiveDate| date - Days::new(date.weekday().num_days_from_sunday() as u64);
+
+        (week_start(second_date) - week_start(first_date))
+            .num_days()
+            .abs()
+            / 7
+    }
+
     /// checks if the given weekday would be active based on the daymap and the weekday defined as daymap[0]
     fn weekly_is(current_day: Weekday, daymap: &[bool; 7], week_start_day: Weekday) -> bool {
         let mut day_index = 0;
@@ -139,12 +217,15 @@ pub enum CommonMessage {
     ExpandOptions,
     EndTask,
     DeleteTemplate,
+    MoveUp,
+    MoveDown,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum TemplateData {
     Standard(StandardTaskTemplate),
     MultiBinary(MultiBinaryTaskTemplate),
+    Counter(CounterTaskTemplate),
 }
 
 impl TemplateData {
@@ -153,6 +234,7 @@ impl TemplateData {
         match self {
             TemplateData::Standard(_) => TaskType::Standard,
             TemplateData::MultiBinary(_) => TaskType::MultiBinary,
+            TemplateData::Counter(_) => TaskType::Counter,
         }
     }
 }
@@ -164,9 +246,27 @@ pub struct TemplateTask {
     creation_date: NaiveDate,
     ended_date: Option<NaiveDate>,
     frequency: Frequency,
-    #[serde(skip)]
+
+    /// Whether the task is expanded in the task list. This is only UI state, so toggling it doesn't mark the template
+    /// dirty. It is saved along with the next real change to the templates, and otherwise the expansion from the last
+    /// save is restored on load
+    #[serde(default)]
     expanded: bool,
+
+    /// True once the task was expanded or collapsed by hand this session, after which the expansion is no longer
+    /// decided by the auto expand preference
+    #[serde(skip)]
+    expansion_overridden: bool,
     template_data: TemplateData,
+
+    /// Where the task is placed in the task list relative to the other templates. Templates with the same order, like
+    /// those saved before tasks could be reordered, fall back to the order they were created in
+    #[serde(default)]
+    order: u32,
+
+    /// True if the template changed since it was last saved or loaded
+    #[serde(skip)]
+    dirty: bool,
 }
 
 impl TemplateTask {
@@ -183,27 +283,146 @@ impl TemplateTask {
             ended_date: None,
             frequency,
             expanded: false,
+            expansion_overridden: false,
             template_data,
+            order: 0,
+            dirty: false,
         }
     }
 
+    /// Marks the template as changed, so it is written on the next save. Anything that changes the template through
+    /// get_template_mut must call this
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
     /// Returns mutable access to the template data
     pub fn get_template_mut(&mut self) -> &mut TemplateData {
         &mut self.template_data
     }
 
-    /// If the TemplateTask does not have an entry for the given day and it should based on its Frequency, a blank
-    /// element is inserted into the elements
-    pub fn generate_template_entry(&mut self, active_date: NaiveDate) {
-        if self.frequency.is_active(active_date) {
-            match &mut self.template_data {
-                TemplateData::Standard(standard_task) => {
-                    standard_task.add_empty_element(active_date);
-                }
-                TemplateData::MultiBinary(multi_binary_task) => {
-                    multi_binary_task.add_empty_element(active_date);
-                }
+    /// Ends the task on the given date. The task is no longer scheduled after the date, but its existing entries are
+    /// kept, so they stay visible on their dates and still count towards the history of the task
+    pub fn end_task(&mut self, date: NaiveDate) {
+        self.ended_date = Some(date);
+        self.mark_dirty();
+    }
+
+    /// Reactivates an ended task, so it is scheduled going forward again
+    pub fn resume_task(&mut self) {
+        self.ended_date = None;
+        self.mark_dirty();
+    }
+
+    /// Returns true if the task has been ended
+    pub fn is_ended(&self) -> bool {
+        self.ended_date.is_some()
+    }
+
+    /// Returns true if the task is scheduled on the given date. The task has to be created on or before the date, its
+    /// Frequency has to land on the date, and if the task was ended, the date can't be after the day it ended
+    pub fn is_active(&self, date: NaiveDate) -> bool {
+        let after_end_date = self.ended_date.is_some_and(|ended_date| date > ended_date);
+
+        self.frequency.is_active(date, self.creation_date)
+            && date >= self.creation_date
+            && !after_end_date
+    }
+
+    /// If the TemplateTask does not have an entry for the given day and it should be active on the day, a blank element
+    /// is inserted into the elements. Returns true if an element was inserted
+    pub fn generate_template_entry(&mut self, active_date: NaiveDate) -> bool {
+        if !self.is_active(active_date) {
+            return false;
+        }
+
+        match &mut self.template_data {
+            TemplateData::Standard(standard_task) => standard_task.add_empty_element(active_date),
+            TemplateData::MultiBinary(multi_binary_task) => {
+                multi_binary_task.add_empty_element(active_date)
             }
+            TemplateData::Counter(counter_task) => counter_task.add_empty_element(active_date),
+        }
+    }
+
+    /// Returns if the element at the given date is completed, or None if there is no element on that date
+    fn completed_on(&self, date: NaiveDate) -> Option<bool> {
+        match &self.template_data {
+            TemplateData::Standard(standard_task) => standard_task
+                .elements
+                .get(&date)
+                .map(|task_element| task_element.is_completed()),
+            TemplateData::MultiBinary(multi_binary_task) => multi_binary_task
+                .elements
+                .get(&date)
+                .map(|task_element| task_element.is_completed()),
+            TemplateData::Counter(counter_task) => counter_task
+                .elements
+                .get(&date)
+                .map(|task_element| task_element.is_completed()),
+        }
+    }
+
+    /// Returns the time of day the element at the given date is due, if it has one
+    fn due_time_on(&self, date: NaiveDate) -> Option<NaiveTime> {
+        match &self.template_data {
+            TemplateData::Standard(standard_task) => standard_task
+                .elements
+                .get(&date)
+                .and_then(|task_element| task_element.due_time()),
+            TemplateData::MultiBinary(multi_binary_task) => multi_binary_task
+                .elements
+                .get(&date)
+                .and_then(|task_element| task_element.due_time()),
+            TemplateData::Counter(_counter_task) => None,
+        }
+    }
+
+    /// Returns true if the element at the given date has any text in it
+    fn has_text_on(&self, date: NaiveDate) -> bool {
+        match &self.template_data {
+            TemplateData::Standard(standard_task) => standard_task
+                .elements
+                .get(&date)
+                .is_some_and(|task_element| task_element.has_text()),
+            TemplateData::MultiBinary(multi_binary_task) => multi_binary_task
+                .elements
+                .get(&date)
+                .is_some_and(|task_element| task_element.has_text()),
+            TemplateData::Counter(_counter_task) => false,
+        }
+    }
+
+    /// Returns true if the task should be shown expanded on the given date. Unless the expansion was toggled by hand
+    /// this session, tasks with text on the date are expanded when the auto expand preference is enabled
+    fn is_expanded_on(&self, date: NaiveDate) -> bool {
+        if self.expansion_overridden || !preferences().general.auto_expand_tasks {
+            return self.expanded;
+        }
+
+        self.expanded || self.has_text_on(date)
+    }
+
+    /// Returns the dates of all the elements of the template
+    fn element_dates(&self) -> Vec<NaiveDate> {
+        match &self.template_data {
+            TemplateData::Standard(standard_task) => {
+                standard_task.elements.keys().copied().collect()
+            }
+            TemplateData::MultiBinary(multi_binary_task) => {
+                multi_binary_task.elements.keys().copied().collect()
+            }
+            TemplateData::Counter(counter_task) => counter_task.elements.keys().copied().collect(),
+        }
+    }
+
+    /// Returns the message toggling the checkbox of the counter sends. A completed counter is being unchecked, so it
+    /// counts one less, and any other counter counts one more
+    fn counter_toggle_message(counter_data: &CounterData) -> CounterMessage {
+        if counter_data.is_completed() {
+            CounterMessage::Decrement
+        } else {
+            CounterMessage::Increment
         }
     }
 
@@ -212,6 +431,7 @@ impl TemplateTask {
         &'a self,
         active_date: NaiveDate,
         options_expanded: bool,
+        focused: bool,
     ) -> Element<'a, TemplateMessage> {
         let checkbox = match &self.template_data {
             TemplateData::Standard(standard_task_template) => standard_task_template
@@ -232,6 +452,16 @@ impl TemplateTask {
                         TemplateMessage::MultiBinary(MultiBinaryMessage::ToggledOverride),
                     )
                 }),
+            // checking off a counter counts one more, and unchecking it takes one back off
+            TemplateData::Counter(counter_task_template) => counter_task_template
+                .elements
+                .get(&active_date)
+                .map(|counter_task| {
+                    (
+                        counter_task.is_completed(),
+                        TemplateMessage::Counter(Self::counter_toggle_message(counter_task)),
+                    )
+                }),
         };
 
         let expanded_ui = match &self.template_data {
@@ -250,9 +480,25 @@ impl TemplateTask {
                     column![].into()
                 }
             }
+            TemplateData::Counter(counter_task) => {
+                if let Some(task_element) = counter_task.elements.get(&active_date) {
+                    task_element.expanded_ui().map(TemplateMessage::Counter)
+                } else {
+                    column![].into()
+                }
+            }
         };
 
-        let expanded = if self.expanded {
+        // the count is shown next to the name, so it can be seen without expanding the task
+        let task_name = match &self.template_data {
+            TemplateData::Counter(counter_task) => match counter_task.elements.get(&active_date) {
+                Some(task_element) => format!("{} ({})", self.name, task_element.count_text()),
+                None => self.name.clone(),
+            },
+            _ => self.name.clone(),
+        };
+
+        let expanded = if self.is_expanded_on(active_date) {
             Some((
                 Some(expanded_ui),
                 TemplateMessage::Common(CommonMessage::ExpandToggled),
@@ -261,13 +507,24 @@ impl TemplateTask {
             Some((None, TemplateMessage::Common(CommonMessage::ExpandToggled)))
         };
 
-        let end_task_text = if self.ended_date.is_none() {
-            "End Task"
-        } else {
+        let end_task_text = if self.is_ended() {
             "Resume Task"
+        } else {
+            "End Task"
         };
 
+        // tasks with a due time are placed by it, so only the tasks without one can be moved by hand
+        let movable = self.due_time_on(active_date).is_none();
+
         let task_menu_items = vec![
+            ContextMenuItem::Button(ContextMenuElement::new(
+                "Move Up",
+                movable.then_some(TemplateMessage::Common(CommonMessage::MoveUp)),
+            )),
+            ContextMenuItem::Button(ContextMenuElement::new(
+                "Move Down",
+                movable.then_some(TemplateMessage::Common(CommonMessage::MoveDown)),
+            )),
             ContextMenuItem::Button(ContextMenuElement::new(
                 end_task_text,
                 Some(TemplateMessage::Common(CommonMessage::EndTask)),
@@ -288,10 +545,11 @@ impl TemplateTask {
 
         custom_widgets::task::build_task(
             checkbox,
-            self.name.clone(),
+            task_name,
             expanded,
             TemplateMessage::Common(CommonMessage::ExpandOptions),
             options_menu,
+            focused,
         )
     }
 }
@@ -302,6 +560,7 @@ pub enum TemplateMessage {
     Common(CommonMessage),
     Standard(StandardMessage),
     MultiBinary(MultiBinaryMessage),
+    Counter(CounterMessage),
 }
 
 #[derive(Debug, Clone)]
@@ -315,11 +574,21 @@ pub struct TemplateTaskMessage {
 /// Collection of all the loaded templates
 pub struct TemplateTasks {
     tasks: BTreeMap<TaskId, TemplateTask>,
+
+    /// True if a template was deleted since the templates were last saved or loaded. Changes to the remaining
+    /// templates are tracked by the templates themselves
+    #[serde(skip)]
+    templates_removed: bool,
 }
 
 impl<'a> TemplateTasks {
-    /// Writes all template tasks to disk, into the template_tasks directory defined in the preferences
-    pub fn save_templates(&self) {
+    /// Writes all template tasks to disk, into the template_tasks directory defined in the preferences. Every template
+    /// shares the same file, so nothing is written unless at least one template changed since the last save
+    pub fn save_templates(&mut self) {
+        if !self.is_dirty() {
+            return;
+        }
+
         let mut template_path = preferences().paths.template_tasks_dir();
         template_path.push("templates.json");
 
@@ -327,6 +596,17 @@ impl<'a> TemplateTasks {
             serde_json::to_string_pretty(self).expect("couldn't serialize disk templates");
 
         fs::write(template_path, template_json).expect("couldn't save template json");
+
+        for template in self.tasks.values_mut() {
+            template.dirty = false;
+        }
+
+        self.templates_removed = false;
+    }
+
+    /// Returns true if any template was changed, created, or deleted since the templates were last saved
+    pub fn is_dirty(&self) -> bool {
+        self.templates_removed || self.tasks.values().any(|template| template.dirty)
     }
 
     /// Loads all template tasks from disk, from the template_tasks directory defined in the preferences. If the
@@ -355,6 +635,15 @@ impl<'a> TemplateTasks {
         let task_date = template.creation_date;
 
         template.generate_template_entry(task_date);
+        template.mark_dirty();
+
+        // new tasks are placed at the end of the task list
+        template.order = self
+            .tasks
+            .values()
+            .map(|task| task.order + 1)
+            .max()
+            .unwrap_or(0);
 
         self.tasks.insert(task_id, template);
     }
@@ -364,8 +653,10 @@ impl<'a> TemplateTasks {
         let active_templates = self.get_active_template_ids(active_date);
 
         for task_id in active_templates {
-            if let Some(task) = self.tasks.get_mut(&task_id) {
-                task.generate_template_entry(active_date);
+            if let Some(task) = self.tasks.get_mut(&task_id)
+                && task.generate_template_entry(active_date)
+            {
+                task.mark_dirty();
             }
         }
     }
@@ -380,6 +671,31 @@ impl<'a> TemplateTasks {
         self.tasks.get_mut(&task_id)
     }
 
+    /// Removes the template with the given name and task type, along with all of its entries. Every template shares
+    /// the same file, so the template is removed from the disk by the next save. Returns true if a template was removed
+    pub fn remove_template(&mut self, task_name: &str, task_type: TaskType) -> bool {
+        let template_count = self.tasks.len();
+
+        self.tasks.retain(|_task_id, task| {
+            task.name != task_name || task.template_data.task_type() != task_type
+        });
+
+        let removed = self.tasks.len() != template_count;
+
+        if removed {
+            self.templates_removed = true;
+        }
+
+        removed
+    }
+
+    /// Returns the name and task type of the template at the given TaskId, if it exists
+    pub fn template_identity(&self, task_id: TaskId) -> Option<(String, TaskType)> {
+        self.tasks
+            .get(&task_id)
+            .map(|task| (task.name.clone(), task.template_data.task_type()))
+    }
+
     /// Returns true if given name and task type are present in the same TemplateTask
     pub fn task_exists(&self, task_name: &str, task_type: TaskType) -> bool {
         for task in self.tasks.values() {
@@ -391,26 +707,96 @@ impl<'a> TemplateTasks {
         false
     }
 
+    /// Returns the ids of every template in the order they are shown in the task list
+    fn ordered_template_ids(&self) -> Vec<TaskId> {
+        let mut task_ids = self.tasks.keys().copied().collect::<Vec<TaskId>>();
+
+        task_ids.sort_by_key(|task_id| (self.tasks[task_id].order, *task_id));
+
+        task_ids
+    }
+
+    /// Moves the template one place up or down in the task list, past the neighboring template that is shown on the
+    /// given date. Templates with a due time on the date are sorted by it in the task list, so only the ones without a
+    /// due time are moved or moved past. Every template is then renumbered in sequence, so no two templates ever share
+    /// an order
+    pub fn move_template(&mut self, active_date: NaiveDate, task_id: TaskId, move_up: bool) {
+        let active_ids = self
+            .get_active_template_ids(active_date)
+            .into_iter()
+            .filter(|id| self.tasks[id].due_time_on(active_date).is_none())
+            .collect::<Vec<TaskId>>();
+
+        let Some(active_index) = active_ids.iter().position(|id| *id == task_id) else {
+            return;
+        };
+
+        let neighbor_index = if move_up {
+            active_index.checked_sub(1)
+        } else {
+            Some(active_index + 1)
+        };
+
+        let Some(&neighbor_id) = neighbor_index.and_then(|index| active_ids.get(index)) else {
+            return;
+        };
+
+        let mut ordered_ids = self.ordered_template_ids();
+        ordered_ids.retain(|id| *id != task_id);
+
+        let Some(neighbor_position) = ordered_ids.iter().position(|id| *id == neighbor_id) else {
+            return;
+        };
+
+        let insert_position = if move_up {
+            neighbor_position
+        } else {
+            neighbor_position + 1
+        };
+
+        ordered_ids.insert(insert_position, task_id);
+
+        for (order, id) in ordered_ids.iter().enumerate() {
+            if let Some(template) = self.tasks.get_mut(id)
+                && template.order != order as u32
+            {
+                template.order = order as u32;
+                template.mark_dirty();
+            }
+        }
+    }
+
     /// Performs the action specified by the message on the TemplateTask on the given date
     pub fn update(&mut self, active_date: NaiveDate, message: TemplateTaskMessage) {
         match message.message {
+            TemplateMessage::Common(CommonMessage::MoveUp) => {
+                self.move_template(active_date, message.task_id, true);
+            }
+            TemplateMessage::Common(CommonMessage::MoveDown) => {
+                self.move_template(active_date, message.task_id, false);
+            }
             TemplateMessage::Common(common_message) => {
                 if let Some(template) = self.tasks.get_mut(&message.task_id) {
                     match common_message {
+                        // expansion is saved lazily, see TemplateTask::expanded
                         CommonMessage::ExpandToggled => {
-                            template.expanded = !template.expanded;
+                            template.expanded = !template.is_expanded_on(active_date);
+                            template.expansion_overridden = true;
                         }
                         CommonMessage::ExpandOptions => {}
                         CommonMessage::EndTask => {
-                            if template.ended_date.is_none() {
-                                template.ended_date = Some(active_date);
+                            if template.is_ended() {
+                                template.resume_task();
                             } else {
-                                template.ended_date = None;
+                                template.end_task(active_date);
                             }
                         }
                         CommonMessage::DeleteTemplate => {
                             self.tasks.remove(&message.task_id);
+
+                            self.templates_removed = true;
                         }
+                        CommonMessage::MoveUp | CommonMessage::MoveDown => {}
                     }
                 }
             }
@@ -420,6 +806,8 @@ impl<'a> TemplateTasks {
                     && let Some(task_element) = standard_task.elements.get_mut(&active_date)
                 {
                     task_element.update(standard_message);
+
+                    task.dirty = true;
                 }
             }
             TemplateMessage::MultiBinary(multi_binary_message) => {
@@ -428,38 +816,157 @@ impl<'a> TemplateTasks {
                     && let Some(task_element) = multi_binary_task.elements.get_mut(&active_date)
                 {
                     task_element.update(multi_binary_message);
+
+                    task.dirty = true;
+                }
+            }
+            TemplateMessage::Counter(counter_message) => {
+                if let Some(task) = self.tasks.get_mut(&message.task_id)
+                    && let TemplateData::Counter(counter_task) = &mut task.template_data
+                    && let Some(task_element) = counter_task.elements.get_mut(&active_date)
+                {
+                    task_element.update(counter_message);
+
+                    task.dirty = true;
                 }
             }
         }
     }
 
-    /// Returns a list of all the templates that are active on a given date
-    pub fn get_active_template_ids(&self, active_date: NaiveDate) -> Vec<TaskId> {
+    /// Toggles the completion of the template's entry on the given date, the same as clicking its checkbox
+    pub fn toggle_completion(&mut self, active_date: NaiveDate, task_id: TaskId) {
+        let message = match self.tasks.get(&task_id).map(|task| &task.template_data) {
+            Some(TemplateData::Standard(_standard_task)) => {
+                TemplateMessage::Standard(StandardMessage::ToggledCheckbox)
+            }
+            Some(TemplateData::MultiBinary(_multi_binary_task)) => {
+                TemplateMessage::MultiBinary(MultiBinaryMessage::ToggledOverride)
+            }
+            Some(TemplateData::Counter(counter_task)) => {
+                match counter_task.elements.get(&active_date) {
+                    Some(counter_data) => {
+                        TemplateMessage::Counter(TemplateTask::counter_toggle_message(counter_data))
+                    }
+                    None => return,
+                }
+            }
+            None => return,
+        };
+
+        self.update(active_date, TemplateTaskMessage { message, task_id });
+    }
+
+    /// Returns the time of day the template at the given TaskId is due on the given date, if it has one
+    pub fn due_time(&self, task_id: TaskId, active_date: NaiveDate) -> Option<NaiveTime> {
         self.tasks
-            .iter()
-            .filter(|(_id, data)| {
-                let after_end_date = data
-                    .ended_date
-                    .is_some_and(|ended_date| active_date > ended_date);
-
-                data.frequency.is_active(active_date)
-                    && active_date >= data.creation_date
-                    && !after_end_date
+            .get(&task_id)
+            .and_then(|template| template.due_time_on(active_date))
+    }
+
+    /// Returns a list of all the templates that are active on a given date, along with any that have an entry on it, in
+    /// the order they are shown in the task list
+    pub fn get_active_template_ids(&self, active_date: NaiveDate) -> Vec<TaskId> {
+        self.ordered_template_ids()
+            .into_iter()
+            // entries that already exist are always shown, so the history of an ended task stays viewable
+            .filter(|id| {
+                let data = &self.tasks[id];
+
+                data.is_active(active_date) || data.completed_on(active_date).is_some()
             })
-            .map(|(id, _data)| *id)
             .collect::<Vec<TaskId>>()
     }
 
+    /// Returns the agenda items of all the templates scheduled on the given date
+    pub fn scheduled_agenda_items(&self, active_date: NaiveDate) -> Vec<AgendaItem> {
+        self.get_active_template_ids(active_date)
+            .iter()
+            .filter_map(|task_id| self.tasks.get(task_id))
+            .map(|template| {
+                let completed = template.completed_on(active_date).unwrap_or(false);
+
+                AgendaItem::new(template.name.clone(), active_date, completed)
+            })
+            .collect()
+    }
+
+    /// Returns the agenda items of all the template elements in the lookback_days before the given date that were
+    /// never completed
+    pub fn overdue_agenda_items(
+        &self,
+        active_date: NaiveDate,
+        lookback_days: u64,
+    ) -> Vec<AgendaItem> {
+        let lookback_start = active_date
+            .checked_sub_days(Days::new(lookback_days))
+            .unwrap_or(NaiveDate::MIN);
+
+        let mut overdue_items = Vec::new();
+
+        for template in self.tasks.values() {
+            for element_date in template.element_dates() {
+                if element_date < lookback_start || element_date >= active_date {
+                    continue;
+                }
+
+                if template.completed_on(element_date) == Some(false) {
+                    overdue_items.push(AgendaItem::new(template.name.clone(), element_date, false));
+                }
+            }
+        }
+
+        overdue_items
+    }
+
+    /// Returns the name of every template along with the first date it was scheduled on, in the order they are shown in
+    /// the task list. A template without any entries yet falls back to the date it was created
+    pub fn template_first_dates(&self) -> Vec<(String, NaiveDate)> {
+        self.ordered_template_ids()
+            .iter()
+            .map(|task_id| {
+                let template = &self.tasks[task_id];
+
+                let first_date = template
+                    .element_dates()
+                    .into_iter()
+                    .min()
+                    .unwrap_or(template.creation_date);
+
+                (template.name.clone(), first_date)
+            })
+            .collect()
+    }
+
+    /// Returns the name and total count of every counter task from the first date up to and including the last date,
+    /// in the order the tasks were created
+    pub fn counter_totals(
+        &self,
+        first_date: NaiveDate,
+        last_date: NaiveDate,
+    ) -> Vec<(String, u32)> {
+        self.tasks
+            .values()
+            .filter_map(|template| match &template.template_data {
+                TemplateData::Counter(counter_task) => Some((
+                    template.name.clone(),
+                    counter_task.total_between(first_date, last_date),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
     /// Full TemplateTask graphical element
     pub fn build_template(
         &'a self,
         task_id: TaskId,
         active_date: NaiveDate,
         options_expanded: bool,
+        focused: bool,
     ) -> Element<'a, TemplateTaskMessage> {
         if let Some(task_data) = self.get_task(task_id) {
             task_data
-                .built_template(active_date, options_expanded)
+                .built_template(active_date, options_expanded, focused)
                 .map(move |template_message| TemplateTaskMessage {
                     message: template_message,
                     task_id,
@@ -469,3 +976,248 @@ impl<'a> TemplateTasks {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{journal_pointer, with_test_preferences};
+    use std::fs::File;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    fn daily_task(name: &str, creation_date: NaiveDate) -> TemplateTask {
+        TemplateTask::new(
+            name.to_string(),
+            creation_date,
+            Frequency::Daily,
+            TemplateData::Standard(StandardTaskTemplate::default()),
+        )
+    }
+
+    /// a daymap, starting on Sunday, with only the given weekday active
+    fn single_day_map(weekday: Weekday) -> [bool; 7] {
+        let mut daymap = [false; 7];
+        daymap[weekday.num_days_from_sunday() as usize] = true;
+
+        daymap
+    }
+
+    #[test]
+    fn weeks_between_counts_sunday_starting_weeks_across_years() {
+        // 2024-12-29 is a Sunday, so the last days of 2024 share a week with the first days of 2025
+        assert_eq!(
+            Frequency::weeks_between(date(2024, 12, 31), date(2025, 1, 4)),
+            0
+        );
+        assert_eq!(
+            Frequency::weeks_between(date(2024, 12, 28), date(2024, 12, 29)),
+            1
+        );
+        assert_eq!(
+            Frequency::weeks_between(date(2024, 12, 25), date(2025, 1, 1)),
+            1
+        );
+        assert_eq!(
+            Frequency::weeks_between(date(2025, 1, 1), date(2024, 12, 25)),
+            1
+        );
+    }
+
+    #[test]
+    fn weeks_between_handles_leap_days() {
+        assert_eq!(
+            Frequency::weeks_between(date(2024, 2, 25), date(2024, 3, 2)),
+            0
+        );
+        assert_eq!(
+            Frequency::weeks_between(date(2024, 2, 22), date(2024, 2, 29)),
+            1
+        );
+        assert_eq!(
+            Frequency::weeks_between(date(2024, 2, 29), date(2024, 3, 7)),
+            1
+        );
+        assert_eq!(
+            Frequency::weeks_between(date(2024, 2, 15), date(2024, 3, 14)),
+            4
+        );
+    }
+
+    #[test]
+    fn biweekly_alternates_across_the_new_year() {
+        let frequency = Frequency::Biweekly(single_day_map(Weekday::Wed));
+        let creation_date = date(2024, 12, 18);
+
+        let active_wednesdays = [
+            date(2024, 12, 18),
+            date(2024, 12, 25),
+            date(2025, 1, 1),
+            date(2025, 1, 8),
+            date(2025, 1, 15),
+        ]
+        .map(|wednesday| frequency.is_active(wednesday, creation_date));
+
+        assert_eq!(active_wednesdays, [true, false, true, false, true]);
+        assert!(!frequency.is_active(date(2025, 1, 2), creation_date));
+    }
+
+    #[test]
+    fn biweekly_alternates_across_a_leap_day() {
+        let frequency = Frequency::Biweekly(single_day_map(Weekday::Thu));
+        let creation_date = date(2024, 2, 15);
+
+        let active_thursdays = [
+            date(2024, 2, 15),
+            date(2024, 2, 22),
+            date(2024, 2, 29),
+            date(2024, 3, 7),
+            date(2024, 3, 14),
+        ]
+        .map(|thursday| frequency.is_active(thursday, creation_date));
+
+        assert_eq!(active_thursdays, [true, false, true, false, true]);
+    }
+
+    fn templates_file() -> PathBuf {
+        preferences()
+            .paths
+            .template_tasks_dir()
+            .join("templates.json")
+    }
+
+    /// moves the modification time of the templates file far into the past, so any rewrite is guaranteed to change it
+    fn age_templates_file() -> SystemTime {
+        let old_time = SystemTime::UNIX_EPOCH + Duration::from_secs(86_400);
+
+        File::options()
+            .write(true)
+            .open(templates_file())
+            .expect("couldn't open the templates file")
+            .set_modified(old_time)
+            .expect("couldn't set the templates file mtime");
+
+        old_time
+    }
+
+    fn templates_file_mtime() -> SystemTime {
+        fs::metadata(templates_file())
+            .expect("couldn't read the templates file metadata")
+            .modified()
+            .expect("no mtime on the templates file")
+    }
+
+    #[test]
+    fn unchanged_templates_are_not_rewritten() {
+        with_test_preferences(
+            |preferences| {
+                preferences.paths.journal_path =
+                    journal_pointer::test_dir().join("unchanged-templates");
+            },
+            || {
+                fs::create_dir_all(preferences().paths.template_tasks_dir())
+                    .expect("couldn't create the template tasks dir");
+
+                let mut template_tasks = TemplateTasks::default();
+                template_tasks.create_task(daily_task("Stretch", date(2024, 5, 1)));
+                template_tasks.generate_template_entries(date(2024, 5, 1));
+                template_tasks.save_templates();
+                assert!(!template_tasks.is_dirty());
+
+                let old_time = age_templates_file();
+                template_tasks.save_templates();
+                assert_eq!(templates_file_mtime(), old_time);
+
+                let task_id = template_tasks.get_active_template_ids(date(2024, 5, 1))[0];
+                template_tasks.toggle_completion(date(2024, 5, 1), task_id);
+                assert!(template_tasks.is_dirty());
+
+                template_tasks.save_templates();
+                assert_ne!(templates_file_mtime(), old_time);
+            },
+        );
+    }
+
+    #[test]
+    fn ended_task_is_active_through_its_end_date() {
+        let mut task = daily_task("Stretch", date(2024, 5, 1));
+        task.end_task(date(2024, 5, 10));
+
+        assert!(task.is_ended());
+        assert!(task.is_active(date(2024, 5, 9)));
+        assert!(task.is_active(date(2024, 5, 10)));
+        assert!(!task.is_active(date(2024, 5, 11)));
+        assert!(!task.is_active(date(2024, 6, 1)));
+    }
+
+    #[test]
+    fn resumed_task_is_active_after_its_old_end_date() {
+        let mut task = daily_task("Stretch", date(2024, 5, 1));
+        task.end_task(date(2024, 5, 10));
+        task.resume_task();
+
+        assert!(!task.is_ended());
+        assert!(task.is_active(date(2024, 5, 11)));
+        assert!(task.is_active(date(2024, 6, 1)));
+    }
+
+    #[test]
+    fn ended_task_stops_generating_entries() {
+        let mut template_tasks = TemplateTasks::default();
+        template_tasks.create_task(daily_task("Stretch", date(2024, 5, 1)));
+
+        let task_id = template_tasks.get_active_template_ids(date(2024, 5, 1))[0];
+        template_tasks
+            .get_task_mut(task_id)
+            .expect("task was just created")
+            .end_task(date(2024, 5, 10));
+
+        template_tasks.generate_template_entries(date(2024, 5, 10));
+        template_tasks.generate_template_entries(date(2024, 5, 11));
+
+        assert_eq!(
+            template_tasks
+                .scheduled_agenda_items(date(2024, 5, 10))
+                .len(),
+            1
+        );
+        assert!(
+            template_tasks
+                .scheduled_agenda_items(date(2024, 5, 11))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn unchecking_a_counter_counts_one_less() {
+        let mut template_tasks = TemplateTasks::default();
+        template_tasks.create_task(TemplateTask::new(
+            "Water".to_string(),
+            date(2024, 5, 1),
+            Frequency::Daily,
+            TemplateData::Counter(CounterTaskTemplate::new(None)),
+        ));
+        template_tasks.generate_template_entries(date(2024, 5, 1));
+
+        let task_id = template_tasks.get_active_template_ids(date(2024, 5, 1))[0];
+        let counter_total = |template_tasks: &TemplateTasks| {
+            template_tasks.counter_totals(date(2024, 5, 1), date(2024, 5, 1))
+        };
+
+        template_tasks.toggle_completion(date(2024, 5, 1), task_id);
+        assert_eq!(
+            counter_total(&template_tasks),
+            vec![("Water".to_string(), 1)]
+        );
+
+        template_tasks.toggle_completion(date(2024, 5, 1), task_id);
+        assert_eq!(
+            counter_total(&template_tasks),
+            vec![("Water".to_string(), 0)]
+        );
+        assert!(!template_tasks.scheduled_agenda_items(date(2024, 5, 1))[0].completed());
+    }
+}
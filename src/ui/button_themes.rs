@@ -1,4 +1,4 @@
-use super::journal_theme::LIGHT;
+use super::journal_theme::journal_theme;
 
 use iced::{
     Background, Border, Color, Shadow, Theme, Vector,
@@ -7,10 +7,12 @@ use iced::{
 
 /// the default button styling, using the default background color with no border or shadow. darkens the button when
 /// hovered or pressed
-pub fn standard_button_style(_theme: &Theme, status: Status) -> button::Style {
+pub fn standard_button_style(theme: &Theme, status: Status) -> button::Style {
+    let journal_theme = journal_theme(theme);
+
     let base_style = button::Style {
-        background: Some(Background::Color(LIGHT.default_background)),
-        text_color: LIGHT.default_text,
+        background: Some(Background::Color(journal_theme.default_background)),
+        text_color: journal_theme.default_text,
         border: Border {
             color: Color::TRANSPARENT,
             width: 0.0,
@@ -25,7 +27,7 @@ pub fn standard_button_style(_theme: &Theme, status: Status) -> button::Style {
     };
 
     let mut darkened_style = base_style;
-    let darkened_background = LIGHT.darken(LIGHT.default_background);
+    let darkened_background = journal_theme.darken(journal_theme.default_background);
     darkened_style.background = Some(Background::Color(darkened_background));
 
     match status {
@@ -35,15 +37,17 @@ pub fn standard_button_style(_theme: &Theme, status: Status) -> button::Style {
 }
 
 /// Styling of the context menu
-pub fn context_menu_style(_theme: &Theme, status: Status) -> button::Style {
+pub fn context_menu_style(theme: &Theme, status: Status) -> button::Style {
+    let journal_theme = journal_theme(theme);
+
     let text_color = if matches!(status, Status::Disabled) {
-        LIGHT.dimmed_text
+        journal_theme.dimmed_text
     } else {
-        LIGHT.default_text
+        journal_theme.default_text
     };
 
     let base_style = button::Style {
-        background: Some(Background::Color(LIGHT.context_menu_background)),
+        background: Some(Background::Color(journal_theme.context_menu_background)),
         text_color,
         border: Border {
             color: Color::TRANSPARENT,
@@ -59,7 +63,7 @@ pub fn context_menu_style(_theme: &Theme, status: Status) -> button::Style {
     };
 
     let mut darkened_style = base_style;
-    let darkened_background = LIGHT.darken(LIGHT.context_menu_background);
+    let darkened_background = journal_theme.darken(journal_theme.context_menu_background);
     darkened_style.background = Some(Background::Color(darkened_background));
 
     match status {
@@ -69,10 +73,12 @@ pub fn context_menu_style(_theme: &Theme, status: Status) -> button::Style {
 }
 
 /// Styling of the context menu bar
-pub fn context_menu_bar_style(_theme: &Theme, status: Status) -> button::Style {
+pub fn context_menu_bar_style(theme: &Theme, status: Status) -> button::Style {
+    let journal_theme = journal_theme(theme);
+
     let base_style = button::Style {
-        background: Some(Background::Color(LIGHT.context_menu_bar_background)),
-        text_color: LIGHT.default_text,
+        background: Some(Background::Color(journal_theme.context_menu_bar_background)),
+        text_color: journal_theme.default_text,
         border: Border {
             color: Color::TRANSPARENT,
             width: 0.0,
@@ -87,7 +93,7 @@ pub fn context_menu_bar_style(_theme: &Theme, status: Status) -> button::Style {
     };
 
     let mut darkened_style = base_style;
-    let darkened_background = LIGHT.darken(LIGHT.context_menu_bar_background);
+    let darkened_background = journal_theme.darken(journal_theme.context_menu_bar_background);
     darkened_style.background = Some(Background::Color(darkened_background));
 
     match status {
@@ -18,11 +18,16 @@ pub struct HighlightSettings {
     pub(crate) cursor_line_idx: usize,
     pub(crate) cursor_char_idx: usize,
     pub(crate) cursor_spellcheck_timed_out: bool,
+    pub(crate) spellcheck_nearby_lines: Option<usize>,
     pub(crate) search_text: String,
     pub(crate) ignore_search_case: bool,
 }
 
 #[derive(Debug)]
+/// highlights misspelled words and search matches. every line is rehighlighted on each edit, so for very long entries
+/// the settings can limit spellchecking to the lines within spellcheck_nearby_lines of the cursor. this keeps the cost
+/// of each keystroke bounded, at the expense of misspellings far from the cursor not being highlighted until the cursor
+/// moves near them
 pub struct SpellHighlighter {
     current_line: usize,
     settings: HighlightSettings,
@@ -33,6 +38,58 @@ pub enum SpellHighlightColor {
     Green,
 }
 
+impl SpellHighlighter {
+    /// the byte ranges of every occurrence of the search text in the line. once a match is found, the search continues
+    /// after its end, so overlapping occurrences are only highlighted once. characters are compared one at a time
+    /// rather than lowercasing the whole line, since lowercasing can change the byte length of a line and misplace the
+    /// ranges
+    pub fn search_matches(line: &str, search_text: &str, ignore_case: bool) -> Vec<Range<usize>> {
+        let search_chars: Vec<char> = search_text.chars().collect();
+
+        let mut matches = Vec::new();
+
+        if search_chars.is_empty() {
+            return matches;
+        }
+
+        let chars_match = |line_char: &char, search_char: &char| {
+            if ignore_case {
+                line_char.to_lowercase().eq(search_char.to_lowercase())
+            } else {
+                line_char == search_char
+            }
+        };
+
+        let line_chars: Vec<(usize, char)> = line.char_indices().collect();
+
+        let mut column = 0;
+
+        while column + search_chars.len() <= line_chars.len() {
+            let is_match = line_chars[column..(column + search_chars.len())]
+                .iter()
+                .zip(&search_chars)
+                .all(|((_byte_idx, line_char), search_char)| chars_match(line_char, search_char));
+
+            if is_match {
+                let match_end = column + search_chars.len();
+
+                let start_byte = line_chars[column].0;
+                let end_byte = line_chars
+                    .get(match_end)
+                    .map_or(line.len(), |(byte_idx, _char)| *byte_idx);
+
+                matches.push(start_byte..end_byte);
+
+                column = match_end;
+            } else {
+                column += 1;
+            }
+        }
+
+        matches
+    }
+}
+
 impl Highlighter for SpellHighlighter {
     type Settings = HighlightSettings;
     type Highlight = SpellHighlightColor;
@@ -60,10 +117,18 @@ impl Highlighter for SpellHighlighter {
     fn highlight_line(&mut self, line: &str) -> Self::Iterator<'_> {
         let mut highlights = Vec::new();
 
-        if let Some(dictionary) = DICTIONARY
-            .read()
-            .expect("couldn't get dictionary read")
-            .as_ref()
+        let line_is_nearby = self
+            .settings
+            .spellcheck_nearby_lines
+            .is_none_or(|nearby_lines| {
+                self.current_line.abs_diff(self.settings.cursor_line_idx) <= nearby_lines
+            });
+
+        if line_is_nearby
+            && let Some(dictionary) = DICTIONARY
+                .read()
+                .expect("couldn't get dictionary read")
+                .as_ref()
         {
             let cursor_line = self.settings.cursor_line_idx;
             let cursor_char = self.settings.cursor_char_idx;
@@ -84,32 +149,33 @@ impl Highlighter for SpellHighlighter {
                     highlights.push((start..end, SpellHighlightColor::Red));
                 }
             }
+        }
 
-            let (search_line, search_text) = if self.settings.ignore_search_case {
-                (
-                    line.to_lowercase(),
-                    self.settings.search_text.to_lowercase(),
-                )
-            } else {
-                (line.to_string(), self.settings.search_text.clone())
-            };
-
-            if !search_text.is_empty() {
-                let indexes: Vec<usize> = search_line
-                    .match_indices(&search_text)
-                    .map(|(idx, _)| idx)
-                    .collect();
-
-                let search_length = search_text.chars().count();
-
-                for index in indexes.iter() {
-                    highlights.push((*index..(*index + search_length), SpellHighlightColor::Green));
-                }
+        // search highlighting doesn't depend on the dictionary, so it still works when spellcheck is unavailable
+        let search_matches = Self::search_matches(
+            line,
+            &self.settings.search_text,
+            self.settings.ignore_search_case,
+        );
+
+        if !search_matches.is_empty() {
+            // a misspelled word inside a search match is shown as part of the match, so no two highlights overlap
+            highlights.retain(|(spelling_range, _highlight)| {
+                !search_matches.iter().any(|search_range| {
+                    spelling_range.start < search_range.end
+                        && search_range.start < spelling_range.end
+                })
+            });
+
+            for search_range in search_matches {
+                highlights.push((search_range, SpellHighlightColor::Green));
             }
 
-            self.current_line += 1;
+            highlights.sort_by_key(|(range, _highlight)| range.start);
         }
 
+        self.current_line += 1;
+
         highlights.into_iter()
     }
 
@@ -1,4 +1,6 @@
-use iced::{Color, color};
+use iced::{Color, Theme, color, widget::text};
+
+use crate::config::{ThemePreference, preferences};
 
 pub struct JournalTheme {
     pub(crate) default_background: Color,
@@ -41,6 +43,52 @@ pub const LIGHT: JournalTheme = JournalTheme {
     context_menu_bar_background: color!(0xe8e8e8, 1.0),
 };
 
+pub const DARK: JournalTheme = JournalTheme {
+    default_background: color!(0x2b2d31, 1.0),
+    default_text: color!(0xe6e6e6, 1.0),
+    dimmed_text: color!(0x80848e, 1.0),
+
+    darkening_delta: color!(0x111111, 0.0),
+
+    selection: color!(0x179bdd, 1.0),
+    selection_text: color!(0xffffff, 1.0),
+
+    char_count_floor: color!(0xc7e6ff, 0.8),
+    char_count_ceiling: color!(0x0040ff, 0.8),
+
+    link: color!(0x6ea8fe, 1.0),
+
+    context_menu_background: color!(0x313338, 1.0),
+    context_menu_border: color!(0x4e5058, 1.0),
+    context_menu_bar_background: color!(0x232428, 1.0),
+};
+
+/// the journal theme matching the iced theme currently in use. style functions should go through this rather than
+/// naming a journal theme directly, so they follow the system theme when it is selected
+pub fn journal_theme(theme: &Theme) -> &'static JournalTheme {
+    if theme.extended_palette().is_dark {
+        &DARK
+    } else {
+        &LIGHT
+    }
+}
+
+/// the journal theme picked in the preferences, for the few places that are built without access to the iced theme.
+/// the system theme isn't known outside of styling, so it falls back to the light theme
+pub fn preferred_journal_theme() -> &'static JournalTheme {
+    match preferences().general.theme {
+        ThemePreference::Dark => &DARK,
+        ThemePreference::Light | ThemePreference::System => &LIGHT,
+    }
+}
+
+/// styling for secondary text, like status messages and line numbers
+pub fn dimmed_text_style(theme: &Theme) -> text::Style {
+    text::Style {
+        color: Some(journal_theme(theme).dimmed_text),
+    }
+}
+
 impl JournalTheme {
     /// applies the darkening_delta by subtracting it from the given color, returning the result
     pub fn darken(&self, color_to_darken: Color) -> Color {
@@ -11,6 +11,9 @@ pub const MEDIUM_WINDOW_SIZE: Size<f32> =
 pub const SMALL_WINDOW_SIZE: Size<f32> =
     Size::new(MAIN_WINDOW_WIDTH / 2.0, MAIN_WINDOW_HEIGHT / 2.0);
 
+pub const QUICK_CAPTURE_WINDOW_SIZE: Size<f32> =
+    Size::new(MAIN_WINDOW_WIDTH / 2.5, MAIN_WINDOW_HEIGHT / 4.0);
+
 pub const DIALOG_WINDOW_SIZE: Size<f32> =
     Size::new(MAIN_WINDOW_WIDTH / 3.0, MAIN_WINDOW_HEIGHT / 5.0);
 
@@ -28,9 +31,14 @@ pub const TOTAL_CALENDER_HEIGHT: f32 =
 
 pub const LOGBOX_HEIGHT: f32 = 20.0;
 
+pub const MONTH_STRIP_HEIGHT: f32 = 20.0;
+
 pub const DASHBOARD_WIDTH: f32 = TOTAL_CALENDER_WIDTH;
-pub const DASHBOARD_TAB_CONTENT_HEIGHT: f32 =
-    MAIN_WINDOW_HEIGHT - ACTION_BAR_HEIGHT - TOTAL_CALENDER_HEIGHT - LOGBOX_HEIGHT;
+pub const DASHBOARD_TAB_CONTENT_HEIGHT: f32 = MAIN_WINDOW_HEIGHT
+    - ACTION_BAR_HEIGHT
+    - MONTH_STRIP_HEIGHT
+    - TOTAL_CALENDER_HEIGHT
+    - LOGBOX_HEIGHT;
 
 pub const EDITOR_WIDTH: f32 = MAIN_WINDOW_WIDTH - DASHBOARD_WIDTH;
 
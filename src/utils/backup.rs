@@ -0,0 +1,230 @@
+use chrono::{Days, Local, NaiveDateTime};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// the chrono format of each backup's folder name. it sorts the same alphabetically as it does chronologically
+const BACKUP_NAME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// copies the contents of the savedata directory into a new timestamped folder under the backups directory, then
+/// deletes the oldest backups beyond the retention count, along with any older than retention_days (unless it is 0).
+/// returns the new backup's folder. this only touches the disk, so it is safe to run off of the main thread
+pub fn backup_savedata(
+    savedata_dir: &Path,
+    backups_dir: &Path,
+    retention_count: usize,
+    retention_days: usize,
+) -> io::Result<PathBuf> {
+    let backup_time = Local::now().naive_local();
+    let backup_name = backup_time.format(BACKUP_NAME_FORMAT).to_string();
+    let backup_dir = backups_dir.join(backup_name);
+
+    copy_dir_recursive(savedata_dir, &backup_dir)?;
+
+    rotate_backups(
+        backups_dir,
+        retention_count.max(1),
+        retention_days,
+        backup_time,
+    )?;
+
+    Ok(backup_dir)
+}
+
+/// copies every file and folder in the source directory into the destination directory, creating it if needed
+fn copy_dir_recursive(source_dir: &Path, destination_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(destination_dir)?;
+
+    for entry in fs::read_dir(source_dir)? {
+        let entry = entry?;
+        let destination = destination_dir.join(entry.file_name());
+
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&entry.path(), &destination)?;
+        } else {
+            fs::copy(entry.path(), destination)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// deletes the oldest backups until only retention_count are left, then deletes the backups made more than
+/// retention_days before the current time. a retention_days of 0 keeps backups regardless of their age, and the
+/// newest backup is never deleted for its age. only folders named like a backup are counted, so anything else placed
+/// in the backups directory is left alone
+fn rotate_backups(
+    backups_dir: &Path,
+    retention_count: usize,
+    retention_days: usize,
+    current_time: NaiveDateTime,
+) -> io::Result<()> {
+    let mut backup_dirs = Vec::new();
+
+    for entry in fs::read_dir(backups_dir)? {
+        let entry = entry?;
+
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let backup_time = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| NaiveDateTime::parse_from_str(name, BACKUP_NAME_FORMAT).ok());
+
+        if let Some(backup_time) = backup_time {
+            backup_dirs.push((backup_time, entry.path()));
+        }
+    }
+
+    backup_dirs.sort();
+
+    let mut excess_count = backup_dirs.len().saturating_sub(retention_count);
+
+    if retention_days > 0
+        && let Some(cutoff_time) = current_time.checked_sub_days(Days::new(retention_days as u64))
+    {
+        let expired_count = backup_dirs
+            .iter()
+            .take(backup_dirs.len().saturating_sub(1))
+            .filter(|(backup_time, _backup_dir)| *backup_time < cutoff_time)
+            .count();
+
+        excess_count = excess_count.max(expired_count);
+    }
+
+    for (_backup_time, old_backup) in &backup_dirs[..excess_count] {
+        fs::remove_dir_all(old_backup)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    /// creates an empty backups directory in the system's temp directory, unique to the test
+    fn temp_backups_dir(test_name: &str) -> PathBuf {
+        let backups_dir =
+            std::env::temp_dir().join(format!("ironnote-{}-{}", test_name, std::process::id()));
+
+        let _ = fs::remove_dir_all(&backups_dir);
+        fs::create_dir_all(&backups_dir).expect("couldn't create backups dir");
+
+        backups_dir
+    }
+
+    fn backup_time(day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, day)
+            .expect("invalid date")
+            .and_hms_opt(12, 0, 0)
+            .expect("invalid time")
+    }
+
+    /// makes a fake backup folder with a file in it, named like a backup made on the given day
+    fn fake_backup(backups_dir: &Path, day: u32) -> String {
+        let backup_name = backup_time(day).format(BACKUP_NAME_FORMAT).to_string();
+        let backup_dir = backups_dir.join(&backup_name);
+
+        fs::create_dir_all(&backup_dir).expect("couldn't create backup");
+        fs::write(backup_dir.join("2025-03.json"), "{}").expect("couldn't write backup file");
+
+        backup_name
+    }
+
+    fn remaining_entries(backups_dir: &Path) -> Vec<String> {
+        let mut entries = fs::read_dir(backups_dir)
+            .expect("couldn't read backups dir")
+            .map(|entry| {
+                entry
+                    .expect("couldn't read entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+
+        entries.sort();
+
+        entries
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_backups() {
+        let backups_dir = temp_backups_dir("keep-newest");
+
+        let backup_names = [1, 2, 3, 4, 5].map(|day| fake_backup(&backups_dir, day));
+
+        rotate_backups(&backups_dir, 2, 0, backup_time(5)).expect("rotation failed");
+
+        assert_eq!(remaining_entries(&backups_dir), backup_names[3..]);
+
+        fs::remove_dir_all(&backups_dir).expect("couldn't clean up");
+    }
+
+    #[test]
+    fn rotation_deletes_backups_older_than_the_retention_days() {
+        let backups_dir = temp_backups_dir("older-than-days");
+
+        let backup_names = [1, 5, 8, 9, 10].map(|day| fake_backup(&backups_dir, day));
+
+        rotate_backups(&backups_dir, 10, 3, backup_time(10)).expect("rotation failed");
+
+        assert_eq!(remaining_entries(&backups_dir), backup_names[2..]);
+
+        fs::remove_dir_all(&backups_dir).expect("couldn't clean up");
+    }
+
+    #[test]
+    fn rotation_applies_whichever_limit_deletes_more() {
+        let backups_dir = temp_backups_dir("both-limits");
+
+        let backup_names = [1, 5, 8, 9, 10].map(|day| fake_backup(&backups_dir, day));
+
+        rotate_backups(&backups_dir, 2, 7, backup_time(10)).expect("rotation failed");
+
+        assert_eq!(remaining_entries(&backups_dir), backup_names[3..]);
+
+        fs::remove_dir_all(&backups_dir).expect("couldn't clean up");
+    }
+
+    #[test]
+    fn rotation_keeps_the_newest_backup_regardless_of_age() {
+        let backups_dir = temp_backups_dir("newest-kept");
+
+        let backup_names = [1, 2].map(|day| fake_backup(&backups_dir, day));
+
+        rotate_backups(&backups_dir, 5, 3, backup_time(20)).expect("rotation failed");
+
+        assert_eq!(remaining_entries(&backups_dir), backup_names[1..]);
+
+        fs::remove_dir_all(&backups_dir).expect("couldn't clean up");
+    }
+
+    #[test]
+    fn rotation_ignores_entries_that_are_not_backups() {
+        let backups_dir = temp_backups_dir("not-backups");
+
+        fs::create_dir_all(backups_dir.join("keep me")).expect("couldn't create folder");
+        fs::write(backups_dir.join("notes.txt"), "notes").expect("couldn't write file");
+
+        let backup_names = [1, 2, 3].map(|day| fake_backup(&backups_dir, day));
+
+        rotate_backups(&backups_dir, 1, 1, backup_time(3)).expect("rotation failed");
+
+        let mut expected_entries = vec![
+            backup_names[2].clone(),
+            "keep me".to_string(),
+            "notes.txt".to_string(),
+        ];
+        expected_entries.sort();
+
+        assert_eq!(remaining_entries(&backups_dir), expected_entries);
+
+        fs::remove_dir_all(&backups_dir).expect("couldn't clean up");
+    }
+}
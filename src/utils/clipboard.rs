@@ -1,19 +1,52 @@
 use arboard::Clipboard;
+use image::RgbaImage;
 use std::sync::{LazyLock, RwLock};
 
 /// global clipboard, interfaced through read/write_clipboard()
 static CLIPBOARD: LazyLock<RwLock<Clipboard>> =
     LazyLock::new(|| RwLock::new(Clipboard::new().expect("couldn't get clipboard")));
 
-/// returns the current contents of the clipboard
+/// returns the current contents of the clipboard. a clipboard without any text, such as one holding only an image, is
+/// treated as empty
 pub fn read_clipboard() -> String {
     let mut clipboard = CLIPBOARD
         .write()
         .expect("couldn't get clipboard write lock");
 
+    clipboard.get_text().unwrap_or_default()
+}
+
+/// returns the image on the clipboard, if there is one
+pub fn read_clipboard_image() -> Option<RgbaImage> {
+    let mut clipboard = CLIPBOARD
+        .write()
+        .expect("couldn't get clipboard write lock");
+
+    let image_data = clipboard.get_image().ok()?;
+
+    RgbaImage::from_raw(
+        image_data.width as u32,
+        image_data.height as u32,
+        image_data.bytes.into_owned(),
+    )
+}
+
+#[cfg(target_os = "linux")]
+/// returns the current contents of the primary selection (the most recently selected text), which is what a middle
+/// click pastes on Linux
+pub fn read_primary_selection() -> String {
+    use arboard::{GetExtLinux, LinuxClipboardKind};
+
+    let mut clipboard = CLIPBOARD
+        .write()
+        .expect("couldn't get clipboard write lock");
+
+    // the primary selection is empty until something has been selected, which isn't an error worth panicking on
     clipboard
-        .get_text()
-        .expect("couldn't read clipboard contents")
+        .get()
+        .clipboard(LinuxClipboardKind::Primary)
+        .text()
+        .unwrap_or_default()
 }
 
 /// writes the provided string into the system's clipboard
@@ -1,4 +1,5 @@
 use crate::config::preferences;
+use crate::utils::logbox::logbox_mut;
 
 use regex::Regex;
 use spellbook::Dictionary;
@@ -18,6 +19,14 @@ pub fn reload_dictionary() {
     *dictionary = composite_dictionary();
 }
 
+/// returns true if the dictionary was successfully loaded. if it wasn't, spellcheck is unavailable
+pub fn dictionary_loaded() -> bool {
+    DICTIONARY
+        .read()
+        .expect("unable to get DICTIONARY read")
+        .is_some()
+}
+
 /// regex that seperates out words. allows ' and - to show up in the middle of words
 pub static WORD_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\b[\w'-]+\b").expect("couldn't create regex"));
@@ -65,8 +74,7 @@ pub fn composite_dictionary() -> Option<Dictionary> {
 /// dictionary file is updated
 pub fn add_word_to_personal_dictionary(new_word: &str) {
     let personal_dic_path = preferences().paths.personal_dictionary_dic.clone();
-    let personal_dic =
-        fs::read_to_string(&personal_dic_path).expect("couldn't read personal dic to string");
+    let personal_dic = fs::read_to_string(&personal_dic_path).unwrap_or_default();
     let mut dic_entries: Vec<&str> = personal_dic.lines().collect();
 
     if !dic_entries.contains(&new_word) {
@@ -76,7 +84,10 @@ pub fn add_word_to_personal_dictionary(new_word: &str) {
 
         let new_dic = dic_entries.join("\n");
 
-        fs::write(personal_dic_path, new_dic).expect("couldn't save new dic");
+        if fs::write(personal_dic_path, new_dic).is_err() {
+            logbox_mut().log("Couldn't save word to the personal dictionary");
+            return;
+        }
 
         let mut dictionary = DICTIONARY.write().expect("couldn't get dictionary write");
 
@@ -85,3 +96,41 @@ pub fn add_word_to_personal_dictionary(new_word: &str) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{journal_pointer, with_test_preferences};
+
+    /// points every dictionary path at files that don't exist
+    fn missing_dictionary_paths(preferences: &mut crate::config::UserPreferences) {
+        let missing_dir = journal_pointer::test_dir().join("missing");
+
+        preferences.paths.system_dictionary_aff = missing_dir.join("en_US.aff");
+        preferences.paths.system_dictionary_dic = missing_dir.join("en_US.dic");
+        preferences.paths.personal_dictionary_dic = missing_dir.join("personal.dic");
+    }
+
+    #[test]
+    fn missing_dictionary_files_leave_the_dictionary_unloaded() {
+        with_test_preferences(missing_dictionary_paths, || {
+            assert!(composite_dictionary().is_none());
+
+            reload_dictionary();
+
+            assert!(!dictionary_loaded());
+        });
+    }
+
+    #[test]
+    fn adding_a_word_without_a_personal_dictionary_does_not_panic() {
+        with_test_preferences(missing_dictionary_paths, || {
+            reload_dictionary();
+
+            add_word_to_personal_dictionary("ironnote");
+
+            assert!(!dictionary_loaded());
+            assert!(!preferences().paths.personal_dictionary_dic.exists());
+        });
+    }
+}
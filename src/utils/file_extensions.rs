@@ -1,6 +1,9 @@
 const TEXT_EXT: &[&str] = &["txt", "text", "md", "TXT", "TEXT", "MD"];
 pub const TEXT_EXT_LIST: &[(&str, &[&str])] = &[("Plaintext", TEXT_EXT)];
 
+const MARKDOWN_EXT: &[&str] = &["md", "MD"];
+pub const MARKDOWN_EXT_LIST: &[(&str, &[&str])] = &[("Markdown", MARKDOWN_EXT)];
+
 const JSON_EXT: &[&str] = &["json", "JSON"];
 pub const JSON_EXT_LIST: &[(&str, &[&str])] = &[("JSON", JSON_EXT)];
 
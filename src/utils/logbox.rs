@@ -1,9 +1,13 @@
+use crate::config::{SaveNotification, preferences};
+
 use chrono::{DateTime, Local};
 use std::sync::{LazyLock, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 pub struct Logbox {
     message: Option<String>,
     timestamp: DateTime<Local>,
+    history: Vec<(DateTime<Local>, String)>,
+    toast: Option<(String, DateTime<Local>)>,
 }
 
 impl Default for Logbox {
@@ -11,6 +15,8 @@ impl Default for Logbox {
         Self {
             message: None,
             timestamp: Local::now(),
+            history: Vec::new(),
+            toast: None,
         }
     }
 }
@@ -20,6 +26,61 @@ impl Logbox {
     pub fn log(&mut self, message: &str) {
         self.message = Some(message.to_string());
         self.timestamp = Local::now();
+
+        self.push_history(self.timestamp, message);
+    }
+
+    /// shows the message in a toast that fades away on its own, rather than in the logbox. the message still goes into
+    /// the history
+    pub fn toast(&mut self, message: &str) {
+        let timestamp = Local::now();
+
+        self.toast = Some((message.to_string(), timestamp));
+
+        self.push_history(timestamp, message);
+    }
+
+    /// confirms a save to the user, either in the logbox or in a toast depending on the preferences
+    pub fn log_save(&mut self, message: &str) {
+        match preferences().general.save_notification {
+            SaveNotification::LogMessage => self.log(message),
+            SaveNotification::Toast => self.toast(message),
+        }
+    }
+
+    /// returns the message of the toast being shown along with its opacity, which drops from 1 to 0 as the toast fades
+    /// out. None once the toast has fully faded
+    pub fn active_toast(&self) -> Option<(String, f32)> {
+        const TOAST_SHOWN_MILLIS: i64 = 2500;
+        const TOAST_FADE_MILLIS: i64 = 1000;
+
+        let (message, timestamp) = self.toast.as_ref()?;
+
+        let age_millis = (Local::now() - *timestamp).num_milliseconds();
+
+        if age_millis >= TOAST_SHOWN_MILLIS + TOAST_FADE_MILLIS {
+            return None;
+        }
+
+        let fade_millis = (age_millis - TOAST_SHOWN_MILLIS).max(0);
+        let opacity = 1.0 - (fade_millis as f32 / TOAST_FADE_MILLIS as f32);
+
+        Some((message.clone(), opacity))
+    }
+
+    /// adds the message to the history, dropping the oldest message once the history is full
+    fn push_history(&mut self, timestamp: DateTime<Local>, message: &str) {
+        const MAX_HISTORY_LENGTH: usize = 500;
+
+        if self.history.len() >= MAX_HISTORY_LENGTH {
+            self.history.remove(0);
+        }
+        self.history.push((timestamp, message.to_string()));
+    }
+
+    /// returns every message logged this session, oldest first, along with the time it was logged
+    pub fn history(&self) -> &[(DateTime<Local>, String)] {
+        &self.history
     }
 
     /// returns the content of the logbox message in the format "message at timestamp"
@@ -1,23 +1,25 @@
 // random tools and utilities that don't really fit anywhere in specific
 
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate, Weekday};
 
-/// returns true if all of the characters in the input string are the same character. returns true on an empty string
-pub fn chars_all_same_in_string(input: &str) -> bool {
-    if let Some(first_char) = input.chars().next() {
-        for chara in input.chars() {
-            if chara != first_char {
-                return false;
-            }
-        }
-        true
-    } else {
-        // no characters in string
-        true
-    }
+/// the long form a date is written out in wherever it is shown to the user as a heading, like "Monday, January 01, 2024"
+pub const LONG_DATE_FORMAT: &str = "%A, %B %d, %Y";
+
+/// returns true if the date falls on a Saturday or Sunday
+pub fn is_weekend(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
 }
 
 /// converts a string in the form of "YYYY-MM-DD" into a NaiveDate
 pub fn yyyy_mm_dd_string_to_date(input: &str) -> NaiveDate {
     NaiveDate::parse_from_str(input, "%Y-%m-%d").expect("couldn't parse date")
 }
+
+/// converts user typed text in the form of "YYYY-MM-DD" into a NaiveDate, ignoring surrounding whitespace. returns
+/// None if the text isn't a valid date. the year has to be written out in full, so a mistyped year like "24" or "202"
+/// is refused rather than taken as a date thousands of years away
+pub fn parse_yyyy_mm_dd(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+        .ok()
+        .filter(|date| (1000..=9999).contains(&date.year()))
+}
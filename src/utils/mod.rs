@@ -1,7 +1,10 @@
+pub mod backup;
 pub mod clipboard;
 pub mod dictionary;
 pub mod file_extensions;
 pub mod logbox;
 pub mod misc_tools;
 pub mod month_day;
+pub mod summon_hotkey;
+pub mod system_tray;
 pub mod text_tools;
@@ -0,0 +1,103 @@
+use crate::utils::logbox::logbox_mut;
+
+use global_hotkey::{
+    GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState,
+    hotkey::{HotKey, HotKeyParseError},
+};
+use std::{cell::RefCell, str::FromStr};
+
+#[derive(Default)]
+/// the OS level hotkey that brings the application to the front. the manager unregisters every hotkey once it is
+/// dropped, so it is kept alive for as long as the hotkey should work
+struct SummonHotkey {
+    manager: Option<GlobalHotKeyManager>,
+
+    /// the hotkey currently registered with the OS, along with the text it was parsed from
+    registered: Option<(HotKey, String)>,
+
+    /// the text of the last hotkey that failed to register, so the same failure isn't retried and logged over and over
+    failed_text: Option<String>,
+}
+
+thread_local! {
+    static SUMMON_HOTKEY: RefCell<SummonHotkey> = RefCell::new(SummonHotkey::default());
+}
+
+/// parses hotkey text like "Ctrl+Shift+J" into a HotKey
+pub fn parse_hotkey(hotkey_text: &str) -> Result<HotKey, HotKeyParseError> {
+    HotKey::from_str(hotkey_text.trim())
+}
+
+/// makes the registered summon hotkey match the given preferences. if the hotkey is disabled, any registered hotkey is
+/// removed. nothing is done if the hotkey is already registered as given, so this is cheap to call repeatedly
+pub fn sync_summon_hotkey(enabled: bool, hotkey_text: &str) {
+    SUMMON_HOTKEY.with(|summon_hotkey| {
+        let mut summon_hotkey = summon_hotkey.borrow_mut();
+
+        let wanted_text = enabled.then(|| hotkey_text.trim().to_string());
+
+        let registered_text = summon_hotkey
+            .registered
+            .as_ref()
+            .map(|(_hotkey, registered_text)| registered_text.clone());
+
+        if wanted_text == registered_text
+            || (wanted_text.is_some() && wanted_text == summon_hotkey.failed_text)
+        {
+            return;
+        }
+
+        if let Some((registered_hotkey, _registered_text)) = summon_hotkey.registered.take()
+            && let Some(manager) = &summon_hotkey.manager
+        {
+            let _ = manager.unregister(registered_hotkey);
+        }
+
+        summon_hotkey.failed_text = None;
+
+        let Some(wanted_text) = wanted_text else {
+            return;
+        };
+
+        if summon_hotkey.manager.is_none() {
+            summon_hotkey.manager = GlobalHotKeyManager::new().ok();
+        }
+
+        let registered = match (&summon_hotkey.manager, parse_hotkey(&wanted_text)) {
+            (Some(manager), Ok(hotkey)) => manager.register(hotkey).is_ok().then_some(hotkey),
+            _ => None,
+        };
+
+        if let Some(hotkey) = registered {
+            summon_hotkey.registered = Some((hotkey, wanted_text));
+        } else {
+            logbox_mut().log(&format!(
+                "Couldn't register \"{}\" as the global hotkey",
+                wanted_text
+            ));
+
+            summon_hotkey.failed_text = Some(wanted_text);
+        }
+    });
+}
+
+/// returns true if the summon hotkey was pressed since this was last called
+pub fn summon_hotkey_pressed() -> bool {
+    let registered_id = SUMMON_HOTKEY.with(|summon_hotkey| {
+        summon_hotkey
+            .borrow()
+            .registered
+            .as_ref()
+            .map(|(hotkey, _registered_text)| hotkey.id())
+    });
+
+    let mut pressed = false;
+
+    while let Ok(hotkey_event) = GlobalHotKeyEvent::receiver().try_recv() {
+        if Some(hotkey_event.id) == registered_id && hotkey_event.state == HotKeyState::Pressed {
+            pressed = true;
+        }
+    }
+
+    pressed
+}
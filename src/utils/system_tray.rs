@@ -0,0 +1,137 @@
+use crate::utils::logbox::logbox_mut;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use tray_icon::{
+    Icon, TrayIcon, TrayIconBuilder,
+    menu::{Menu, MenuEvent, MenuItem},
+};
+
+const OPEN_MENU_ID: &str = "open";
+const QUIT_MENU_ID: &str = "quit";
+
+/// width and height of the generated tray icon, in pixels
+const TRAY_ICON_SIZE: u32 = 32;
+
+/// set once starting the tray has been attempted, so only one tray icon is ever created per session
+static TRAY_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// set once the tray icon actually exists. until then, there is no way back into the application from the tray
+static TRAY_RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[cfg(not(target_os = "linux"))]
+thread_local! {
+    /// the tray icon is removed as soon as it is dropped, so it is kept alive here for the rest of the session
+    static TRAY_ICON: std::cell::RefCell<Option<TrayIcon>> = const { std::cell::RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// actions that can be picked from the tray icon's menu
+pub enum TrayAction {
+    /// opens the main window, or focuses it if it is already open
+    Open,
+
+    /// saves and exits the application
+    Quit,
+}
+
+/// starts the tray icon if it hasn't been started yet this session. on Linux the tray needs its own GTK event loop, so
+/// it is run on a separate thread
+pub fn start_tray() {
+    if TRAY_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    #[cfg(target_os = "linux")]
+    std::thread::spawn(|| {
+        if gtk::init().is_err() {
+            logbox_mut().log("Couldn't start the system tray, GTK is unavailable");
+            return;
+        }
+
+        let _tray_icon = build_tray_icon();
+
+        gtk::main();
+    });
+
+    #[cfg(not(target_os = "linux"))]
+    TRAY_ICON.with(|tray_icon| *tray_icon.borrow_mut() = build_tray_icon());
+}
+
+/// true if the tray icon exists, so the application can be reopened or quit from it
+pub fn tray_running() -> bool {
+    TRAY_RUNNING.load(Ordering::SeqCst)
+}
+
+/// returns the next action picked from the tray menu since this was last called, if there is one
+pub fn next_tray_action() -> Option<TrayAction> {
+    while let Ok(menu_event) = MenuEvent::receiver().try_recv() {
+        match menu_event.id.0.as_str() {
+            OPEN_MENU_ID => return Some(TrayAction::Open),
+            QUIT_MENU_ID => return Some(TrayAction::Quit),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// creates the tray icon along with its menu. failures are logged rather than treated as fatal, since the application
+/// still works without the tray
+fn build_tray_icon() -> Option<TrayIcon> {
+    let menu = Menu::new();
+
+    let open_item = MenuItem::with_id(OPEN_MENU_ID, "Open ironnote", true, None);
+    let quit_item = MenuItem::with_id(QUIT_MENU_ID, "Quit", true, None);
+
+    let tray_icon = menu
+        .append_items(&[&open_item, &quit_item])
+        .ok()
+        .and_then(|_| Icon::from_rgba(tray_icon_rgba(), TRAY_ICON_SIZE, TRAY_ICON_SIZE).ok())
+        .and_then(|icon| {
+            TrayIconBuilder::new()
+                .with_menu(Box::new(menu))
+                .with_tooltip("ironnote")
+                .with_icon(icon)
+                .build()
+                .ok()
+        });
+
+    if tray_icon.is_some() {
+        TRAY_RUNNING.store(true, Ordering::SeqCst);
+    } else {
+        logbox_mut().log("Couldn't create the system tray icon");
+    }
+
+    tray_icon
+}
+
+/// draws the tray icon: a white page with a few dark lines of text on it, over a transparent background
+fn tray_icon_rgba() -> Vec<u8> {
+    const PAGE_MARGIN: u32 = 5;
+    const TEXT_MARGIN: u32 = 10;
+
+    let mut rgba = Vec::with_capacity((TRAY_ICON_SIZE * TRAY_ICON_SIZE * 4) as usize);
+
+    for y in 0..TRAY_ICON_SIZE {
+        for x in 0..TRAY_ICON_SIZE {
+            let on_page = (PAGE_MARGIN..TRAY_ICON_SIZE - PAGE_MARGIN).contains(&x)
+                && (PAGE_MARGIN - 2..TRAY_ICON_SIZE - PAGE_MARGIN + 2).contains(&y);
+
+            let on_text = (TEXT_MARGIN..TRAY_ICON_SIZE - TEXT_MARGIN).contains(&x)
+                && (TEXT_MARGIN..TRAY_ICON_SIZE - TEXT_MARGIN).contains(&y)
+                && y % 4 == 0;
+
+            let pixel = if on_text {
+                [40, 40, 40, 255]
+            } else if on_page {
+                [245, 245, 245, 255]
+            } else {
+                [0, 0, 0, 0]
+            };
+
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+
+    rgba
+}
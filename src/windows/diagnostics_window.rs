@@ -0,0 +1,163 @@
+use super::window_manager::{WindowType, Windowable};
+use crate::{
+    SharedAppState, UpstreamAction,
+    config::preferences,
+    content::ContentAction,
+    keyboard_manager::KeyboardAction,
+    utils::{clipboard::write_clipboard, dictionary, logbox::logbox},
+};
+
+use iced::{
+    Length, Task,
+    widget::{Space, Text, button, column, row, scrollable},
+};
+
+#[derive(Debug, Clone)]
+pub enum DiagnosticsMessage {
+    KeyEvent(KeyboardAction),
+
+    Refresh,
+    CopyToClipboard,
+    Close,
+}
+
+#[derive(Debug, Default)]
+/// a read only view of the session log and the state of the journal, for diagnosing problems and writing bug reports
+pub struct Diagnostics {
+    /// the report shown in the window. it is built when the window is opened and whenever it is refreshed, rather
+    /// than on every redraw
+    report: String,
+}
+
+impl Windowable<DiagnosticsMessage> for Diagnostics {
+    fn title(&self) -> String {
+        "Diagnostics".to_string()
+    }
+
+    fn view<'a>(&'a self, _state: &'a SharedAppState) -> iced::Element<'a, DiagnosticsMessage> {
+        let report = scrollable(Text::new(&self.report).size(13))
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        let refresh_button = button(Text::new("Refresh")).on_press(DiagnosticsMessage::Refresh);
+        let copy_button =
+            button(Text::new("Copy to Clipboard")).on_press(DiagnosticsMessage::CopyToClipboard);
+        let close_button = button(Text::new("Close")).on_press(DiagnosticsMessage::Close);
+
+        let bottom_buttons = row![
+            Space::new().width(Length::Fill),
+            refresh_button,
+            copy_button,
+            close_button
+        ];
+
+        column![report, bottom_buttons].into()
+    }
+
+    fn update(
+        &mut self,
+        state: &mut SharedAppState,
+        message: DiagnosticsMessage,
+    ) -> Task<DiagnosticsMessage> {
+        match message {
+            DiagnosticsMessage::KeyEvent(_keyboard_action) => {}
+            DiagnosticsMessage::Refresh => {
+                self.report = Self::report(state);
+            }
+            DiagnosticsMessage::CopyToClipboard => {
+                write_clipboard(self.report.clone());
+            }
+            DiagnosticsMessage::Close => {
+                state
+                    .upstream_actions
+                    .push(UpstreamAction::CloseWindow(WindowType::Diagnostics));
+            }
+        }
+
+        Task::none()
+    }
+
+    fn content_perform(&mut self, _state: &mut SharedAppState, _action: ContentAction) {}
+}
+
+impl Diagnostics {
+    /// builds the full diagnostics text: the environment, the loaded journal, the configured paths, the dictionary
+    /// status, and every message logged this session, which includes any load errors. entry text is never included
+    pub fn report(state: &SharedAppState) -> String {
+        let paths = preferences().paths.clone();
+
+        let path_status = |path: &std::path::Path| {
+            if path.exists() { "found" } else { "missing" }
+        };
+
+        let date_range = match (
+            state.global_store.first_edited_day(),
+            state.global_store.last_edited_day(),
+        ) {
+            (Some(first_day), Some(last_day)) => format!("{} to {}", first_day, last_day),
+            _ => "none".to_string(),
+        };
+
+        let mut report = String::new();
+
+        report.push_str("Environment\n");
+        report.push_str(&format!("  version: {}\n", env!("CARGO_PKG_VERSION")));
+        report.push_str(&format!(
+            "  os: {} ({})\n",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ));
+
+        report.push_str("\nJournal\n");
+        report.push_str(&format!(
+            "  months loaded: {}\n",
+            state.global_store.month_stores().count()
+        ));
+        report.push_str(&format!(
+            "  months not loaded yet: {}\n",
+            state.global_store.unloaded_month_count()
+        ));
+        report.push_str(&format!(
+            "  edited days: {}\n",
+            state.global_store.edited_day_count()
+        ));
+        report.push_str(&format!("  edited range: {}\n", date_range));
+        report.push_str(&format!(
+            "  current day: {}\n",
+            state.global_store.current_date()
+        ));
+
+        report.push_str("\nPaths\n");
+        for (path_name, path) in [
+            ("journal", &paths.journal_path),
+            ("preferences", &paths.preferences_path),
+            ("system dictionary .dic", &paths.system_dictionary_dic),
+            ("system dictionary .aff", &paths.system_dictionary_aff),
+            ("personal dictionary", &paths.personal_dictionary_dic),
+        ] {
+            report.push_str(&format!(
+                "  {}: {} ({})\n",
+                path_name,
+                path.display(),
+                path_status(path.as_path())
+            ));
+        }
+
+        report.push_str("\nSpellcheck\n");
+        report.push_str(&format!(
+            "  dictionary loaded: {}\n",
+            dictionary::dictionary_loaded()
+        ));
+
+        report.push_str("\nSession Log\n");
+        let logbox = logbox();
+        if logbox.history().is_empty() {
+            report.push_str("  nothing logged\n");
+        }
+        for (timestamp, message) in logbox.history() {
+            report.push_str(&format!("  {} {}\n", timestamp.format("%H:%M:%S"), message));
+        }
+
+        report
+    }
+}
@@ -1,24 +1,190 @@
 use super::window_manager::{WindowType, Windowable};
 use crate::{
     SharedAppState, UpstreamAction,
+    config::{preferences, preferences_mut},
     content::ContentAction,
     custom_widgets::file_picker::{FilePicker, FilePickerMessage},
+    dialogs::DialogType,
     keyboard_manager::KeyboardAction,
-    utils::file_extensions::{self, TEXT_EXT_LIST},
+    utils::file_extensions::{self, MARKDOWN_EXT_LIST, TEXT_EXT_LIST},
+    utils::misc_tools::LONG_DATE_FORMAT,
 };
 
-use chrono::{Datelike, Days};
+use chrono::{Datelike, Days, NaiveDate};
 use iced::{
     Task,
-    widget::{Text, button, column, radio, row},
+    widget::{Text, button, checkbox, column, pick_list, radio, row},
 };
-use std::{fs, path::PathBuf};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use strum::Display;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-pub enum FileExportStrategy {
+/// which entries get exported
+pub enum FileExportScope {
+    /// the current day, into a single file
     #[default]
     SingleDay,
+
+    /// every edited day, each into its own file
     AllSingle,
+
+    /// every edited day, in order, as sections of a single markdown file
+    MarkdownBundle,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display)]
+/// the format the exported entries are written in. the format is independent of the scope, so every scope can be
+/// exported in every format, except for the markdown bundle which is always markdown
+pub enum FileExportFormat {
+    #[default]
+    Plaintext,
+    Markdown,
+    #[strum(to_string = "HTML")]
+    Html,
+}
+
+impl FileExportFormat {
+    pub const ALL: [FileExportFormat; 3] = [
+        FileExportFormat::Plaintext,
+        FileExportFormat::Markdown,
+        FileExportFormat::Html,
+    ];
+
+    /// the file extension given to files exported in bulk. plaintext files have never had an extension, so they
+    /// continue to have none
+    fn extension(&self) -> Option<&'static str> {
+        match self {
+            FileExportFormat::Plaintext => None,
+            FileExportFormat::Markdown => Some("md"),
+            FileExportFormat::Html => Some("html"),
+        }
+    }
+
+    /// formats the entry text of the given date into the export format
+    pub fn format_entry(&self, date: NaiveDate, entry_text: &str) -> String {
+        let entry_text = &with_header_and_footer(date, entry_text);
+
+        match self {
+            FileExportFormat::Plaintext => entry_text.to_string(),
+            FileExportFormat::Markdown => format!("# {}\n\n{}\n", date, entry_text),
+            FileExportFormat::Html => {
+                let paragraphs = entry_text
+                    .split("\n\n")
+                    .filter(|paragraph| !paragraph.trim().is_empty())
+                    .map(|paragraph| {
+                        format!("<p>{}</p>", escape_html(paragraph).replace('\n', "<br>\n"))
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n");
+
+                format!(
+                    "<!DOCTYPE html>\n\
+                    <html>\n\
+                    <head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n\
+                    <body>\n<h1>{}</h1>\n{}\n</body>\n\
+                    </html>\n",
+                    date, date, paragraphs
+                )
+            }
+        }
+    }
+}
+
+/// surrounds the entry text with the header and footer from the export preferences, with their date tokens filled in
+/// for the given date. the header and footer are only added when they aren't empty
+fn with_header_and_footer(date: NaiveDate, entry_text: &str) -> String {
+    let export_prefs = &preferences().export;
+
+    let mut full_text = String::new();
+
+    if !export_prefs.header.is_empty() {
+        full_text.push_str(&expand_date_tokens(&export_prefs.header, date));
+        full_text.push_str("\n\n");
+    }
+
+    full_text.push_str(entry_text);
+
+    if !export_prefs.footer.is_empty() {
+        full_text.push_str("\n\n");
+        full_text.push_str(&expand_date_tokens(&export_prefs.footer, date));
+    }
+
+    full_text
+}
+
+/// replaces the {date}, {weekday}, {day}, {month}, and {year} tokens in the template with the parts of the given date
+fn expand_date_tokens(template: &str, date: NaiveDate) -> String {
+    template
+        .replace("{date}", &date.to_string())
+        .replace("{weekday}", &date.format("%A").to_string())
+        .replace("{day}", &date.day().to_string())
+        .replace("{month}", &date.format("%B").to_string())
+        .replace("{year}", &date.year().to_string())
+}
+
+/// the existing contents of the file with the exported entry added onto the end, separated by a line with the entry's
+/// date. if the file doesn't exist or is empty, the entry is exported on its own. any other problem reading the file is
+/// returned, so the file isn't overwritten with only the entry
+fn appended_export(export_path: &Path, date: NaiveDate, day_text: &str) -> io::Result<String> {
+    let existing_text = match fs::read_to_string(export_path) {
+        Ok(existing_text) => existing_text,
+        Err(read_error) if read_error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(read_error) => return Err(read_error),
+    };
+
+    if existing_text.trim().is_empty() {
+        return Ok(day_text.to_string());
+    }
+
+    Ok(format!(
+        "{}\n\n--- {} ---\n\n{}",
+        existing_text.trim_end(),
+        date.format(LONG_DATE_FORMAT),
+        day_text
+    ))
+}
+
+/// formats every edited day into a single markdown document. each day is a section headed by its long form date,
+/// followed by the entry and a horizontal rule
+fn markdown_bundle(state: &SharedAppState) -> String {
+    let mut bundle = String::new();
+
+    if let Some(first_edited_day) = state.global_store.first_edited_day()
+        && let Some(last_edited_day) = state.global_store.last_edited_day()
+    {
+        let mut iterative_day = first_edited_day;
+
+        while iterative_day <= last_edited_day {
+            if let Some(day_store) = state.global_store.get_day(iterative_day)
+                && day_store.contains_entry()
+            {
+                let entry_text = with_header_and_footer(iterative_day, &day_store.get_day_text());
+
+                bundle.push_str(&format!(
+                    "## {}\n\n{}\n\n---\n\n",
+                    iterative_day.format(LONG_DATE_FORMAT),
+                    entry_text.trim_end()
+                ));
+            }
+
+            iterative_day = iterative_day
+                .checked_add_days(Days::new(1))
+                .expect("couldn't add day");
+        }
+    }
+
+    bundle
+}
+
+/// escapes the characters that have special meaning in html
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
 }
 
 #[derive(Debug, Clone)]
@@ -26,7 +192,9 @@ pub enum FileExportMessage {
     KeyEvent(KeyboardAction),
 
     FilePicker(FilePickerMessage),
-    SelectedStrategy(FileExportStrategy),
+    SelectedScope(FileExportScope),
+    SelectedFormat(FileExportFormat),
+    ToggleAppend(bool),
     Cancel,
     Export,
 }
@@ -35,21 +203,36 @@ pub enum FileExportMessage {
 pub struct FileExport {
     individial_file_picker: FilePicker,
     bulk_directory_picker: FilePicker,
+    bundle_file_picker: FilePicker,
     filepicker_content_is_active: bool,
-    export_strategy: FileExportStrategy,
+    export_scope: FileExportScope,
+    export_format: FileExportFormat,
+
+    /// if true, a single day export is added onto the end of the chosen file rather than replacing it
+    append_single_day: bool,
 }
 
 impl Default for FileExport {
     fn default() -> Self {
-        Self {
+        let mut file_export = Self {
             individial_file_picker: FilePicker::file(
                 PathBuf::new(),
                 &file_extensions::build_extensions(TEXT_EXT_LIST),
             ),
             bulk_directory_picker: FilePicker::directory(PathBuf::new()),
+            bundle_file_picker: FilePicker::file(
+                PathBuf::new(),
+                &file_extensions::build_extensions(MARKDOWN_EXT_LIST),
+            ),
             filepicker_content_is_active: false,
-            export_strategy: Default::default(),
-        }
+            export_scope: Default::default(),
+            export_format: Default::default(),
+            append_single_day: false,
+        };
+
+        file_export.set_starting_directory(preferences().export.default_directory.clone());
+
+        file_export
     }
 }
 
@@ -61,29 +244,55 @@ impl Windowable<FileExportMessage> for FileExport {
     fn view<'a>(&'a self, _state: &SharedAppState) -> iced::Element<'a, FileExportMessage> {
         let radio_single_day = radio(
             "Export current day",
-            FileExportStrategy::SingleDay,
-            (self.export_strategy == FileExportStrategy::SingleDay)
-                .then_some(FileExportStrategy::SingleDay),
-            FileExportMessage::SelectedStrategy,
+            FileExportScope::SingleDay,
+            Some(self.export_scope),
+            FileExportMessage::SelectedScope,
         );
 
         let radio_all_single = radio(
-            "Export all days individually as plaintext",
-            FileExportStrategy::AllSingle,
-            (self.export_strategy == FileExportStrategy::AllSingle)
-                .then_some(FileExportStrategy::AllSingle),
-            FileExportMessage::SelectedStrategy,
+            "Export all days individually",
+            FileExportScope::AllSingle,
+            Some(self.export_scope),
+            FileExportMessage::SelectedScope,
         );
 
-        let file_picker = match self.export_strategy {
-            FileExportStrategy::SingleDay => self
+        let radio_markdown_bundle = radio(
+            "Export all days into a single markdown file",
+            FileExportScope::MarkdownBundle,
+            Some(self.export_scope),
+            FileExportMessage::SelectedScope,
+        );
+
+        let format_picker = (self.export_scope != FileExportScope::MarkdownBundle).then(|| {
+            row![
+                Text::new("Format: "),
+                pick_list(
+                    FileExportFormat::ALL,
+                    Some(self.export_format),
+                    FileExportMessage::SelectedFormat
+                )
+            ]
+        });
+
+        let append_checkbox = (self.export_scope == FileExportScope::SingleDay).then(|| {
+            checkbox(self.append_single_day)
+                .on_toggle(FileExportMessage::ToggleAppend)
+                .label("Append to the end of the file instead of overwriting it")
+        });
+
+        let file_picker = match self.export_scope {
+            FileExportScope::SingleDay => self
                 .individial_file_picker
                 .view()
                 .map(FileExportMessage::FilePicker),
-            FileExportStrategy::AllSingle => self
+            FileExportScope::AllSingle => self
                 .bulk_directory_picker
                 .view()
                 .map(FileExportMessage::FilePicker),
+            FileExportScope::MarkdownBundle => self
+                .bundle_file_picker
+                .view()
+                .map(FileExportMessage::FilePicker),
         };
 
         let cancel_button = button(Text::new("Cancel")).on_press(FileExportMessage::Cancel);
@@ -96,6 +305,9 @@ impl Windowable<FileExportMessage> for FileExport {
             Text::new("Export File"),
             radio_single_day,
             radio_all_single,
+            radio_markdown_bundle,
+            format_picker,
+            append_checkbox,
             file_picker,
             bottom_buttons
         ]
@@ -115,6 +327,15 @@ impl Windowable<FileExportMessage> for FileExport {
                     }
                     KeyboardAction::Save => {}
                     KeyboardAction::Debug => {}
+                    KeyboardAction::OpenPreferences => {}
+                    KeyboardAction::NewTask => {}
+                    KeyboardAction::SearchCursorWord => {}
+                    KeyboardAction::Paste => {}
+                    KeyboardAction::RevertEntry => {}
+                    KeyboardAction::JumpToDate => {}
+                    KeyboardAction::SwitchFocusRegion => {}
+                    KeyboardAction::ToggleFocusMode => {}
+                    KeyboardAction::TaskNavigation(_task_navigation) => {}
                     KeyboardAction::Unbound(_unbound_key) => {}
                 };
             }
@@ -122,15 +343,26 @@ impl Windowable<FileExportMessage> for FileExport {
                 self.filepicker_content_is_active =
                     matches!(&message, FilePickerMessage::FilepathEdit(_content_action));
 
-                match self.export_strategy {
-                    FileExportStrategy::SingleDay => self.individial_file_picker.update(message),
-                    FileExportStrategy::AllSingle => self.bulk_directory_picker.update(message),
+                match self.export_scope {
+                    FileExportScope::SingleDay => self.individial_file_picker.update(message),
+                    FileExportScope::AllSingle => self.bulk_directory_picker.update(message),
+                    FileExportScope::MarkdownBundle => self.bundle_file_picker.update(message),
                 }
             }
-            FileExportMessage::SelectedStrategy(strategy) => {
+            FileExportMessage::SelectedScope(scope) => {
+                self.filepicker_content_is_active = false;
+
+                self.export_scope = scope;
+            }
+            FileExportMessage::SelectedFormat(format) => {
+                self.filepicker_content_is_active = false;
+
+                self.export_format = format;
+            }
+            FileExportMessage::ToggleAppend(checked) => {
                 self.filepicker_content_is_active = false;
 
-                self.export_strategy = strategy;
+                self.append_single_day = checked;
             }
             FileExportMessage::Cancel => {
                 self.filepicker_content_is_active = false;
@@ -142,45 +374,137 @@ impl Windowable<FileExportMessage> for FileExport {
             FileExportMessage::Export => {
                 self.filepicker_content_is_active = false;
 
-                match self.export_strategy {
-                    FileExportStrategy::SingleDay => {
-                        let day_text = state.global_store.day().get_day_text();
+                state.global_store.load_all();
+
+                let mut exported_directory = None;
+                let mut export_failure = None;
+
+                match self.export_scope {
+                    FileExportScope::SingleDay => {
+                        let day_text = self.export_format.format_entry(
+                            state.global_store.current_date(),
+                            &state.global_store.day().get_day_text(),
+                        );
 
-                        if let Err(_error) = fs::write(self.individial_file_picker.path(), day_text)
+                        let export_path = self.individial_file_picker.path();
+
+                        let export_text = if self.append_single_day {
+                            appended_export(
+                                &export_path,
+                                state.global_store.current_date(),
+                                &day_text,
+                            )
+                        } else {
+                            Ok(day_text)
+                        };
+
+                        match export_text
+                            .and_then(|export_text| fs::write(&export_path, export_text))
                         {
+                            Ok(()) => {
+                                exported_directory = export_path.parent().map(PathBuf::from);
+                            }
+                            Err(export_error) => {
+                                export_failure = Some(format!(
+                                    "Couldn't export to {}: {}",
+                                    export_path.display(),
+                                    export_error
+                                ));
+                            }
                         }
                     }
-                    FileExportStrategy::AllSingle => {
+                    FileExportScope::AllSingle => {
                         if let Some(first_edited_day) = state.global_store.first_edited_day()
                             && let Some(last_edited_day) = state.global_store.last_edited_day()
                         {
                             let mut iterative_day = first_edited_day;
 
+                            let mut failed_count = 0;
+                            let mut last_export_error = None;
+
                             while iterative_day <= last_edited_day {
                                 if let Some(day_store) = state.global_store.get_day(iterative_day)
                                     && day_store.contains_entry()
                                 {
                                     let year = iterative_day.year().to_string();
-                                    let filename = iterative_day.to_string();
+                                    let filename = match self.export_format.extension() {
+                                        Some(extension) => {
+                                            format!("{}.{}", iterative_day, extension)
+                                        }
+                                        None => iterative_day.to_string(),
+                                    };
 
                                     let mut root_path = self.bulk_directory_picker.path();
                                     root_path.push(year);
-                                    if let Err(_error) = fs::create_dir_all(&root_path) {}
 
-                                    root_path.push(filename);
+                                    let day_text = self
+                                        .export_format
+                                        .format_entry(iterative_day, &day_store.get_day_text());
 
-                                    let day_text = day_store.get_day_text();
+                                    let export_result =
+                                        fs::create_dir_all(&root_path).and_then(|()| {
+                                            root_path.push(filename);
 
-                                    if let Err(_error) = fs::write(root_path, day_text) {}
+                                            fs::write(&root_path, day_text)
+                                        });
+
+                                    if let Err(export_error) = export_result {
+                                        failed_count += 1;
+                                        last_export_error = Some(format!(
+                                            "{}: {}",
+                                            root_path.display(),
+                                            export_error
+                                        ));
+                                    }
                                 }
 
                                 iterative_day = iterative_day
                                     .checked_add_days(Days::new(1))
                                     .expect("couldn't add day");
                             }
+
+                            exported_directory = Some(self.bulk_directory_picker.path());
+
+                            // a single problem, like a read only directory, tends to fail every entry, so only the
+                            // last error is shown rather than one per entry
+                            if let Some(last_export_error) = last_export_error {
+                                export_failure = Some(format!(
+                                    "Couldn't export {} of the entries. The last error was {}",
+                                    failed_count, last_export_error
+                                ));
+                            }
+                        }
+                    }
+                    FileExportScope::MarkdownBundle => {
+                        let export_path = self.bundle_file_picker.path();
+
+                        match fs::write(&export_path, markdown_bundle(state)) {
+                            Ok(()) => {
+                                exported_directory = export_path.parent().map(PathBuf::from);
+                            }
+                            Err(export_error) => {
+                                export_failure = Some(format!(
+                                    "Couldn't export to {}: {}",
+                                    export_path.display(),
+                                    export_error
+                                ));
+                            }
                         }
                     }
                 }
+
+                if let Some(exported_directory) = exported_directory
+                    && exported_directory.is_dir()
+                {
+                    self.remember_export_directory(exported_directory);
+                }
+
+                if let Some(export_failure) = export_failure {
+                    state.upstream_actions.push(UpstreamAction::OpenDialog(
+                        DialogType::Warning,
+                        export_failure,
+                    ));
+                }
             }
         }
 
@@ -189,14 +513,88 @@ impl Windowable<FileExportMessage> for FileExport {
 
     fn content_perform(&mut self, _state: &mut SharedAppState, action: ContentAction) {
         if self.filepicker_content_is_active {
-            match self.export_strategy {
-                FileExportStrategy::SingleDay => self
+            match self.export_scope {
+                FileExportScope::SingleDay => self
                     .individial_file_picker
                     .update(FilePickerMessage::FilepathEdit(action)),
-                FileExportStrategy::AllSingle => self
+                FileExportScope::AllSingle => self
                     .bulk_directory_picker
                     .update(FilePickerMessage::FilepathEdit(action)),
+                FileExportScope::MarkdownBundle => self
+                    .bundle_file_picker
+                    .update(FilePickerMessage::FilepathEdit(action)),
             }
         }
     }
 }
+
+impl FileExport {
+    /// sets the directory every file dialog of the export window opens in
+    fn set_starting_directory(&mut self, starting_directory: Option<PathBuf>) {
+        self.individial_file_picker
+            .set_starting_directory(starting_directory.clone());
+        self.bulk_directory_picker
+            .set_starting_directory(starting_directory.clone());
+        self.bundle_file_picker
+            .set_starting_directory(starting_directory);
+    }
+
+    /// makes the directory the default export directory, so the next export starts where this one went
+    fn remember_export_directory(&mut self, exported_directory: PathBuf) {
+        self.set_starting_directory(Some(exported_directory.clone()));
+
+        preferences_mut().export.default_directory = Some(exported_directory);
+        preferences().write_to_disk();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::journal_pointer;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    /// a fresh directory for the export files of a single test
+    fn export_dir(dir_name: &str) -> PathBuf {
+        let export_dir = journal_pointer::test_dir().join("exports").join(dir_name);
+        let _ = fs::remove_dir_all(&export_dir);
+        fs::create_dir_all(&export_dir).expect("couldn't create export dir");
+
+        export_dir
+    }
+
+    #[test]
+    fn appending_to_a_missing_file_exports_the_entry_alone() {
+        let export_path = export_dir("append-missing").join("journal.txt");
+
+        let export_text = appended_export(&export_path, date(2024, 5, 1), "the entry")
+            .expect("a missing file should be treated as empty");
+
+        assert_eq!(export_text, "the entry");
+    }
+
+    #[test]
+    fn appending_adds_the_entry_after_the_existing_text() {
+        let export_path = export_dir("append-existing").join("journal.txt");
+        fs::write(&export_path, "earlier entry\n\n").expect("couldn't write export file");
+
+        let export_text = appended_export(&export_path, date(2024, 5, 1), "the entry")
+            .expect("couldn't append to the export file");
+
+        assert_eq!(
+            export_text,
+            "earlier entry\n\n--- Wednesday, May 01, 2024 ---\n\nthe entry"
+        );
+    }
+
+    #[test]
+    fn unreadable_export_files_are_not_overwritten() {
+        // a directory can't be read as text, but unlike a missing file it must not be treated as empty
+        let export_path = export_dir("append-unreadable");
+
+        assert!(appended_export(&export_path, date(2024, 5, 1), "the entry").is_err());
+    }
+}
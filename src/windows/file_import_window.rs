@@ -1,14 +1,21 @@
 use super::window_manager::{WindowType, Windowable};
 use crate::{
     SharedAppState, UpstreamAction,
-    content::{ContentAction, UpgradedContent},
+    content::ContentAction,
     custom_widgets::file_picker::{FilePicker, FilePickerMessage},
+    dialogs::{ConfirmedAction, DialogType},
     keyboard_manager::KeyboardAction,
-    utils::file_extensions::{self, TEXT_EXT_LIST},
+    utils::{
+        file_extensions::{self, TEXT_EXT_LIST},
+        logbox::logbox_mut,
+    },
 };
 use iced::{
     Task,
-    widget::{Text, button, column, radio, row},
+    widget::{
+        Text, button, column, radio, row,
+        text_editor::{Action, Edit},
+    },
 };
 use std::{fs, path::PathBuf};
 
@@ -109,6 +116,15 @@ impl Windowable<FileImportMessage> for FileImport {
                     }
                     KeyboardAction::Save => {}
                     KeyboardAction::Debug => {}
+                    KeyboardAction::OpenPreferences => {}
+                    KeyboardAction::NewTask => {}
+                    KeyboardAction::SearchCursorWord => {}
+                    KeyboardAction::Paste => {}
+                    KeyboardAction::RevertEntry => {}
+                    KeyboardAction::JumpToDate => {}
+                    KeyboardAction::SwitchFocusRegion => {}
+                    KeyboardAction::ToggleFocusMode => {}
+                    KeyboardAction::TaskNavigation(_task_navigation) => {}
                     KeyboardAction::Unbound(_unbound_key) => {}
                 };
             }
@@ -133,16 +149,41 @@ impl Windowable<FileImportMessage> for FileImport {
             FileImportMessage::Import(strategy) => {
                 self.filepicker_content_is_active = false;
 
-                if let Ok(imported_string) = fs::read_to_string(self.filepicker.path()) {
-                    let new_text = match strategy {
-                        FileImportStrategy::AppendEnd => state.content.text() + &imported_string,
-                        FileImportStrategy::AppendStart => imported_string + &state.content.text(),
-                        FileImportStrategy::Overwrite => imported_string,
-                    };
+                let import_path = self.filepicker.path();
 
-                    state.content = UpgradedContent::with_text(&new_text);
-                } else {
-                    //TODO: unable to import
+                match fs::read(&import_path) {
+                    Ok(imported_bytes) => match String::from_utf8(imported_bytes) {
+                        Ok(imported_string) => {
+                            import_text(state, imported_string, strategy);
+                        }
+                        Err(utf8_error) => {
+                            // the file is likely in another encoding. rather than refusing it outright, offer to
+                            // import it with the invalid characters replaced
+                            let lossy_string =
+                                String::from_utf8_lossy(utf8_error.as_bytes()).into_owned();
+
+                            let dialog_text = format!(
+                                "{} isn't valid UTF-8 text. Import it anyway, replacing any unreadable characters?",
+                                import_path.display()
+                            );
+
+                            state.upstream_actions.push(UpstreamAction::OpenDialog(
+                                DialogType::Confirmation(ConfirmedAction::ImportText(
+                                    lossy_string,
+                                    strategy,
+                                )),
+                                dialog_text,
+                            ));
+                        }
+                    },
+                    Err(io_error) => {
+                        let dialog_text =
+                            format!("Couldn't import {}: {}", import_path.display(), io_error);
+
+                        state
+                            .upstream_actions
+                            .push(UpstreamAction::OpenDialog(DialogType::Warning, dialog_text));
+                    }
                 }
 
                 state
@@ -161,3 +202,154 @@ impl Windowable<FileImportMessage> for FileImport {
         }
     }
 }
+
+/// places the imported text into the active entry according to the import strategy. locked entries are left as they
+/// are. the text is replaced through the editor, so the import is a single step on the undo stack
+pub fn import_text(
+    state: &mut SharedAppState,
+    imported_string: String,
+    strategy: FileImportStrategy,
+) {
+    if state.global_store.day().locked() {
+        logbox_mut().log("The entry is locked and wasn't imported into");
+
+        return;
+    }
+
+    let new_text = match strategy {
+        FileImportStrategy::AppendEnd => state.content.text() + &imported_string,
+        FileImportStrategy::AppendStart => imported_string + &state.content.text(),
+        FileImportStrategy::Overwrite => imported_string,
+    };
+
+    state
+        .content
+        .perform(ContentAction::Standard(Action::SelectAll));
+    state
+        .content
+        .perform(ContentAction::Standard(Action::Edit(Edit::Paste(
+            new_text.into(),
+        ))));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{journal_pointer, with_test_preferences};
+    use crate::store::journal_storage::MemoryStorage;
+
+    /// writes the bytes to a file in the test directory and imports it into a fresh journal, overwriting the entry
+    fn import_bytes(filename: &str, bytes: &[u8]) -> SharedAppState {
+        let import_dir = journal_pointer::test_dir().join("imports");
+        fs::create_dir_all(&import_dir).expect("couldn't create import dir");
+
+        let import_path = import_dir.join(filename);
+        fs::write(&import_path, bytes).expect("couldn't write import file");
+
+        let mut state = SharedAppState::with_storage(Box::new(MemoryStorage::default()));
+
+        let mut file_import = FileImport {
+            filepicker: FilePicker::file(import_path, &[]),
+            ..FileImport::default()
+        };
+
+        let _task = file_import.update(
+            &mut state,
+            FileImportMessage::Import(FileImportStrategy::Overwrite),
+        );
+
+        state
+    }
+
+    #[test]
+    fn utf8_files_are_imported_directly() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let state = import_bytes("utf8.txt", "café".as_bytes());
+
+                assert_eq!(state.content.text().trim_end(), "café");
+                assert!(
+                    !state
+                        .upstream_actions
+                        .iter()
+                        .any(|upstream_action| matches!(
+                            upstream_action,
+                            UpstreamAction::OpenDialog(..)
+                        ))
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn imports_are_a_single_undo_step() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let mut state = SharedAppState::with_storage(Box::new(MemoryStorage::default()));
+
+                import_text(
+                    &mut state,
+                    "imported".to_string(),
+                    FileImportStrategy::AppendEnd,
+                );
+
+                assert_eq!(state.content.text().trim_end(), "imported");
+                assert_eq!(state.content.undo_stack_height(), 1);
+            },
+        );
+    }
+
+    #[test]
+    fn locked_entries_are_not_imported_into() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let mut state = SharedAppState::with_storage(Box::new(MemoryStorage::default()));
+                state.global_store.day_mut().set_locked(true);
+
+                import_text(
+                    &mut state,
+                    "imported".to_string(),
+                    FileImportStrategy::Overwrite,
+                );
+
+                assert!(state.content.text().trim().is_empty());
+                assert_eq!(state.content.undo_stack_height(), 0);
+            },
+        );
+    }
+
+    #[test]
+    fn latin1_files_offer_a_lossy_import() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                // "café" in Latin-1, where the é is the single byte 0xE9
+                let state = import_bytes("latin1.txt", b"caf\xe9");
+
+                assert!(state.content.text().trim().is_empty());
+
+                let lossy_import =
+                    state.upstream_actions.iter().find_map(
+                        |upstream_action| match upstream_action {
+                            UpstreamAction::OpenDialog(
+                                DialogType::Confirmation(ConfirmedAction::ImportText(
+                                    lossy_text,
+                                    strategy,
+                                )),
+                                _dialog_text,
+                            ) => Some((lossy_text.clone(), *strategy)),
+                            _ => None,
+                        },
+                    );
+
+                assert_eq!(
+                    lossy_import,
+                    Some(("caf\u{FFFD}".to_string(), FileImportStrategy::Overwrite))
+                );
+            },
+        );
+    }
+}
@@ -1,9 +1,9 @@
-use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate};
+use chrono::{DateTime, Datelike, Days, Local, Months, NaiveDate, Weekday};
 use iced::Length::Fill;
 use iced::font;
-use iced::widget::operation::snap_to;
+use iced::widget::operation::{focus, scroll_to, snap_to};
 use iced::widget::scrollable::{AbsoluteOffset, RelativeOffset, Viewport};
-use iced::widget::text_editor::Action;
+use iced::widget::text_editor::{Action, Position};
 use iced::widget::{Id, Space, Text, opaque, stack, tooltip};
 use iced::window;
 use iced::{
@@ -18,12 +18,18 @@ use iced::{
         text_editor::{self},
     },
 };
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::fs;
 use strum::Display;
 
+use super::diagnostics_window::Diagnostics;
+use super::file_import_window;
 use super::window_manager::{WindowType, Windowable};
 
-use crate::config::{preferences, preferences_mut};
-use crate::content::{ContentAction, UpgradedContent};
+use crate::config::{EditorAlignment, SearchEnterAction, preferences, preferences_mut};
+use crate::content::history_stack::HistoryStack;
+use crate::content::{ContentAction, Restriction, UpgradedContent};
 use crate::custom_widgets::calender::{Calender, CalenderColormap, CalenderMessage};
 use crate::custom_widgets::context_menu::{
     ContextMenuElement, ContextMenuItem, build_context_menu,
@@ -32,26 +38,31 @@ use crate::custom_widgets::menu_bar::{MenuBar, build_full_menu_bar};
 use crate::custom_widgets::menu_bar_builder::{
     EditMessage, FileMessage, MenuMessage, ToolsMessage, build_menu_bar,
 };
+use crate::custom_widgets::month_strip::{MonthStrip, MonthStripMessage};
 use crate::custom_widgets::search_table::{SearchTable, SearchTableMessage};
 use crate::custom_widgets::tabview::{TabviewItem, tabview_content_vertical};
-use crate::dialogs::DialogType;
-use crate::keyboard_manager::{KeyboardAction, TextEdit, UnboundKey};
+use crate::dialogs::{ConfirmedAction, DialogType};
+use crate::keyboard_manager::{KeyboardAction, TaskNavigation, TextEdit, UnboundKey};
 use crate::md_image::markdown_image::{self, ImageCache, ParsedMarkdown};
-use crate::store::{TimedWordCount, WordCount};
+use crate::store::{Draft, TimedWordCount, WordCount, journal_cipher};
 use crate::tasks::task_manager::TaskMessage;
 use crate::tasks::template_tasks::TemplateData;
-use crate::tasks::{StandardMessage, TaskId};
+use crate::tasks::{Agenda, AgendaItem, StandardMessage, TaskId};
+use crate::ui::button_themes::standard_button_style;
 use crate::ui::highlighter::{self, HighlightSettings, SpellHighlighter};
-use crate::ui::journal_theme::LIGHT;
+use crate::ui::journal_theme::{self, preferred_journal_theme};
 use crate::ui::layout::{
     CONTEXT_MENU_HEIGHT, DASHBOARD_TAB_CONTENT_HEIGHT, DASHBOARD_WIDTH, EDITOR_WIDTH,
     LOGBOX_HEIGHT, SCROLLBAR_WIDTH,
 };
 use crate::ui::styling::{TOOLTIP_DELAY, TOOLTIP_SIZE};
 use crate::ui::ui_tools;
-use crate::utils::clipboard::{read_clipboard, write_clipboard};
+#[cfg(target_os = "linux")]
+use crate::utils::clipboard::read_primary_selection;
+use crate::utils::clipboard::{read_clipboard, read_clipboard_image, write_clipboard};
 use crate::utils::dictionary::{self, DICTIONARY};
 use crate::utils::logbox::{logbox, logbox_mut};
+use crate::utils::misc_tools::{self, LONG_DATE_FORMAT};
 use crate::{SharedAppState, UpstreamAction};
 
 #[derive(Debug, Default, Clone, PartialEq, Display)]
@@ -60,6 +71,7 @@ pub enum Tab {
     Tasks,
     Search,
     Stats,
+    Agenda,
 }
 
 impl Tab {
@@ -68,6 +80,7 @@ impl Tab {
             Tab::Tasks => 0,
             Tab::Search => 1,
             Tab::Stats => 2,
+            Tab::Agenda => 3,
         }
     }
 }
@@ -90,22 +103,57 @@ pub enum EditorMode {
 pub enum ActiveContent {
     Editor,
     Search,
+    Replace,
+    WordFilter,
+    Tag,
+    TagFilter,
+    JumpDate,
     /// the TemplateTaskMessage stores which task has the editor, so we don't need to store anything else
     Task(TaskId),
 }
 
+#[derive(Debug, Default, PartialEq)]
+/// which part of the window the keyboard is driving
+pub enum FocusRegion {
+    #[default]
+    Editor,
+
+    /// the keys move through and check off the tasks of the current day instead of going to the editor
+    Tasks,
+}
+
 #[derive(Debug)]
 pub struct Main {
     title: String,
     active_content: Option<ActiveContent>,
+    focus_region: FocusRegion,
+    focused_task_index: usize,
     search_content: UpgradedContent,
     search_text: String,
+    search_history: Vec<String>,
+    search_result_index: Option<usize>,
+    replace_content: UpgradedContent,
+    tag_filter_content: UpgradedContent,
+    tag_content: UpgradedContent,
+    jump_date_content: UpgradedContent,
+    word_filter_content: UpgradedContent,
+    word_filter_threshold: Option<usize>,
     calender: Calender,
+    month_strip: MonthStrip,
     search_table: SearchTable,
     current_tab: Tab,
     selected_misspelled_word: Option<String>,
     spell_suggestions: Vec<String>,
     last_edit_time: DateTime<Local>,
+    unrecorded_writing_millis: i64,
+    last_draft_time: DateTime<Local>,
+    draft_text: String,
+
+    /// true if the last draft couldn't be written. the error is only logged once, rather than every time the draft is
+    /// retried
+    draft_write_failed: bool,
+    sentence_save_pending: bool,
+    carried_over_text: Option<String>,
     show_context_menu: bool,
     mouse_position: Point,
     captured_mouse_position: Point,
@@ -117,6 +165,20 @@ pub struct Main {
     editor_mode: EditorMode,
     editor_markdown: Vec<ParsedMarkdown>,
     markdown_image_cache: ImageCache,
+    history_cache: VecDeque<CachedHistory>,
+
+    /// the agenda of the current day. it is only recomputed while the agenda tab is open, when the date or the tasks
+    /// change
+    agenda: Agenda,
+}
+
+#[derive(Debug)]
+/// the undo history of a recently visited day, along with the text of the day when it was left. the history is only
+/// valid while the text of the day hasn't been changed by anything else
+struct CachedHistory {
+    date: NaiveDate,
+    text: String,
+    history_stack: HistoryStack,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -133,29 +195,51 @@ pub enum MainMessage {
     BackOneDay,
     ForwardOneDay,
     JumpToToday,
+    LoadToday,
+    EditJumpDate(Action),
+    JumpToDate,
     Edit(text_editor::Action),
     EditSearch(text_editor::Action),
     SwitchEditorMode(EditorMode),
     Markdown,
     Calender(CalenderMessage),
+    MonthStrip(MonthStripMessage),
     TableSearch(SearchTableMessage),
     TabSwitched(Tab),
     AcceptSpellcheck(usize),
     AddToDictionary(String),
+    InsertSnippet(String),
     ClearSearch,
+    SearchHistorySelected(String),
     ToggleSearchCase,
     MouseMoved(Point),
     WindowMouseMoved(Point),
     RightClickEditArea,
+    #[cfg(target_os = "linux")]
+    MiddleClickEditArea,
     ExitContextMenu,
     MenuBar(MenuMessage),
     EditorScrolled(Viewport),
     AddTask,
     TaskAction(TaskMessage),
     Autosave,
+    AppendToToday(String),
+    RevertEntry,
+    PerformConfirmed(ConfirmedAction),
+    ToggleDayLock,
+    EditWordFilter(Action),
+    ResetWordFilter,
+    EditReplace(Action),
+    ReplaceNext,
+    ReplaceAll,
+    EditTagFilter(Action),
+    EditTag(Action),
+    AddTag,
+    RemoveTag(String),
 }
 
 const LOG_EDIT_AREA_ID: &str = "log_edit_area";
+const JUMP_DATE_ID: &str = "jump_date";
 
 impl Windowable<MainMessage> for Main {
     fn title(&self) -> String {
@@ -184,6 +268,20 @@ impl Windowable<MainMessage> for Main {
 
         let daily_nav_bar = row![back_button, today_button, forward_button].width(DASHBOARD_WIDTH);
 
+        let jump_date_editor = widget::text_editor(self.jump_date_content.raw_content())
+            .id(Id::new(JUMP_DATE_ID))
+            .placeholder("YYYY-MM-DD")
+            .on_action(MainMessage::EditJumpDate)
+            .size(14)
+            .wrapping(Wrapping::None);
+        let jump_date_button = widget::button(widget::Text::new("Go").size(14).center())
+            .on_press(MainMessage::JumpToDate)
+            .width(50);
+
+        let jump_date_bar = row![jump_date_editor, jump_date_button].width(DASHBOARD_WIDTH);
+
+        let month_strip = self.month_strip.view().map(MainMessage::MonthStrip);
+
         let calender = self.calender.build_calender().map(MainMessage::Calender);
 
         let (tasks_tab_content, tasks_tab_overlay) = {
@@ -193,7 +291,10 @@ impl Windowable<MainMessage> for Main {
             let content = column![
                 state
                     .task_manager
-                    .build_tasks(state.global_store.current_date())
+                    .build_tasks(
+                        state.global_store.current_date(),
+                        self.focused_task_id(state)
+                    )
                     .map(MainMessage::TaskAction),
                 Space::new().height(ADD_BUTTON_WIDTH + ADD_MARGIN * 2.0)
             ];
@@ -274,10 +375,55 @@ impl Windowable<MainMessage> for Main {
             ]
             .width(DASHBOARD_WIDTH);
 
-            let search_results =
-                SearchTable::view(&self.search_table).map(MainMessage::TableSearch);
+            let replace_bar = widget::text_editor(self.replace_content.raw_content())
+                .placeholder("Replace with...")
+                .on_action(MainMessage::EditReplace)
+                .height(26);
+            let replace_button = widget::button(Text::new("Replace").size(12).center())
+                .on_press(MainMessage::ReplaceNext)
+                .height(26);
+            let replace_all_button = widget::button(Text::new("All").size(12).center())
+                .on_press(MainMessage::ReplaceAll)
+                .height(26);
+
+            let replace_line = row![
+                replace_bar,
+                replace_button,
+                replace_all_button,
+                Space::new().width(SCROLLBAR_WIDTH)
+            ]
+            .width(DASHBOARD_WIDTH);
+
+            let tag_filter_bar = widget::text_editor(self.tag_filter_content.raw_content())
+                .placeholder("Filter by tag...")
+                .on_action(MainMessage::EditTagFilter)
+                .height(26);
+
+            let tag_filter_line =
+                row![tag_filter_bar, Space::new().width(SCROLLBAR_WIDTH)].width(DASHBOARD_WIDTH);
+
+            let show_search_history = self.active_content == Some(ActiveContent::Search)
+                && self.search_content.text().trim().is_empty()
+                && !self.search_history.is_empty();
+
+            let search_results = if show_search_history {
+                let history_entries = self.search_history.iter().map(|past_search| {
+                    widget::button(Text::new(past_search.clone()).size(12))
+                        .on_press(MainMessage::SearchHistorySelected(past_search.clone()))
+                        .width(DASHBOARD_WIDTH - SCROLLBAR_WIDTH)
+                        .into()
+                });
+
+                column![
+                    Text::new("Recent searches").size(12),
+                    widget::Column::with_children(history_entries)
+                ]
+                .into()
+            } else {
+                SearchTable::view(&self.search_table).map(MainMessage::TableSearch)
+            };
 
-            column![search_line, search_results]
+            column![search_line, replace_line, tag_filter_line, search_results]
         };
 
         let search_tab = TabviewItem {
@@ -297,30 +443,187 @@ impl Windowable<MainMessage> for Main {
             let twc = state.global_store.total_word_count().to_string();
             let tcc = state.global_store.total_char_count().to_string();
 
-            let maw = format!("{:.2}", state.global_store.month().average_words());
-            let taw = format!("{:.2}", state.global_store.average_words());
-            let mac = format!("{:.2}", state.global_store.month().average_chars());
-            let tac = format!("{:.2}", state.global_store.average_chars());
+            // an average over no entries would be meaningless, so a dash is shown instead of a 0 or NaN
+            let format_average = |average: f64, edited_day_count: usize| {
+                if edited_day_count == 0 || !average.is_finite() {
+                    "—".to_string()
+                } else {
+                    format!("{:.2}", average)
+                }
+            };
+
+            let month_edited_days = state.global_store.month().edited_day_count();
+            let total_edited_days = state.global_store.edited_day_count();
+
+            let maw = format_average(
+                state.global_store.month().average_words(),
+                month_edited_days,
+            );
+            let taw = format_average(state.global_store.average_words(), total_edited_days);
+            let mac = format_average(
+                state.global_store.month().average_chars(),
+                month_edited_days,
+            );
+            let tac = format_average(state.global_store.average_chars(), total_edited_days);
+
+            // makes it clear what the averages are taken over, since it depends on the stats preferences
+            let average_label = if preferences().stats.include_empty_days {
+                "per day"
+            } else {
+                "per entry"
+            };
+
+            let weekday_lines = state
+                .global_store
+                .weekday_breakdown()
+                .iter()
+                .enumerate()
+                .map(|(weekday_index, (entry_count, word_count))| {
+                    let weekday = Weekday::try_from(weekday_index as u8)
+                        .expect("couldn't convert weekday index");
+
+                    let average_words =
+                        format_average(*word_count as f64 / *entry_count as f64, *entry_count);
+
+                    widget::Text::new(format!(
+                        "     {}: {} entries, {} avg words",
+                        weekday, entry_count, average_words
+                    ))
+                    .into()
+                })
+                .collect::<Vec<Element<MainMessage>>>();
+
+            let current_date = state.global_store.current_date();
+            let month_start = current_date.with_day(1).expect("couldn't get month start");
+            let month_end = month_start
+                .checked_add_months(Months::new(1))
+                .and_then(|next_month| next_month.pred_opt())
+                .expect("couldn't get month end");
+
+            let counter_totals = state
+                .task_manager
+                .template_tasks
+                .counter_totals(month_start, month_end)
+                .into_iter()
+                .map(|(counter_name, total)| {
+                    widget::Text::new(format!("     {counter_name} Total: {total}")).into()
+                })
+                .collect::<Vec<Element<MainMessage>>>();
+
+            // each goal is only shown once it's configured, as a bar filling up towards the goal that turns green with a
+            // checkmark once the goal is met
+            let goal_progress = |label: &str, count: usize, goal: Option<usize>| {
+                goal.map(|goal| {
+                    let goal_met = count >= goal;
+
+                    let progress_bar = widget::progress_bar(0.0..=goal as f32, count as f32)
+                        .girth(8)
+                        .length(200)
+                        .style(if goal_met {
+                            widget::progress_bar::success
+                        } else {
+                            widget::progress_bar::primary
+                        });
+
+                    let progress_text = format!(
+                        "     {} Goal: {} / {} {}",
+                        label,
+                        count,
+                        goal,
+                        if goal_met { "✓" } else { "" }
+                    );
+
+                    column![widget::Text::new(progress_text), progress_bar].into()
+                })
+            };
+
+            let goals = [
+                goal_progress(
+                    "Word",
+                    state.global_store.day().total_word_count(),
+                    preferences().stats.daily_word_goal,
+                ),
+                goal_progress(
+                    "Character",
+                    state.global_store.day().total_char_count(),
+                    preferences().stats.daily_char_goal,
+                ),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<Element<MainMessage>>>();
+
+            let format_writing_time = |writing_seconds: u64| {
+                format!(
+                    "{}:{:02}",
+                    writing_seconds / 3600,
+                    writing_seconds / 60 % 60
+                )
+            };
+
+            let writing_time = format_writing_time(state.global_store.day().writing_seconds());
+            let month_writing_time =
+                format_writing_time(state.global_store.month().writing_seconds());
+            let total_writing_time = format_writing_time(state.global_store.writing_seconds());
 
             let longest_streak = format!("{}", state.global_store.longest_streak());
             let current_streak = format!("{}", state.global_store.current_streak());
+            let goal_streak = preferences()
+                .stats
+                .daily_word_goal
+                .map(|word_goal| {
+                    let goal_streak = state.global_store.goal_streak(word_goal);
+
+                    widget::Text::new(format!("     Word Goal Streak: {goal_streak} days")).into()
+                })
+                .into_iter()
+                .collect::<Vec<Element<MainMessage>>>();
+
+            let word_filter_editor = widget::text_editor(self.word_filter_content.raw_content())
+                .placeholder("0")
+                .on_action(MainMessage::EditWordFilter)
+                .size(13)
+                .width(60);
+
+            let reset_filter_button = widget::button(widget::Text::new("Reset").size(13))
+                .on_press_maybe(
+                    self.word_filter_threshold
+                        .is_some()
+                        .then_some(MainMessage::ResetWordFilter),
+                );
+
+            let word_filter = row![
+                widget::Text::new("Highlight days with ").size(13),
+                word_filter_editor,
+                widget::Text::new(" + words ").size(13),
+                reset_filter_button
+            ];
 
             column![
+                word_filter,
                 widget::Text::new("Current Day"),
                 widget::Text::new("     Words:      ".to_string() + &dwc),
                 widget::Text::new("     Characters: ".to_string() + &dcc),
+                widget::Text::new("     Time Writing: ".to_string() + &writing_time),
+                widget::Column::with_children(goals),
                 widget::Text::new("This Month"),
                 widget::Text::new("     Words:      ".to_string() + &mwc),
                 widget::Text::new("     Characters: ".to_string() + &mcc),
-                widget::Text::new("     Average Words: ".to_string() + &maw),
-                widget::Text::new("     Average Chars: ".to_string() + &mac),
+                widget::Text::new(format!("     Average Words ({}): {}", average_label, maw)),
+                widget::Text::new(format!("     Average Chars ({}): {}", average_label, mac)),
+                widget::Text::new("     Time Writing: ".to_string() + &month_writing_time),
+                widget::Column::with_children(counter_totals),
                 widget::Text::new("Total"),
                 widget::Text::new("     Words:      ".to_string() + &twc),
                 widget::Text::new("     Characters: ".to_string() + &tcc),
-                widget::Text::new("     Average Words: ".to_string() + &taw),
-                widget::Text::new("     Average Chars: ".to_string() + &tac),
+                widget::Text::new(format!("     Average Words ({}): {}", average_label, taw)),
+                widget::Text::new(format!("     Average Chars ({}): {}", average_label, tac)),
+                widget::Text::new("     Time Writing: ".to_string() + &total_writing_time),
                 widget::Text::new("     Current Streak: ".to_string() + &current_streak + " days"),
                 widget::Text::new("     Longest Streak: ".to_string() + &longest_streak + " days"),
+                widget::Column::with_children(goal_streak),
+                widget::Text::new("By Weekday"),
+                widget::Column::with_children(weekday_lines),
             ]
         };
 
@@ -331,7 +634,67 @@ impl Windowable<MainMessage> for Main {
             overlay: None,
         };
 
-        let tab_elements = vec![tasks_tab, search_tab, stats_tab];
+        let agenda_tab_content = {
+            let agenda = &self.agenda;
+
+            let agenda_line =
+                |agenda_item: &AgendaItem, show_date: bool| -> Element<'static, MainMessage> {
+                    let checkmark = if agenda_item.completed() {
+                        "[x]"
+                    } else {
+                        "[ ]"
+                    };
+
+                    let line = if show_date {
+                        format!(
+                            "     {} {} ({})",
+                            checkmark,
+                            agenda_item.name(),
+                            agenda_item.date()
+                        )
+                    } else {
+                        format!("     {} {}", checkmark, agenda_item.name())
+                    };
+
+                    widget::Text::new(line).into()
+                };
+
+            let scheduled_lines = if agenda.scheduled.is_empty() {
+                vec![widget::Text::new("     Nothing scheduled").into()]
+            } else {
+                agenda
+                    .scheduled
+                    .iter()
+                    .map(|agenda_item| agenda_line(agenda_item, false))
+                    .collect::<Vec<Element<MainMessage>>>()
+            };
+
+            let overdue_lines = if agenda.overdue.is_empty() {
+                vec![widget::Text::new("     Nothing overdue").into()]
+            } else {
+                agenda
+                    .overdue
+                    .iter()
+                    .map(|agenda_item| agenda_line(agenda_item, true))
+                    .collect::<Vec<Element<MainMessage>>>()
+            };
+
+            column![
+                widget::Text::new("Scheduled"),
+                widget::Column::with_children(scheduled_lines),
+                widget::Text::new("Overdue"),
+                widget::Column::with_children(overdue_lines),
+            ]
+        };
+
+        let agenda_tab = TabviewItem {
+            title: Tab::Agenda.to_string(),
+            clicked_message: MainMessage::TabSwitched(Tab::Agenda),
+            content: agenda_tab_content.into(),
+            overlay: None,
+        };
+
+        let tab_elements = vec![tasks_tab, search_tab, stats_tab, agenda_tab];
 
         let tab_view = tabview_content_vertical(
             tab_elements,
@@ -340,7 +703,13 @@ impl Windowable<MainMessage> for Main {
             Length::Fill,
         );
 
-        let left_ui = column![daily_nav_bar, calender, tab_view];
+        let left_ui = column![
+            daily_nav_bar,
+            jump_date_bar,
+            month_strip,
+            calender,
+            tab_view
+        ];
 
         let right_top_bar = row![
             widget::button("Edit Mode")
@@ -355,28 +724,69 @@ impl Windowable<MainMessage> for Main {
         ];
 
         let editor_area = {
+            const SPELLCHECK_NEARBY_LINES: usize = 40;
+
+            let show_line_numbers = preferences().editor.show_line_numbers;
+
+            let spellcheck_length_limit = preferences().editor.spellcheck_length_limit;
+            let spellcheck_nearby_lines = (spellcheck_length_limit != 0
+                && state.content.text().chars().count() > spellcheck_length_limit)
+                .then_some(SPELLCHECK_NEARBY_LINES);
+
+            // alignment only moves where the lines are drawn. the cursor and the highlighter both work on the text
+            // itself, so neither is affected by it
+            let text_alignment = match preferences().editor.text_alignment {
+                EditorAlignment::Left => widget::text::Alignment::Left,
+                EditorAlignment::Center => widget::text::Alignment::Center,
+                EditorAlignment::Justified => widget::text::Alignment::Justified,
+            };
+
             let log_text_input = widget::text_editor(state.content.raw_content())
                 .placeholder("Type today's log...")
                 .on_action(MainMessage::Edit)
                 .size(13)
                 .font(Font::DEFAULT)
-                .wrapping(Wrapping::WordOrGlyph)
+                .align_x(text_alignment)
+                .wrapping(if show_line_numbers {
+                    Wrapping::None
+                } else {
+                    Wrapping::WordOrGlyph
+                })
                 .height(Length::Shrink)
                 .highlight_with::<SpellHighlighter>(
                     HighlightSettings {
                         cursor_line_idx,
                         cursor_char_idx,
                         cursor_spellcheck_timed_out,
+                        spellcheck_nearby_lines,
                         search_text: self.search_text.clone(),
                         ignore_search_case: preferences().search.ignore_search_case,
                     },
                     highlighter::highlight_to_format,
                 );
 
+            // the gutter lives in the same scrollable as the editor, so it always scrolls in sync with the editor. with
+            // wrapping disabled, every line of the content is exactly one line of text tall
+            let line_number_gutter = show_line_numbers.then(|| {
+                let line_numbers = (1..=state.content.line_count())
+                    .map(|line_number| {
+                        Text::new(line_number.to_string())
+                            .size(13)
+                            .style(journal_theme::dimmed_text_style)
+                            .into()
+                    })
+                    .collect::<Vec<Element<MainMessage>>>();
+
+                widget::Column::with_children(line_numbers)
+                    .align_x(iced::Alignment::End)
+                    .padding([5, 4])
+            });
+
             match self.editor_mode {
-                EditorMode::Editor => row![log_text_input],
+                EditorMode::Editor => row![line_number_gutter, log_text_input],
                 EditorMode::SplitView => {
-                    let half_editor = log_text_input.width(EDITOR_WIDTH / 2.0);
+                    let half_editor = row![line_number_gutter, log_text_input.width(Length::Fill)]
+                        .width(EDITOR_WIDTH / 2.0);
                     let half_viewer = markdown_image::build_markdown(
                         &self.editor_markdown,
                         &self.markdown_image_cache,
@@ -491,6 +901,24 @@ impl Windowable<MainMessage> for Main {
         context_menu_items.push(ContextMenuItem::Button(undo));
         context_menu_items.push(ContextMenuItem::Button(redo));
 
+        let snippets = preferences()
+            .editor
+            .snippets
+            .keys()
+            .map(|snippet_name| {
+                ContextMenuElement::new(
+                    snippet_name,
+                    Some(MainMessage::InsertSnippet(snippet_name.clone())),
+                )
+            })
+            .collect::<Vec<ContextMenuElement<MainMessage>>>();
+
+        if !snippets.is_empty() {
+            context_menu_items.push(ContextMenuItem::Break);
+            context_menu_items.push(ContextMenuItem::Text("Insert snippet:".to_string()));
+            context_menu_items.push(ContextMenuItem::Scroller((snippets, 4)));
+        }
+
         let mut context_menu_position = self.captured_mouse_position;
         context_menu_position.y += self.editor_scroll_offset.y;
 
@@ -548,9 +976,21 @@ impl Windowable<MainMessage> for Main {
             .on_right_release(MainMessage::RightClickEditArea)
             .on_move(MainMessage::MouseMoved);
 
-        let right_ui = column![right_top_bar, mouse_editor_area];
+        // middle click pasting is native behavior on Linux, but would be unexpected anywhere else
+        #[cfg(target_os = "linux")]
+        let mouse_editor_area =
+            mouse_editor_area.on_middle_release(MainMessage::MiddleClickEditArea);
+
+        // focus mode only leaves the editor showing. everything hidden keeps its state, so it comes back as it was
+        let focus_mode = preferences().general.focus_mode;
+
+        let right_ui: Element<'a, MainMessage> = if focus_mode {
+            mouse_editor_area.into()
+        } else {
+            column![right_top_bar, mouse_editor_area].into()
+        };
 
-        let top_ui = row![left_ui, right_ui];
+        let top_ui = row![(!focus_mode).then_some(left_ui), right_ui];
 
         let (logbox_text, logbox_log_time) = logbox().get_log_at_time();
 
@@ -573,9 +1013,102 @@ impl Windowable<MainMessage> for Main {
         ))
         .size(14);
 
-        let bottom_ui = row![logbox, Space::new().width(Fill), cursor_position_box];
+        // the word being typed isn't spellchecked until the editing pauses, so the status shows that the check is still
+        // pending rather than leaving a brief gap where misspellings aren't highlighted unexplained
+        let spellcheck_status = if dictionary::dictionary_loaded() {
+            (!cursor_spellcheck_timed_out).then(|| {
+                widget::text("Checking spelling...")
+                    .size(14)
+                    .style(journal_theme::dimmed_text_style)
+            })
+        } else {
+            Some(
+                widget::text("Spellcheck unavailable - set dictionary paths in Preferences")
+                    .size(14)
+                    .style(journal_theme::dimmed_text_style),
+            )
+        };
+
+        let day_locked = state.global_store.day().locked();
+
+        let lock_status = day_locked.then(|| {
+            widget::text("Entry locked")
+                .size(14)
+                .style(journal_theme::dimmed_text_style)
+        });
+
+        let lock_button =
+            widget::button(widget::text(if day_locked { "Unlock" } else { "Lock" }).size(14))
+                .on_press(MainMessage::ToggleDayLock)
+                .padding([0, 5])
+                .style(standard_button_style);
+
+        let tag_buttons = state.global_store.day().tags().iter().map(|tag| {
+            let tag_button = widget::button(widget::text(tag.clone()).size(14))
+                .on_press_maybe((!day_locked).then(|| MainMessage::RemoveTag(tag.clone())))
+                .padding([0, 5])
+                .style(standard_button_style);
+
+            tooltip(
+                tag_button,
+                Text::new("Remove Tag").size(TOOLTIP_SIZE),
+                tooltip::Position::Top,
+            )
+            .delay(TOOLTIP_DELAY)
+            .into()
+        });
+
+        let tag_editor = widget::text_editor(self.tag_content.raw_content())
+            .placeholder("Add tag...")
+            .on_action(MainMessage::EditTag)
+            .size(14)
+            .padding([0, 5])
+            .width(100);
+
+        let tags = row![
+            widget::Row::with_children(tag_buttons).spacing(5),
+            Space::new().width(5),
+            tag_editor
+        ];
+
+        let bottom_ui = row![
+            logbox,
+            Space::new().width(Fill),
+            spellcheck_status,
+            Space::new().width(15),
+            tags,
+            Space::new().width(15),
+            lock_status,
+            Space::new().width(5),
+            lock_button,
+            Space::new().width(15),
+            cursor_position_box
+        ];
+
+        // the toast floats over the bottom corner of the window, so it never moves the rest of the layout around
+        let toast = logbox().active_toast().map(|(toast_text, opacity)| {
+            let toast_box = widget::container(widget::text(toast_text).size(14))
+                .padding([5, 10])
+                .style(move |theme: &iced::Theme| {
+                    let palette = theme.extended_palette();
+
+                    widget::container::Style {
+                        text_color: Some(palette.background.strong.text.scale_alpha(opacity)),
+                        background: Some(
+                            palette.background.strong.color.scale_alpha(opacity).into(),
+                        ),
+                        border: iced::Border::default().rounded(5),
+                        ..Default::default()
+                    }
+                });
+
+            widget::container(toast_box)
+                .padding([LOGBOX_HEIGHT + 10.0, 15.0])
+                .align_right(Fill)
+                .align_bottom(Fill)
+        });
 
-        let layout_ui = column![top_ui, bottom_ui];
+        let layout_ui = stack![column![top_ui, bottom_ui], toast];
 
         let layout_menus = build_full_menu_bar(layout_ui.into(), &self.menu_bar);
 
@@ -590,6 +1123,17 @@ impl Windowable<MainMessage> for Main {
         let mut preserve_context_menu = false;
         let mut preserve_task_menu = false;
 
+        // tasks are also created in their own window, so the agenda is refreshed whenever the main window is focused
+        let tasks_may_change = matches!(
+            message,
+            MainMessage::TaskAction(_)
+                | MainMessage::AddTask
+                | MainMessage::TabSwitched(_)
+                | MainMessage::PerformConfirmed(_)
+                | MainMessage::KeyEvent(KeyboardAction::TaskNavigation(_))
+                | MainMessage::WindowEvent(window::Event::Focused)
+        );
+
         match message {
             MainMessage::EmptyMessage => {
                 panic!("uninit message");
@@ -608,6 +1152,8 @@ impl Windowable<MainMessage> for Main {
                 {
                     previous_day
                 } else {
+                    state.global_store.load_all();
+
                     state
                         .global_store
                         .get_previous_edited_day(state.global_store.current_date())
@@ -633,6 +1179,8 @@ impl Windowable<MainMessage> for Main {
                 {
                     next_day
                 } else {
+                    state.global_store.load_all();
+
                     state
                         .global_store
                         .get_next_edited_day(state.global_store.current_date())
@@ -645,6 +1193,22 @@ impl Windowable<MainMessage> for Main {
                 tasks.push(snap_task);
             }
             MainMessage::JumpToToday => {
+                let today = Local::now().date_naive();
+
+                // reloading the date would clear the undo history, so an accidental second press on today must not
+                // reload the entry
+                if state.global_store.current_date() == today {
+                    if preferences().general.today_scrolls_to_top {
+                        let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
+                        tasks.push(snap_task);
+                    }
+                } else {
+                    return self.update(state, MainMessage::LoadToday);
+                }
+            }
+            // unlike the Today button, this always reloads, since a window or store that was just created is already
+            // on today without anything about the day being loaded into the window yet
+            MainMessage::LoadToday => {
                 self.active_content = None;
 
                 self.reload_date(state, Local::now().date_naive());
@@ -652,16 +1216,113 @@ impl Windowable<MainMessage> for Main {
                 let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
                 tasks.push(snap_task);
             }
+            MainMessage::EditJumpDate(jump_date_action) => {
+                if self.active_content != Some(ActiveContent::JumpDate) {
+                    self.write_active_entry_to_store(state);
+                }
+                self.active_content = Some(ActiveContent::JumpDate);
+
+                if let text_editor::Action::Edit(
+                    text_editor::Edit::Enter | text_editor::Edit::Insert('\n'),
+                ) = &jump_date_action
+                {
+                    return self.update(state, MainMessage::JumpToDate);
+                }
+
+                self.jump_date_content.perform(ContentAction::Restricted((
+                    Restriction::NoNewlines,
+                    jump_date_action,
+                )));
+            }
+            MainMessage::JumpToDate => {
+                let jump_date_text = self.jump_date_content.text().trim().to_string();
+
+                if jump_date_text.is_empty() {
+                    return Task::none();
+                }
+
+                let Some(new_date) = misc_tools::parse_yyyy_mm_dd(&jump_date_text) else {
+                    logbox_mut().log(&format!(
+                        "\"{}\" isn't a valid date, dates are written as YYYY-MM-DD",
+                        jump_date_text
+                    ));
+
+                    return Task::none();
+                };
+
+                self.active_content = None;
+                self.jump_date_content = UpgradedContent::default();
+
+                self.navigate_to_date(state, new_date);
+
+                let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
+                tasks.push(snap_task);
+            }
             MainMessage::Edit(editor_action) => {
+                // while the tasks have the keyboard focus, the keys meant for them must not reach the editor. clicking
+                // into the editor hands the focus back to it
+                if self.tasks_focused() {
+                    match &editor_action {
+                        Action::Click(_) | Action::SelectWord | Action::SelectLine => {
+                            self.focus_region = FocusRegion::Editor;
+                        }
+                        Action::Scroll { .. } => {}
+                        _ => return Task::none(),
+                    }
+                }
+
                 self.active_content = Some(ActiveContent::Editor);
 
-                if let Action::Edit(_edit) = &editor_action {
+                if let Action::Edit(text_editor::Edit::Paste(pasted_text)) = &editor_action
+                    && preferences().editor.confirm_large_pastes
+                    && !state.global_store.day().locked()
+                {
+                    let pasted_char_count = pasted_text.chars().count();
+
+                    if pasted_char_count > preferences().editor.large_paste_threshold {
+                        let dialog_text = format!(
+                            "You are about to paste {} characters into the entry. Continue?",
+                            pasted_char_count
+                        );
+
+                        state.upstream_actions.push(UpstreamAction::OpenDialog(
+                            DialogType::Confirmation(ConfirmedAction::PasteText(
+                                pasted_text.to_string(),
+                            )),
+                            dialog_text,
+                        ));
+
+                        return Task::none();
+                    }
+                }
+
+                if let Action::Edit(edit) = &editor_action {
+                    self.record_writing_time(state);
                     self.last_edit_time = Local::now();
+
+                    if let text_editor::Edit::Insert('.' | '!' | '?') = edit
+                        && preferences().general.save_after_sentence
+                    {
+                        self.sentence_save_pending = true;
+                    }
                 }
 
-                state
-                    .content
-                    .perform(ContentAction::Standard(editor_action.clone()));
+                // double clicking selects the word under the cursor the same way the spellchecker splits words, so the
+                // selection lines up with the spelling suggestions. clicks on whitespace or punctuation select nothing
+                if let Action::SelectWord = &editor_action {
+                    state.content.select_word_at_cursor();
+                } else {
+                    let content_action = if let Action::Edit(text_editor::Edit::Enter) =
+                        &editor_action
+                        && preferences().editor.continue_lists
+                    {
+                        ContentAction::ListEnter
+                    } else {
+                        ContentAction::Standard(editor_action.clone())
+                    };
+
+                    Self::editor_perform(state, content_action);
+                }
 
                 self.update_spellcheck(state);
 
@@ -675,32 +1336,44 @@ impl Windowable<MainMessage> for Main {
                 }
                 self.active_content = Some(ActiveContent::Search);
 
-                if let text_editor::Action::Edit(edit) = &search_action {
-                    // prevent newlines from being entered into the searchbar since it causes issues with the
-                    // highlighted search results, among other things
-                    match edit {
-                        text_editor::Edit::Insert(inserted_char) => {
-                            if *inserted_char == '\n' {
-                                return Task::none();
-                            }
-                        }
-                        text_editor::Edit::Paste(pasted_text) => {
-                            let pasted_string = pasted_text.to_string();
-                            if pasted_string.contains("\n") {
-                                return Task::none();
-                            }
-                        }
-                        text_editor::Edit::Enter => {
-                            return Task::none();
+                if let text_editor::Action::Edit(
+                    text_editor::Edit::Enter | text_editor::Edit::Insert('\n'),
+                ) = &search_action
+                {
+                    let enter_action = preferences().search.enter_action;
+
+                    let result_index = match enter_action {
+                        SearchEnterAction::Ignore => None,
+                        SearchEnterAction::FirstResult => Some(0),
+                        SearchEnterAction::NextResult => {
+                            Some(self.search_result_index.map_or(0, |index| {
+                                (index + 1) % self.search_table.result_count().max(1)
+                            }))
                         }
-                        _ => {}
-                    }
-                }
+                    };
 
-                self.search_content
-                    .perform(ContentAction::Standard(search_action.clone()));
+                    if let Some(result_index) = result_index
+                        && let Some(result_date) = self.search_table.result_date(result_index)
+                    {
+                        self.search_result_index = Some(result_index);
 
-                self.recompute_search(state);
+                        self.push_search_history(self.search_content.text());
+
+                        self.reload_date(state, result_date);
+
+                        let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
+                        tasks.push(snap_task);
+                    }
+                } else {
+                    // newlines are never entered into the searchbar since they cause issues with the highlighted
+                    // search results, among other things. pasted newlines are stripped out
+                    self.search_content.perform(ContentAction::Restricted((
+                        Restriction::NoNewlines,
+                        search_action,
+                    )));
+
+                    self.recompute_search(state);
+                }
             }
             MainMessage::SwitchEditorMode(new_editor_mode) => {
                 self.active_content = None;
@@ -720,47 +1393,31 @@ impl Windowable<MainMessage> for Main {
             MainMessage::Calender(calender_message) => {
                 self.active_content = None;
 
-                match calender_message {
-                    CalenderMessage::DayClicked(new_date) => {
-                        self.reload_date(state, new_date);
-                    }
-                    CalenderMessage::BackMonth => {
-                        let new_date = state
-                            .global_store
-                            .current_date()
-                            .checked_sub_months(Months::new(1))
-                            .expect("couldn't go back a month");
-
-                        self.reload_date(state, new_date);
-                    }
-                    CalenderMessage::ForwardMonth => {
-                        let new_date = state
-                            .global_store
-                            .current_date()
-                            .checked_add_months(Months::new(1))
-                            .expect("couldn't go forward a month");
-
-                        self.reload_date(state, new_date);
-                    }
-                    CalenderMessage::BackYear => {
-                        let new_date = state
-                            .global_store
-                            .current_date()
-                            .checked_sub_months(Months::new(12))
-                            .expect("couldn't go back a year");
-
-                        self.reload_date(state, new_date);
-                    }
-                    CalenderMessage::ForwardYear => {
-                        let new_date = state
-                            .global_store
-                            .current_date()
-                            .checked_add_months(Months::new(12))
-                            .expect("couldn't go forward a year");
+                let new_date = match calender_message {
+                    CalenderMessage::DayClicked(new_date) => new_date,
+                    CalenderMessage::BackMonth => state
+                        .global_store
+                        .current_date()
+                        .checked_sub_months(Months::new(1))
+                        .expect("couldn't go back a month"),
+                    CalenderMessage::ForwardMonth => state
+                        .global_store
+                        .current_date()
+                        .checked_add_months(Months::new(1))
+                        .expect("couldn't go forward a month"),
+                    CalenderMessage::BackYear => state
+                        .global_store
+                        .current_date()
+                        .checked_sub_months(Months::new(12))
+                        .expect("couldn't go back a year"),
+                    CalenderMessage::ForwardYear => state
+                        .global_store
+                        .current_date()
+                        .checked_add_months(Months::new(12))
+                        .expect("couldn't go forward a year"),
+                };
 
-                        self.reload_date(state, new_date);
-                    }
-                }
+                self.navigate_to_date(state, new_date);
 
                 let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
                 tasks.push(snap_task);
@@ -772,9 +1429,16 @@ impl Windowable<MainMessage> for Main {
                     self.last_edit_time = Local::now();
                 }
                 KeyboardAction::Save => {
+                    // saving only reads the entry out of the editor and never rebuilds its content, so the cursor and
+                    // selection are untouched. the scroll offset is restored in case the layout around the editor
+                    // shifted while saving
                     self.save_all(state);
 
-                    logbox_mut().log("Saved");
+                    logbox_mut().log_save("Saved");
+
+                    let restore_scroll_task =
+                        scroll_to(Id::new(LOG_EDIT_AREA_ID), self.editor_scroll_offset);
+                    tasks.push(restore_scroll_task);
                 }
                 KeyboardAction::Debug => {
                     let dialog_text = "debug!".to_string();
@@ -783,6 +1447,79 @@ impl Windowable<MainMessage> for Main {
                         .upstream_actions
                         .push(UpstreamAction::OpenDialog(DialogType::Warning, dialog_text));
                 }
+                KeyboardAction::OpenPreferences => {
+                    return self.update(state, MainMessage::OpenPreferencesWindow);
+                }
+                KeyboardAction::NewTask => {
+                    return self.update(state, MainMessage::AddTask);
+                }
+                KeyboardAction::Paste => {
+                    // the editor pastes text on its own, so only a clipboard without any text is left to handle here
+                    if self.active_content == Some(ActiveContent::Editor)
+                        && read_clipboard().is_empty()
+                    {
+                        return self.paste_clipboard_image(state);
+                    }
+                }
+                KeyboardAction::RevertEntry => {
+                    return self.update(state, MainMessage::RevertEntry);
+                }
+                KeyboardAction::SwitchFocusRegion => {
+                    if self.tasks_focused() {
+                        self.focus_region = FocusRegion::Editor;
+                        self.active_content = Some(ActiveContent::Editor);
+                    } else {
+                        if self.current_tab != Tab::Tasks {
+                            tasks.push(self.update(state, MainMessage::TabSwitched(Tab::Tasks)));
+                        }
+
+                        self.write_active_entry_to_store(state);
+
+                        self.active_content = None;
+                        self.focus_region = FocusRegion::Tasks;
+                        self.focused_task_index = 0;
+                    }
+                }
+                KeyboardAction::ToggleFocusMode => {
+                    self.toggle_focus_mode();
+                }
+                KeyboardAction::TaskNavigation(task_navigation) => {
+                    if self.tasks_focused() {
+                        self.navigate_tasks(state, task_navigation);
+                    } else {
+                        // any other content taking over the keyboard ends the task focus
+                        self.focus_region = FocusRegion::Editor;
+                    }
+                }
+                KeyboardAction::JumpToDate => {
+                    if self.active_content != Some(ActiveContent::JumpDate) {
+                        self.write_active_entry_to_store(state);
+                    }
+                    self.active_content = Some(ActiveContent::JumpDate);
+
+                    tasks.push(focus(Id::new(JUMP_DATE_ID)));
+                }
+                KeyboardAction::SearchCursorWord => {
+                    let cursor_word = state.content.word_under_cursor().trim().to_string();
+
+                    if !cursor_word.is_empty() {
+                        // the active entry may have edits that haven't reached the store yet, which would leave the
+                        // current day out of the results
+                        self.write_active_entry_to_store(state);
+
+                        self.search_content = UpgradedContent::with_text(&cursor_word);
+                        self.push_search_history(cursor_word);
+
+                        let switch_task = self.update(state, MainMessage::TabSwitched(Tab::Search));
+                        tasks.push(switch_task);
+
+                        let search_task = self.update(
+                            state,
+                            MainMessage::EditSearch(Action::Move(text_editor::Motion::DocumentEnd)),
+                        );
+                        tasks.push(search_task);
+                    }
+                }
                 KeyboardAction::Unbound(unbounded_action) => match unbounded_action {
                     UnboundKey::Cut => {
                         let selection = state.content.selection();
@@ -806,6 +1543,10 @@ impl Windowable<MainMessage> for Main {
                     UnboundKey::Paste => {
                         let clipboard_text = read_clipboard();
 
+                        if clipboard_text.is_empty() {
+                            return self.paste_clipboard_image(state);
+                        }
+
                         return self.update(
                             state,
                             MainMessage::Edit(Action::Edit(text_editor::Edit::Paste(
@@ -815,31 +1556,53 @@ impl Windowable<MainMessage> for Main {
                     }
                 },
             },
+            MainMessage::MonthStrip(month_strip_message) => {
+                self.active_content = None;
+
+                let MonthStripMessage::MonthClicked(first_of_month) = month_strip_message;
+
+                self.reload_date(state, first_of_month);
+
+                let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
+                tasks.push(snap_task);
+            }
             MainMessage::TableSearch(table_message) => {
                 self.active_content = None;
 
                 let SearchTableMessage::EntryClicked(table_date) = table_message;
 
+                self.push_search_history(self.search_content.text());
+
                 self.reload_date(state, table_date);
 
                 let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
                 tasks.push(snap_task);
             }
             MainMessage::TabSwitched(tab) => {
-                self.active_content = None;
+                // switching tabs shouldn't take the focus away from the editor, so typing can resume right where it
+                // left off
+                let editor_was_active = self.active_content == Some(ActiveContent::Editor);
+
+                self.active_content = editor_was_active.then_some(ActiveContent::Editor);
 
                 self.write_active_entry_to_store(state);
 
+                let restore_scroll_task =
+                    scroll_to(Id::new(LOG_EDIT_AREA_ID), self.editor_scroll_offset);
+                tasks.push(restore_scroll_task);
+
                 self.current_tab = tab;
 
                 match self.current_tab {
                     Tab::Tasks => {
                         self.calender.set_colormap(CalenderColormap::default());
                     }
-                    Tab::Search => {
+                    Tab::Search | Tab::Agenda => {
                         self.calender.set_colormap(CalenderColormap::default());
                     }
                     Tab::Stats => {
+                        // the stats cover the whole journal, so every month has to be loaded first
+                        state.global_store.load_all();
                         state.global_store.update_word_count();
 
                         self.calender
@@ -852,13 +1615,26 @@ impl Windowable<MainMessage> for Main {
 
                 let equivalent_edit = text_editor::Edit::Paste(selected_suggestion.into());
 
-                state
-                    .content
-                    .perform(ContentAction::Standard(Action::Edit(equivalent_edit)));
+                Self::editor_perform(
+                    state,
+                    ContentAction::Standard(Action::Edit(equivalent_edit)),
+                );
             }
             MainMessage::AddToDictionary(word) => {
                 dictionary::add_word_to_personal_dictionary(&word);
             }
+            MainMessage::InsertSnippet(snippet_name) => {
+                let snippet_text = preferences().editor.snippets.get(&snippet_name).cloned();
+
+                if let Some(snippet_text) = snippet_text {
+                    return self.update(
+                        state,
+                        MainMessage::Edit(Action::Edit(text_editor::Edit::Paste(
+                            snippet_text.into(),
+                        ))),
+                    );
+                }
+            }
             MainMessage::ClearSearch => {
                 // TODO: auto focus
                 // self.active_content = Some(ActiveContent::Search);
@@ -871,11 +1647,21 @@ impl Windowable<MainMessage> for Main {
                 );
                 tasks.push(search_task);
             }
+            MainMessage::SearchHistorySelected(past_search) => {
+                self.search_content = UpgradedContent::with_text(&past_search);
+
+                let search_task = self.update(
+                    state,
+                    MainMessage::EditSearch(Action::Move(text_editor::Motion::DocumentEnd)),
+                );
+                tasks.push(search_task);
+            }
             MainMessage::ToggleSearchCase => {
                 // TODO: keep focus?
                 self.active_content = None;
 
                 preferences_mut().search.toggle_ignore_search_case();
+                preferences().write_to_disk();
 
                 let search_task = self.update(
                     state,
@@ -900,20 +1686,39 @@ impl Windowable<MainMessage> for Main {
                         self.captured_mouse_position,
                     )));
 
-                    state
-                        .content
-                        .perform(ContentAction::Standard(Action::SelectWord));
+                    state.content.select_word_at_cursor();
 
                     self.update_spellcheck(state);
                 }
 
                 self.show_context_menu = true;
             }
-            MainMessage::ExitContextMenu => {
-                self.show_context_menu = false;
-            }
-            MainMessage::WindowEvent(event) => match event {
-                window::Event::Opened {
+            #[cfg(target_os = "linux")]
+            MainMessage::MiddleClickEditArea => {
+                // the paste lands where the middle click happened, not at the previous cursor position. the mouse
+                // position is within the visible part of the editor, so the scrolled away part is added back
+                let mut click_position = self.mouse_position;
+                click_position.y += self.editor_scroll_offset.y;
+
+                Self::editor_perform(
+                    state,
+                    ContentAction::Standard(Action::Click(click_position)),
+                );
+
+                let selection_text = read_primary_selection();
+
+                return self.update(
+                    state,
+                    MainMessage::Edit(Action::Edit(text_editor::Edit::Paste(
+                        selection_text.into(),
+                    ))),
+                );
+            }
+            MainMessage::ExitContextMenu => {
+                self.show_context_menu = false;
+            }
+            MainMessage::WindowEvent(event) => match event {
+                window::Event::Opened {
                     position: _position,
                     size: inital_size,
                 } => {
@@ -995,11 +1800,89 @@ impl Windowable<MainMessage> for Main {
                                 MainMessage::KeyEvent(KeyboardAction::Unbound(UnboundKey::Paste)),
                             );
                         }
+                        EditMessage::ContinueFromYesterday => {
+                            let previous_text = state
+                                .global_store
+                                .current_date()
+                                .checked_sub_days(Days::new(1))
+                                .and_then(|previous_date| state.global_store.get_day(previous_date))
+                                .filter(|previous_day| previous_day.contains_entry())
+                                .map(|previous_day| previous_day.get_day_text());
+
+                            if !state.content.text().trim().is_empty() {
+                                logbox_mut()
+                                    .log("Only an empty entry can be continued from yesterday");
+                            } else if state.global_store.day().locked() {
+                                logbox_mut().log("The entry is locked");
+                            } else if let Some(previous_text) = previous_text {
+                                self.active_content = Some(ActiveContent::Editor);
+
+                                Self::editor_perform(
+                                    state,
+                                    ContentAction::Standard(Action::SelectAll),
+                                );
+                                Self::editor_perform(
+                                    state,
+                                    ContentAction::Standard(Action::Edit(
+                                        text_editor::Edit::Paste(previous_text.into()),
+                                    )),
+                                );
+
+                                self.carried_over_text = Some(state.content.text());
+                            } else {
+                                logbox_mut()
+                                    .log("There is no entry from yesterday to continue from");
+                            }
+                        }
+                        EditMessage::RevertEntry => {
+                            return self.update(state, MainMessage::RevertEntry);
+                        }
+                        EditMessage::ClearEntry => {
+                            let current_date = state.global_store.current_date();
+
+                            // a locked entry can't be cleared, so there is nothing to confirm
+                            if state.global_store.day().locked() {
+                                logbox_mut().log("The entry is locked");
+                            } else {
+                                let dialog_text = format!(
+                                    "Clear the entire entry for {}? It can be brought back with Undo.",
+                                    current_date
+                                );
+
+                                state.upstream_actions.push(UpstreamAction::OpenDialog(
+                                    DialogType::Confirmation(ConfirmedAction::ClearEntry(
+                                        current_date,
+                                    )),
+                                    dialog_text,
+                                ));
+                            }
+                        }
                     },
                     MenuMessage::Tools(tools_message) => match tools_message {
+                        ToolsMessage::NewTask => {
+                            return self.update(state, MainMessage::AddTask);
+                        }
                         ToolsMessage::Preferences => {
                             return self.update(state, MainMessage::OpenPreferencesWindow);
                         }
+                        ToolsMessage::QuickCapture => {
+                            state
+                                .upstream_actions
+                                .push(UpstreamAction::CreateWindow(WindowType::QuickCapture));
+                        }
+                        ToolsMessage::FocusMode => {
+                            self.toggle_focus_mode();
+                        }
+                        ToolsMessage::Diagnostics => {
+                            state
+                                .upstream_actions
+                                .push(UpstreamAction::CreateWindow(WindowType::Diagnostics));
+                        }
+                        ToolsMessage::CopyDiagnostics => {
+                            write_clipboard(Diagnostics::report(state));
+
+                            logbox_mut().log("Copied diagnostics to the clipboard");
+                        }
                     },
                 }
             }
@@ -1039,17 +1922,433 @@ impl Windowable<MainMessage> for Main {
 
                 self.active_content = Some(ActiveContent::Task(template_message.get_id()));
 
-                state
-                    .task_manager
-                    .update(state.global_store.current_date(), template_message);
+                // deleting a template throws away every one of its entries, so it has to be confirmed first
+                if template_message.is_delete_template_message() {
+                    state.task_manager.close_menu();
+
+                    if let Some((task_name, task_type)) = state
+                        .task_manager
+                        .template_tasks
+                        .template_identity(template_message.get_id())
+                    {
+                        let dialog_text = format!(
+                            "Delete the task \"{}\" and all of its entries? This can't be undone.",
+                            task_name
+                        );
+
+                        state.upstream_actions.push(UpstreamAction::OpenDialog(
+                            DialogType::Confirmation(ConfirmedAction::DeleteTemplate(
+                                task_name, task_type,
+                            )),
+                            dialog_text,
+                        ));
+                    }
+                } else {
+                    state
+                        .task_manager
+                        .update(state.global_store.current_date(), template_message);
+                }
             }
             MainMessage::Autosave => {
                 preserve_context_menu = true;
                 preserve_task_menu = true;
 
+                self.write_active_entry_to_store(state);
+
+                // autosaving runs on a timer, so the journal files are left alone when nothing changed since the last
+                // save. tasks are still saved, since they keep track of their own changes
+                if state.global_store.has_unsaved_changes() {
+                    self.save_all(state);
+
+                    logbox_mut().log_save("Autosaved");
+                } else {
+                    state.task_manager.save_all();
+
+                    // any draft left over is of edits that were since undone back to the saved text
+                    Draft::clear_from_disk();
+
+                    self.sentence_save_pending = false;
+                }
+            }
+            MainMessage::AppendToToday(capture_text) => {
+                preserve_context_menu = true;
+                preserve_task_menu = true;
+
+                let active_date = state.global_store.current_date();
+                let today = Local::now().date_naive();
+
+                self.write_active_entry_to_store(state);
+                state.global_store.set_current_store_date(today);
+
+                if state.global_store.day().locked() {
+                    state.global_store.set_current_store_date(active_date);
+
+                    logbox_mut().log("Today's entry is locked, so the note wasn't added");
+
+                    return Task::none();
+                }
+
+                let mut timestamp = String::new();
+                let timestamp_format = preferences().general.quick_capture_timestamp.clone();
+
+                // chrono only reports an invalid format once it is written out, so a bad format leaves the note
+                // without a timestamp rather than panicking
+                if !timestamp_format.is_empty()
+                    && write!(timestamp, "{} ", Local::now().format(&timestamp_format)).is_err()
+                {
+                    timestamp.clear();
+
+                    logbox_mut().log(&format!(
+                        "\"{}\" isn't a valid timestamp format",
+                        timestamp_format
+                    ));
+                }
+
+                let separator = if state.global_store.day().get_day_text().ends_with('\n') {
+                    ""
+                } else {
+                    "\n"
+                };
+
+                state
+                    .global_store
+                    .day_mut()
+                    .append_text(&(timestamp + &capture_text), separator);
+
+                state.global_store.set_current_store_date(active_date);
+
+                if active_date == today {
+                    self.load_active_entry(state);
+                }
+
                 self.save_all(state);
 
-                logbox_mut().log("Autosaved");
+                logbox_mut().log_save("Added a note to today's entry");
+            }
+            MainMessage::RevertEntry => {
+                self.write_active_entry_to_store(state);
+
+                let current_date = state.global_store.current_date();
+
+                if state.global_store.day().get_day_text() == state.global_store.day().saved_text()
+                {
+                    logbox_mut().log("No unsaved edits to revert");
+                } else {
+                    let dialog_text = format!(
+                        "Revert the entry for {} to its last save? Every edit since then will be lost, and this can't \
+                        be undone.",
+                        current_date
+                    );
+
+                    state.upstream_actions.push(UpstreamAction::OpenDialog(
+                        DialogType::Confirmation(ConfirmedAction::RevertEntry(current_date)),
+                        dialog_text,
+                    ));
+                }
+            }
+            MainMessage::PerformConfirmed(confirmed_action) => match confirmed_action {
+                ConfirmedAction::PasteText(pasted_text) => {
+                    self.active_content = Some(ActiveContent::Editor);
+
+                    Self::editor_perform(
+                        state,
+                        ContentAction::Standard(Action::Edit(text_editor::Edit::Paste(
+                            pasted_text.into(),
+                        ))),
+                    );
+
+                    self.last_edit_time = Local::now();
+
+                    self.update_spellcheck(state);
+                }
+                ConfirmedAction::RecoverDrafts(drafts) => {
+                    // each draft is opened in turn, leaving the most recent one as the active entry
+                    for draft in &drafts {
+                        self.reload_date(state, draft.date());
+
+                        state
+                            .global_store
+                            .day_mut()
+                            .set_day_text(draft.text().to_string());
+                        self.load_active_entry(state);
+                        self.write_active_entry_to_store(state);
+
+                        logbox_mut().log(&format!("Recovered unsaved draft of {}", draft.date()));
+                    }
+                }
+                ConfirmedAction::ImportText(imported_string, strategy) => {
+                    file_import_window::import_text(state, imported_string, strategy);
+
+                    self.update_spellcheck(state);
+                }
+                ConfirmedAction::DeleteTemplate(task_name, task_type) => {
+                    self.active_content = None;
+
+                    if state
+                        .task_manager
+                        .template_tasks
+                        .remove_template(&task_name, task_type)
+                    {
+                        logbox_mut().log(&format!("Deleted task \"{}\"", task_name));
+                    }
+                }
+                ConfirmedAction::RevertEntry(revert_date) => {
+                    if revert_date != state.global_store.current_date() {
+                        self.reload_date(state, revert_date);
+                    }
+
+                    let saved_text = state.global_store.day().saved_text().to_string();
+
+                    state.global_store.day_mut().set_day_text(saved_text);
+
+                    self.load_active_entry(state);
+                    self.write_active_entry_to_store(state);
+                    self.update_spellcheck(state);
+
+                    // the day is back to what is on disk, so there is nothing left to save
+                    state.global_store.day_mut().mark_saved();
+
+                    // the draft of the day only holds the edits that were just discarded. the drafts of other days
+                    // still hold their own unsaved edits
+                    Draft::remove_from_disk(revert_date);
+                    self.draft_text = state.content.text();
+
+                    logbox_mut().log(&format!(
+                        "Reverted entry of {} to its last save",
+                        revert_date
+                    ));
+                }
+                ConfirmedAction::NavigateToDate(new_date) => {
+                    self.active_content = None;
+
+                    self.reload_date(state, new_date);
+
+                    let snap_task = snap_to(Id::new(LOG_EDIT_AREA_ID), RelativeOffset::START);
+                    tasks.push(snap_task);
+                }
+                ConfirmedAction::ClearEntry(clear_date) => {
+                    if clear_date != state.global_store.current_date() {
+                        self.reload_date(state, clear_date);
+                    }
+
+                    self.active_content = Some(ActiveContent::Editor);
+
+                    // clearing through the editor keeps the removal as a single event on the undo stack
+                    Self::editor_perform(state, ContentAction::Standard(Action::SelectAll));
+                    Self::editor_perform(
+                        state,
+                        ContentAction::Standard(Action::Edit(text_editor::Edit::Delete)),
+                    );
+
+                    self.write_active_entry_to_store(state);
+                    self.update_spellcheck(state);
+
+                    if state.global_store.day().contains_entry() {
+                        logbox_mut().log("Entry is locked and wasn't cleared");
+                    } else {
+                        logbox_mut().log(&format!("Cleared entry of {}", clear_date));
+                    }
+                }
+            },
+            MainMessage::EditWordFilter(filter_action) => {
+                self.active_content = Some(ActiveContent::WordFilter);
+
+                self.word_filter_content.perform(ContentAction::Restricted((
+                    Restriction::NumbersOnly,
+                    filter_action,
+                )));
+
+                self.word_filter_threshold = self.word_filter_content.text().parse::<usize>().ok();
+
+                self.update_word_filter(state);
+            }
+            MainMessage::ResetWordFilter => {
+                self.active_content = None;
+
+                self.word_filter_content = UpgradedContent::default();
+                self.word_filter_threshold = None;
+
+                self.update_word_filter(state);
+            }
+            MainMessage::EditReplace(replace_action) => {
+                self.active_content = Some(ActiveContent::Replace);
+
+                self.replace_content.perform(ContentAction::Restricted((
+                    Restriction::NoNewlines,
+                    replace_action,
+                )));
+            }
+            MainMessage::ReplaceNext => {
+                self.active_content = Some(ActiveContent::Editor);
+
+                let search_text = self.search_content.text();
+
+                if search_text.is_empty() || state.global_store.day().locked() {
+                    return Task::none();
+                }
+
+                let matches = Self::find_matches(
+                    &state.content.text(),
+                    &search_text,
+                    preferences().search.ignore_search_case,
+                );
+
+                // the search continues from the start of the selection, so a selected match is the one replaced
+                let cursor = UpgradedContent::char_cursor(state.content.raw_content());
+                let search_start = cursor.selection.map_or(cursor.position, |selection| {
+                    if (selection.line, selection.column)
+                        < (cursor.position.line, cursor.position.column)
+                    {
+                        selection
+                    } else {
+                        cursor.position
+                    }
+                });
+
+                let next_match = matches
+                    .iter()
+                    .find(|match_position| {
+                        (match_position.line, match_position.column)
+                            >= (search_start.line, search_start.column)
+                    })
+                    .or(matches.first());
+
+                if let Some(match_position) = next_match {
+                    state
+                        .content
+                        .select_from(*match_position, search_text.chars().count());
+
+                    Self::editor_perform(
+                        state,
+                        ContentAction::Standard(Action::Edit(text_editor::Edit::Paste(
+                            self.replace_content.text().into(),
+                        ))),
+                    );
+
+                    self.last_edit_time = Local::now();
+                    self.write_active_entry_to_store(state);
+                    self.recompute_search(state);
+                } else {
+                    logbox_mut().log("No matches to replace");
+                }
+            }
+            MainMessage::ReplaceAll => {
+                self.active_content = Some(ActiveContent::Editor);
+
+                let search_text = self.search_content.text();
+
+                if search_text.is_empty() || state.global_store.day().locked() {
+                    return Task::none();
+                }
+
+                let (replaced_text, replacement_count, last_replacement_end) =
+                    Self::replace_matches(
+                        &state.content.text(),
+                        &search_text,
+                        &self.replace_content.text(),
+                        preferences().search.ignore_search_case,
+                    );
+
+                if let Some(last_replacement_end) = last_replacement_end {
+                    // replacing the whole text at once makes all the replacements a single undo step
+                    Self::editor_perform(state, ContentAction::Standard(Action::SelectAll));
+                    Self::editor_perform(
+                        state,
+                        ContentAction::Standard(Action::Edit(text_editor::Edit::Paste(
+                            replaced_text.into(),
+                        ))),
+                    );
+
+                    state.content.move_cursor_to(last_replacement_end);
+
+                    logbox_mut().log(&format!("Replaced {} matches", replacement_count));
+
+                    self.last_edit_time = Local::now();
+                    self.write_active_entry_to_store(state);
+                    self.recompute_search(state);
+                } else {
+                    logbox_mut().log("No matches to replace");
+                }
+            }
+            MainMessage::EditTagFilter(tag_filter_action) => {
+                if self.active_content != Some(ActiveContent::TagFilter) {
+                    self.write_active_entry_to_store(state);
+                }
+                self.active_content = Some(ActiveContent::TagFilter);
+
+                self.tag_filter_content.perform(ContentAction::Restricted((
+                    Restriction::NoNewlines,
+                    tag_filter_action,
+                )));
+
+                self.recompute_search(state);
+            }
+            MainMessage::EditTag(tag_action) => {
+                if self.active_content != Some(ActiveContent::Tag) {
+                    self.write_active_entry_to_store(state);
+                }
+                self.active_content = Some(ActiveContent::Tag);
+
+                if let text_editor::Action::Edit(
+                    text_editor::Edit::Enter | text_editor::Edit::Insert('\n'),
+                ) = &tag_action
+                {
+                    return self.update(state, MainMessage::AddTag);
+                }
+
+                self.tag_content.perform(ContentAction::Restricted((
+                    Restriction::NoNewlines,
+                    tag_action,
+                )));
+            }
+            MainMessage::AddTag => {
+                let tag = self.tag_content.text().trim().to_string();
+
+                if tag.is_empty() {
+                    return Task::none();
+                }
+
+                if state.global_store.day().locked() {
+                    logbox_mut().log("Entry is locked and its tags can't be changed");
+
+                    return Task::none();
+                }
+
+                if state.global_store.day_mut().add_tag(&tag) {
+                    logbox_mut().log(&format!("Tagged entry with \"{}\"", tag));
+
+                    self.recompute_search(state);
+                } else {
+                    logbox_mut().log(&format!("Entry is already tagged with \"{}\"", tag));
+                }
+
+                self.tag_content = UpgradedContent::default();
+            }
+            MainMessage::RemoveTag(tag) => {
+                if state.global_store.day().locked() {
+                    logbox_mut().log("Entry is locked and its tags can't be changed");
+
+                    return Task::none();
+                }
+
+                if state.global_store.day_mut().remove_tag(&tag) {
+                    logbox_mut().log(&format!("Removed tag \"{}\"", tag));
+
+                    self.recompute_search(state);
+                }
+            }
+            MainMessage::ToggleDayLock => {
+                self.write_active_entry_to_store(state);
+
+                let day_store = state.global_store.day_mut();
+                let locked = !day_store.locked();
+                day_store.set_locked(locked);
+
+                if locked {
+                    logbox_mut().log("Locked entry");
+                } else {
+                    logbox_mut().log("Unlocked entry");
+                }
             }
         }
 
@@ -1061,6 +2360,10 @@ impl Windowable<MainMessage> for Main {
             state.task_manager.close_menu();
         }
 
+        if tasks_may_change {
+            self.update_agenda(state);
+        }
+
         if tasks.is_empty() {
             tasks.push(Task::none());
         }
@@ -1071,8 +2374,13 @@ impl Windowable<MainMessage> for Main {
     fn content_perform(&mut self, state: &mut SharedAppState, action: ContentAction) {
         if let Some(active_content) = &self.active_content {
             match active_content {
-                ActiveContent::Editor => state.content.perform(action),
+                ActiveContent::Editor => Self::editor_perform(state, action),
                 ActiveContent::Search => self.search_content.perform(action),
+                ActiveContent::Replace => self.replace_content.perform(action),
+                ActiveContent::WordFilter => self.word_filter_content.perform(action),
+                ActiveContent::Tag => self.tag_content.perform(action),
+                ActiveContent::TagFilter => self.tag_filter_content.perform(action),
+                ActiveContent::JumpDate => self.jump_date_content.perform(action),
                 ActiveContent::Task(task_id) => {
                     if let Some(task) = state.task_manager.template_tasks.get_task_mut(*task_id) {
                         match task.get_template_mut() {
@@ -1090,7 +2398,11 @@ impl Windowable<MainMessage> for Main {
                                     task_element.content_perform(action);
                                 }
                             }
+                            // counters don't have any text to edit
+                            TemplateData::Counter(_counter_task) => {}
                         }
+
+                        task.mark_dirty();
                     }
                 }
             }
@@ -1105,12 +2417,29 @@ impl Default for Main {
             active_content: None,
             search_content: UpgradedContent::default(),
             search_text: String::default(),
+            search_history: preferences().search.search_history.clone(),
+            search_result_index: None,
+            replace_content: UpgradedContent::default(),
+            tag_filter_content: UpgradedContent::default(),
+            tag_content: UpgradedContent::default(),
+            jump_date_content: UpgradedContent::default(),
+            word_filter_content: UpgradedContent::default(),
+            word_filter_threshold: None,
             calender: Calender::default(),
+            month_strip: MonthStrip::default(),
             search_table: SearchTable::default(),
             current_tab: Tab::default(),
             selected_misspelled_word: None,
             spell_suggestions: vec![],
             last_edit_time: Local::now(),
+            unrecorded_writing_millis: 0,
+            focus_region: FocusRegion::Editor,
+            focused_task_index: 0,
+            last_draft_time: Local::now(),
+            draft_text: String::default(),
+            draft_write_failed: false,
+            sentence_save_pending: false,
+            carried_over_text: None,
             show_context_menu: false,
             mouse_position: Point::default(),
             captured_mouse_position: Point::default(),
@@ -1122,19 +2451,69 @@ impl Default for Main {
             editor_mode: EditorMode::Editor,
             editor_markdown: Vec::default(),
             markdown_image_cache: ImageCache::default(),
+            history_cache: VecDeque::new(),
+            agenda: Agenda::default(),
         }
     }
 }
 
 impl Main {
+    /// performs the action on the entry editor. if the current day is locked, the editor is treated as read only:
+    /// all Edits are blocked, as are any ContentActions that could modify the entry text
+    fn editor_perform(state: &mut SharedAppState, action: ContentAction) {
+        if !state.global_store.day().locked() {
+            state.content.perform(action);
+
+            return;
+        }
+
+        match action {
+            ContentAction::Standard(action) | ContentAction::Restricted((_, action)) => {
+                state
+                    .content
+                    .perform(ContentAction::Restricted((Restriction::ReadOnly, action)));
+            }
+            ContentAction::ClearHistoryStack | ContentAction::CtrlMotion(_) => {
+                state.content.perform(action)
+            }
+            ContentAction::Ctrl(_)
+            | ContentAction::ListEnter
+            | ContentAction::Undo
+            | ContentAction::Redo => {}
+        }
+    }
+
     /// retrieves the text from the store and overwrites the content with it
     fn load_active_entry(&mut self, state: &mut SharedAppState) {
-        state.content = UpgradedContent::with_text(&state.global_store.day().get_day_text());
+        self.draft_text = state.global_store.day().get_day_text();
+        self.carried_over_text = None;
+
+        state.content = UpgradedContent::with_text(&self.draft_text);
+    }
+
+    /// true if exiting now would lose anything: edits to the active entry, anything else in the store, or tasks that
+    /// were changed or checked off since the last save
+    pub fn has_unsaved_changes(&self, state: &SharedAppState) -> bool {
+        let entry_text = state.content.text();
+
+        // text carried over from yesterday isn't kept until it's edited, so it's never unsaved on its own
+        let entry_edited = self.carried_over_text.as_ref() != Some(&entry_text)
+            && entry_text.trim_end_matches('\n')
+                != state.global_store.day().saved_text().trim_end_matches('\n');
+
+        entry_edited
+            || state.global_store.has_unsaved_changes()
+            || state.task_manager.has_unsaved_changes()
     }
 
     /// write the current text into the store
     fn write_active_entry_to_store(&mut self, state: &mut SharedAppState) {
-        let current_text = state.content.text();
+        let mut current_text = state.content.text();
+
+        // text continued from yesterday is only a starting point, so it isn't kept until it has been edited
+        if self.carried_over_text.as_ref() == Some(&current_text) {
+            current_text.clear();
+        }
 
         state.global_store.day_mut().set_day_text(current_text);
 
@@ -1143,39 +2522,290 @@ impl Main {
 
         state.global_store.update_word_count();
 
+        self.update_calender_word_counts(state);
+        self.update_month_strip(state);
+        self.update_word_filter(state);
+
         if self.current_tab == Tab::Stats {
             self.calender
                 .set_colormap(self.compute_word_count_colormap(state));
         }
     }
 
-    /// writes current entry to store, saves the store to disk, and saves task list to disk
+    /// saves the image on the clipboard into the active day's attachments folder, then pastes a markdown image linking to
+    /// it. nothing happens if pasting images is turned off, the clipboard holds no image, or the day is locked
+    fn paste_clipboard_image(&mut self, state: &mut SharedAppState) -> Task<MainMessage> {
+        if !preferences().editor.paste_images_as_attachments || state.global_store.day().locked() {
+            return Task::none();
+        }
+
+        let Some(image) = read_clipboard_image() else {
+            return Task::none();
+        };
+
+        let mut attachment_dir = preferences().paths.journal_path.clone();
+        attachment_dir.push("attachments");
+        attachment_dir.push(state.global_store.current_date().to_string());
+
+        let timestamp = Local::now().format("%H-%M-%S").to_string();
+
+        let mut attachment_path = attachment_dir.join(timestamp.clone() + ".png");
+        let mut duplicate_count = 1;
+
+        // several images pasted within the same second each get their own file
+        while attachment_path.exists() {
+            duplicate_count += 1;
+            attachment_path = attachment_dir.join(format!("{}-{}.png", timestamp, duplicate_count));
+        }
+
+        if fs::create_dir_all(&attachment_dir).is_err() || image.save(&attachment_path).is_err() {
+            logbox_mut().log("Couldn't save the pasted image");
+
+            return Task::none();
+        }
+
+        logbox_mut().log(&format!(
+            "Saved the pasted image to {}",
+            attachment_path.display()
+        ));
+
+        // the link is relative to the journal, so it keeps working if the journal is moved or synced to another
+        // machine. the markdown view resolves relative image paths against the journal directory
+        let attachment_filename = attachment_path
+            .file_name()
+            .map(|filename| filename.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let image_markdown = format!(
+            "![](attachments/{}/{})",
+            state.global_store.current_date(),
+            attachment_filename
+        );
+
+        self.update(
+            state,
+            MainMessage::Edit(Action::Edit(text_editor::Edit::Paste(
+                image_markdown.into(),
+            ))),
+        )
+    }
+
+    /// writes current entry to store, saves the store to disk, and saves task list to disk. the editor content is only
+    /// read, never replaced, so saving keeps the cursor position, selection, and undo history
     fn save_all(&mut self, state: &mut SharedAppState) {
         self.write_active_entry_to_store(state);
         state.global_store.save_all();
 
         state.task_manager.save_all();
-    }
 
-    /// reloads the window's title based on the current active date
-    fn update_window_title(&mut self, state: &mut SharedAppState) {
-        let formated_date = state
-            .global_store
-            .current_date()
-            .format("%A, %B %d, %Y")
-            .to_string();
-        let new_title = "ironnote - ".to_string() + &formated_date;
+        Draft::clear_from_disk();
 
-        self.title = new_title;
+        self.sentence_save_pending = false;
     }
 
-    /// writes the current entry into the store and changes the date of the current entry
-    fn reload_date(&mut self, state: &mut SharedAppState, new_date: NaiveDate) {
-        self.write_active_entry_to_store(state);
-
-        state.global_store.set_current_store_date(new_date);
+    /// adds the time since the last edit to the active day's writing time. only pauses shorter than a minute count as
+    /// writing, so time spent away from the entry is never added. time is kept to the millisecond between edits, since
+    /// the pause between keystrokes is usually well under a second
+    fn record_writing_time(&mut self, state: &mut SharedAppState) {
+        const WRITING_PAUSE_LIMIT_MILLIS: i64 = 60 * 1000;
+
+        if state.global_store.day().locked() {
+            return;
+        }
+
+        let pause_millis = (Local::now() - self.last_edit_time).num_milliseconds();
+
+        if (0..=WRITING_PAUSE_LIMIT_MILLIS).contains(&pause_millis) {
+            self.unrecorded_writing_millis += pause_millis;
+        }
+
+        let recorded_seconds = self.unrecorded_writing_millis / 1000;
+
+        if recorded_seconds > 0 {
+            state
+                .global_store
+                .day_mut()
+                .add_writing_seconds(recorded_seconds as u64);
+
+            self.unrecorded_writing_millis %= 1000;
+        }
+    }
+
+    /// returns true if a sentence was finished and typing has paused long enough since then that the entry should be
+    /// autosaved. the pause keeps the save from interrupting someone who is still typing
+    pub fn sentence_save_due(&self) -> bool {
+        const SENTENCE_SAVE_IDLE_SECONDS: i64 = 2;
+
+        self.sentence_save_pending
+            && (Local::now() - self.last_edit_time).num_seconds() >= SENTENCE_SAVE_IDLE_SECONDS
+    }
+
+    /// writes the active entry to the recovery draft if it has changed since the last draft was written. drafts are
+    /// written at most once every few seconds, no matter how often this is called
+    pub fn write_draft(&mut self, state: &SharedAppState) {
+        const DRAFT_INTERVAL_SECONDS: i64 = 3;
+
+        if (Local::now() - self.last_draft_time).num_seconds() < DRAFT_INTERVAL_SECONDS {
+            return;
+        }
+        self.last_draft_time = Local::now();
+
+        let entry_text = state.content.text();
+
+        if entry_text == self.draft_text {
+            return;
+        }
+
+        let draft = Draft::new(state.global_store.current_date(), entry_text.clone());
+
+        match draft.write_to_disk() {
+            Ok(()) => self.draft_write_failed = false,
+            Err(draft_error) => {
+                if !self.draft_write_failed {
+                    logbox_mut().log(&format!(
+                        "Couldn't write the recovery draft: {}",
+                        draft_error
+                    ));
+                }
+
+                self.draft_write_failed = true;
+            }
+        }
+
+        self.draft_text = entry_text;
+    }
+
+    /// reloads the window's title based on the current active date
+    fn update_window_title(&mut self, state: &mut SharedAppState) {
+        let formated_date = state
+            .global_store
+            .current_date()
+            .format(LONG_DATE_FORMAT)
+            .to_string();
+        let new_title = "ironnote - ".to_string() + &formated_date;
+
+        self.title = new_title;
+    }
+
+    /// changes the date of the current entry, unless the date is far enough from today to need confirmation first. a far
+    /// date only needs confirming when its month isn't in the journal yet, since going there would create one
+    fn navigate_to_date(&mut self, state: &mut SharedAppState, new_date: NaiveDate) {
+        let confirmation_days = preferences().general.far_date_confirmation_days;
+        let days_from_today = (new_date - Local::now().date_naive())
+            .num_days()
+            .unsigned_abs();
+
+        if confirmation_days > 0
+            && days_from_today > confirmation_days as u64
+            && !state.global_store.month_exists(new_date)
+        {
+            let dialog_text = format!(
+                "{} is {} days from today and would start a new month in the journal. Go there anyway?",
+                new_date.format(LONG_DATE_FORMAT),
+                days_from_today
+            );
+
+            state.upstream_actions.push(UpstreamAction::OpenDialog(
+                DialogType::Confirmation(ConfirmedAction::NavigateToDate(new_date)),
+                dialog_text,
+            ));
+
+            return;
+        }
+
+        self.reload_date(state, new_date);
+    }
+
+    /// switches focus mode on or off, remembering it in the preferences so the layout is kept across restarts. the task
+    /// list is hidden in focus mode, so it gives the keyboard back to the editor
+    fn toggle_focus_mode(&mut self) {
+        let focus_mode = !preferences().general.focus_mode;
+
+        preferences_mut().general.focus_mode = focus_mode;
+        preferences().write_to_disk();
+
+        if focus_mode && self.tasks_focused() {
+            self.focus_region = FocusRegion::Editor;
+            self.active_content = Some(ActiveContent::Editor);
+        }
+    }
+
+    /// true if the keyboard is driving the task list. any text editor becoming active takes the keyboard back
+    fn tasks_focused(&self) -> bool {
+        self.focus_region == FocusRegion::Tasks && self.active_content.is_none()
+    }
+
+    /// the task that currently has the keyboard focus, if the task list has it
+    fn focused_task_id(&self, state: &SharedAppState) -> Option<TaskId> {
+        if !self.tasks_focused() {
+            return None;
+        }
+
+        let task_ids = state
+            .task_manager
+            .ordered_task_ids(state.global_store.current_date());
+
+        // tasks can be created or deleted while focused, so the index may have fallen off the end of the list
+        task_ids
+            .get(
+                self.focused_task_index
+                    .min(task_ids.len().saturating_sub(1)),
+            )
+            .copied()
+    }
+
+    /// recomputes the agenda of the current day, if the agenda tab is open
+    fn update_agenda(&mut self, state: &SharedAppState) {
+        if self.current_tab == Tab::Agenda {
+            self.agenda = state.task_manager.agenda(state.global_store.current_date());
+        }
+    }
+
+    /// moves the keyboard focus through the tasks of the current day, or toggles the focused task
+    fn navigate_tasks(&mut self, state: &mut SharedAppState, task_navigation: TaskNavigation) {
+        let active_date = state.global_store.current_date();
+        let task_count = state.task_manager.ordered_task_ids(active_date).len();
+
+        if task_count == 0 {
+            return;
+        }
+
+        let focused_index = self.focused_task_index.min(task_count - 1);
+
+        self.focused_task_index = match task_navigation {
+            TaskNavigation::Previous => focused_index.saturating_sub(1),
+            TaskNavigation::Next => (focused_index + 1).min(task_count - 1),
+            TaskNavigation::Toggle => {
+                if let Some(task_id) = self.focused_task_id(state) {
+                    state.task_manager.toggle_completion(active_date, task_id);
+                }
+
+                focused_index
+            }
+        };
+    }
+
+    /// hands the word counts of the current month to the calender, if it shows them. the counts are only recomputed
+    /// when the entry is written into the store or the date changes, never while rendering
+    fn update_calender_word_counts(&mut self, state: &SharedAppState) {
+        if preferences().general.calender_word_counts {
+            let day_word_counts = state.global_store.month().day_word_counts();
+
+            self.calender.set_word_counts(Some(&day_word_counts));
+        } else {
+            self.calender.set_word_counts(None);
+        }
+    }
+
+    /// writes the current entry into the store and changes the date of the current entry
+    fn reload_date(&mut self, state: &mut SharedAppState, new_date: NaiveDate) {
+        self.write_active_entry_to_store(state);
+        self.cache_history(state);
+
+        state.global_store.set_current_store_date(new_date);
 
         self.update_window_title(state);
+        self.calender
+            .set_compact_weeks(preferences().general.compact_calender);
         self.calender
             .set_current_date(state.global_store.current_date());
         self.load_active_entry(state);
@@ -1183,6 +2813,12 @@ impl Main {
         self.calender
             .set_bolded_days(&state.global_store.month().edited_days());
 
+        state.global_store.update_word_count();
+
+        self.update_calender_word_counts(state);
+        self.update_month_strip(state);
+        self.update_word_filter(state);
+
         if self.current_tab == Tab::Stats {
             self.calender
                 .set_colormap(self.compute_word_count_colormap(state));
@@ -1200,9 +2836,60 @@ impl Main {
             .template_tasks
             .generate_template_entries(state.global_store.current_date());
 
+        self.update_agenda(state);
+
         self.last_edit_time = Local::now();
+        self.unrecorded_writing_millis = 0;
+        self.focused_task_index = 0;
+
+        if !self.restore_cached_history(state) {
+            self.content_perform(state, ContentAction::ClearHistoryStack);
+        }
+    }
+
+    /// moves the undo history of the active entry into the history cache, so it can be restored if the day is visited
+    /// again. once the cache is full, the least recently visited days are dropped
+    fn cache_history(&mut self, state: &mut SharedAppState) {
+        let current_date = state.global_store.current_date();
+        let cache_days = preferences().editor.history_cache_days;
+
+        self.history_cache
+            .retain(|cached_history| cached_history.date != current_date);
+
+        if cache_days == 0 {
+            self.history_cache.clear();
+
+            return;
+        }
+
+        self.history_cache.push_front(CachedHistory {
+            date: current_date,
+            text: state.content.text(),
+            history_stack: state.content.take_history_stack(),
+        });
+        self.history_cache.truncate(cache_days);
+    }
+
+    /// restores the cached undo history of the current day into the freshly loaded entry. returns false if there was
+    /// no usable history for the day
+    fn restore_cached_history(&mut self, state: &mut SharedAppState) -> bool {
+        let current_date = state.global_store.current_date();
+
+        if let Some(cache_index) = self
+            .history_cache
+            .iter()
+            .position(|cached_history| cached_history.date == current_date)
+            && let Some(cached_history) = self.history_cache.remove(cache_index)
+            && cached_history.text == state.content.text()
+        {
+            state
+                .content
+                .set_history_stack(cached_history.history_stack);
+
+            return true;
+        }
 
-        self.content_perform(state, ContentAction::ClearHistoryStack);
+        false
     }
 
     fn update_spellcheck(&mut self, state: &mut SharedAppState) {
@@ -1239,39 +2926,155 @@ impl Main {
                 dictionary.suggest(&selection, &mut spell_suggestions);
                 self.selected_misspelled_word = Some(selection.clone());
 
-                state.global_store.update_word_count();
-
-                let mut sorted_suggestions: Vec<_> = spell_suggestions
-                    .iter()
-                    .map(|word| {
-                        let word_count = state.global_store.get_word_count(&word.to_lowercase());
-
-                        (word_count, word)
-                    })
-                    .collect();
-
-                sorted_suggestions.sort_by_key(|(word_count, _word)| *word_count);
-
-                self.spell_suggestions = sorted_suggestions
-                    .iter()
-                    .map(|(_count, word)| word.to_string())
-                    .rev()
-                    .collect();
+                self.spell_suggestions = Self::order_suggestions(state, spell_suggestions);
             } else {
                 self.selected_misspelled_word = None;
             }
         }
     }
 
+    /// orders the dictionary's suggestions by how often each word is used in the journal if the preferences ask for it,
+    /// otherwise they are kept in the dictionary's order. words shorter than the minimum sort length, and words used
+    /// equally often, keep their dictionary order
+    fn order_suggestions(
+        state: &mut SharedAppState,
+        spell_suggestions: Vec<String>,
+    ) -> Vec<String> {
+        if !preferences().editor.sort_suggestions_by_usage {
+            return spell_suggestions;
+        }
+
+        state.global_store.update_word_count();
+
+        let min_sort_length = preferences().editor.suggestion_min_sort_length;
+
+        let mut sorted_suggestions: Vec<_> = spell_suggestions
+            .into_iter()
+            .map(|word| {
+                let word_count = if word.chars().count() >= min_sort_length {
+                    state.global_store.get_word_count(&word.to_lowercase())
+                } else {
+                    0
+                };
+
+                (word_count, word)
+            })
+            .collect();
+
+        // the sort is stable, so sorting by the reversed count keeps ties in the dictionary's order
+        sorted_suggestions.sort_by_key(|(word_count, _word)| std::cmp::Reverse(*word_count));
+
+        sorted_suggestions
+            .into_iter()
+            .map(|(_word_count, word)| word)
+            .collect()
+    }
+
     /// Parses the editor text into their format for rendering
     fn parse_markdown(&mut self, state: &SharedAppState) {
         self.editor_markdown =
             markdown_image::parse(&state.content.text(), &mut self.markdown_image_cache);
     }
 
+    /// adds the search to the front of the search history, removing any older duplicates of it. the history is bounded
+    /// to the most recent searches, and is written to the preferences if the history is set to persist
+    fn push_search_history(&mut self, search: String) {
+        const MAX_SEARCH_HISTORY: usize = 10;
+
+        let search = search.trim().to_string();
+
+        if search.is_empty() {
+            return;
+        }
+
+        self.search_history
+            .retain(|past_search| *past_search != search);
+        self.search_history.insert(0, search);
+        self.search_history.truncate(MAX_SEARCH_HISTORY);
+
+        // the preferences file is never encrypted, so searches through an encrypted journal are only kept in memory
+        if preferences().search.persist_search_history && !journal_cipher::encryption_enabled() {
+            preferences_mut().search.search_history = self.search_history.clone();
+            preferences().write_to_disk();
+        }
+    }
+
+    /// finds the positions, in characters, of every non-overlapping occurrence of the search text in the text. the
+    /// matching is the same as the search highlighting, so every highlighted occurrence is found
+    fn find_matches(text: &str, search_text: &str, ignore_case: bool) -> Vec<Position> {
+        text.split('\n')
+            .enumerate()
+            .flat_map(|(line_index, line)| {
+                SpellHighlighter::search_matches(line, search_text, ignore_case)
+                    .into_iter()
+                    .map(move |match_range| Position {
+                        line: line_index,
+                        column: line[..match_range.start].chars().count(),
+                    })
+            })
+            .collect()
+    }
+
+    /// replaces every occurrence of the search text in the text. returns the replaced text, the number of replacements,
+    /// and the position just after the last replacement, if anything was replaced
+    fn replace_matches(
+        text: &str,
+        search_text: &str,
+        replacement: &str,
+        ignore_case: bool,
+    ) -> (String, usize, Option<Position>) {
+        let matches = Self::find_matches(text, search_text, ignore_case);
+
+        let search_length = search_text.chars().count();
+        let replacement_length = replacement.chars().count();
+
+        let mut last_replacement_end = None;
+
+        let replaced_lines: Vec<String> = text
+            .split('\n')
+            .enumerate()
+            .map(|(line_index, line)| {
+                let line_chars: Vec<char> = line.chars().collect();
+
+                let mut replaced_line = String::new();
+                let mut replaced_line_length = 0;
+                let mut column = 0;
+
+                for match_position in matches
+                    .iter()
+                    .filter(|match_position| match_position.line == line_index)
+                {
+                    replaced_line.extend(&line_chars[column..match_position.column]);
+                    replaced_line.push_str(replacement);
+
+                    replaced_line_length += (match_position.column - column) + replacement_length;
+                    column = match_position.column + search_length;
+
+                    last_replacement_end = Some(Position {
+                        line: line_index,
+                        column: replaced_line_length,
+                    });
+                }
+
+                replaced_line.extend(&line_chars[column..]);
+
+                replaced_line
+            })
+            .collect();
+
+        (
+            replaced_lines.join("\n"),
+            matches.len(),
+            last_replacement_end,
+        )
+    }
+
     fn recompute_search(&mut self, state: &mut SharedAppState) {
+        state.global_store.load_all();
+
         self.search_table.clear();
         self.search_text.clear();
+        self.search_result_index = None;
 
         let search_text = if preferences().search.ignore_search_case {
             self.search_content.text().to_lowercase()
@@ -1279,14 +3082,64 @@ impl Main {
             self.search_content.text()
         };
 
-        if search_text.is_empty() || search_text == " " {
+        let tag_filter = self.tag_filter_content.text().trim().to_string();
+
+        let has_search_text = !(search_text.is_empty() || search_text == " ");
+
+        if !has_search_text && tag_filter.is_empty() {
             return;
         }
 
+        if has_search_text {
+            // the highlighter is given the query itself rather than a matched piece of text, so every occurrence in
+            // the displayed entry is highlighted no matter which day the matches came from
+            self.search_text = self.search_content.text();
+        }
+
+        // tags only belong to entries, so task names are only searched without a tag filter
+        if has_search_text && tag_filter.is_empty() {
+            self.search_task_names(state, &search_text);
+        }
+
+        // the index rules out the days that can't contain the search text, so only the rest have to be scanned
+        let candidate_dates = if has_search_text {
+            state.global_store.search_candidates(&search_text)
+        } else {
+            None
+        };
+
         for month_store in state.global_store.month_stores().rev() {
             for day_store in month_store.days().rev() {
+                if !tag_filter.is_empty() && !day_store.has_tag(&tag_filter) {
+                    continue;
+                }
+
+                if let Some(candidate_dates) = &candidate_dates
+                    && !candidate_dates.contains(&day_store.date())
+                {
+                    continue;
+                }
+
                 let original_content_text = day_store.get_day_text();
 
+                // with only a tag filter, every tagged day is a result, shown with the start of its entry
+                if !has_search_text {
+                    let preview_text = original_content_text
+                        .chars()
+                        .take(50)
+                        .collect::<String>()
+                        .replace("\n", " ");
+
+                    self.search_table.insert_element(
+                        day_store.date().to_string() + " ... ",
+                        String::new(),
+                        preview_text + " ...",
+                        day_store.date(),
+                    );
+
+                    continue;
+                }
+
                 let content_text = if preferences().search.ignore_search_case {
                     original_content_text.to_lowercase()
                 } else {
@@ -1324,8 +3177,6 @@ impl Main {
                         + " ...")
                         .replace("\n", " ");
 
-                    self.search_text = bolded_text.clone();
-
                     self.search_table.insert_element(
                         start_text,
                         bolded_text,
@@ -1337,6 +3188,89 @@ impl Main {
         }
     }
 
+    /// adds a result for every template task whose name contains the search text, leading to the first date the task
+    /// was scheduled on
+    fn search_task_names(&mut self, state: &SharedAppState, search_text: &str) {
+        let ignore_case = preferences().search.ignore_search_case;
+
+        for (task_name, first_date) in state.task_manager.template_tasks.template_first_dates() {
+            let compared_name = if ignore_case {
+                task_name.to_lowercase()
+            } else {
+                task_name.clone()
+            };
+
+            let Some(match_idx) = compared_name.find(search_text) else {
+                continue;
+            };
+
+            let match_end_idx = match_idx + search_text.len();
+
+            let (Some(start_text), Some(bolded_text), Some(end_text)) = (
+                task_name.get(..match_idx),
+                task_name.get(match_idx..match_end_idx),
+                task_name.get(match_end_idx..),
+            ) else {
+                continue;
+            };
+
+            self.search_table.insert_task_element(
+                format!("Task: {}", start_text),
+                bolded_text.to_string(),
+                format!("{} (since {})", end_text, first_date),
+                first_date,
+            );
+        }
+    }
+
+    /// refreshes the month strip with the most recent months in the store and their word counts
+    fn update_month_strip(&mut self, state: &SharedAppState) {
+        let month_strip_length = preferences().general.month_strip_length;
+
+        let mut months: Vec<(NaiveDate, usize)> = state
+            .global_store
+            .month_stores()
+            .rev()
+            .take(month_strip_length)
+            .map(|month_store| (month_store.first_of_month(), month_store.total_word_count()))
+            .collect();
+
+        months.reverse();
+
+        let current_month = state
+            .global_store
+            .current_date()
+            .with_day(1)
+            .expect("invalid first of month");
+
+        self.month_strip.set_months(months, current_month);
+    }
+
+    /// dims the calender days that have fewer words than the word filter threshold. if there is no threshold, no days
+    /// are dimmed
+    fn update_word_filter(&mut self, state: &SharedAppState) {
+        let mut dimmed_days = [false; 42];
+
+        if let Some(word_threshold) = self.word_filter_threshold {
+            let mut iterative_date = self.calender.calender_start_date();
+
+            for dimmed_day in dimmed_days.iter_mut() {
+                let day_word_count = state
+                    .global_store
+                    .get_day(iterative_date)
+                    .map_or(0, |day_store| day_store.total_word_count());
+
+                *dimmed_day = day_word_count < word_threshold;
+
+                iterative_date = iterative_date
+                    .checked_add_days(Days::new(1))
+                    .expect("couldn't add day");
+            }
+        }
+
+        self.calender.set_dimmed_days(dimmed_days);
+    }
+
     /// maps the word counts for all days of the calender into the corresponding colormap
     fn compute_word_count_colormap(&self, state: &SharedAppState) -> CalenderColormap {
         let mut char_counts = [0; 42];
@@ -1361,9 +3295,448 @@ impl Main {
 
         CalenderColormap {
             colormap_weights,
-            color_floor: LIGHT.char_count_floor,
-            color_ceiling: LIGHT.char_count_ceiling,
+            color_floor: preferred_journal_theme().char_count_floor,
+            color_ceiling: preferred_journal_theme().char_count_ceiling,
             current_day_overwrite: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{journal_pointer, with_test_preferences};
+    use crate::store::journal_storage::MemoryStorage;
+    use crate::tasks::template_tasks::{
+        Frequency, StandardTaskTemplate, TemplateTask, TemplateTasks,
+    };
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("invalid date")
+    }
+
+    /// state whose journal holds the given entries, with the current date left on the last entry
+    fn state_with_entries(entries: &[(NaiveDate, &str)]) -> SharedAppState {
+        let mut state = SharedAppState::with_storage(Box::new(MemoryStorage::default()));
+
+        for (entry_date, entry_text) in entries {
+            state.global_store.set_current_store_date(*entry_date);
+            state
+                .global_store
+                .day_mut()
+                .set_day_text(entry_text.to_string());
+        }
+
+        state
+    }
+
+    fn suggestions(words: &[&str]) -> Vec<String> {
+        words.iter().map(|word| word.to_string()).collect()
+    }
+
+    #[test]
+    fn suggestions_are_sorted_by_usage_when_enabled() {
+        with_test_preferences(
+            |preferences| {
+                preferences.editor.sort_suggestions_by_usage = true;
+                preferences.editor.suggestion_min_sort_length = 0;
+            },
+            || {
+                let mut state =
+                    state_with_entries(&[(date(2024, 5, 1), "there and there and their")]);
+
+                let ordered_suggestions =
+                    Main::order_suggestions(&mut state, suggestions(&["three", "their", "there"]));
+
+                assert_eq!(
+                    ordered_suggestions,
+                    suggestions(&["there", "their", "three"])
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn suggestions_keep_the_dictionary_order_when_sorting_is_disabled() {
+        with_test_preferences(
+            |preferences| preferences.editor.sort_suggestions_by_usage = false,
+            || {
+                let mut state =
+                    state_with_entries(&[(date(2024, 5, 1), "there and there and their")]);
+
+                let ordered_suggestions =
+                    Main::order_suggestions(&mut state, suggestions(&["three", "their", "there"]));
+
+                assert_eq!(
+                    ordered_suggestions,
+                    suggestions(&["three", "their", "there"])
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn short_suggestions_are_not_sorted_by_usage() {
+        with_test_preferences(
+            |preferences| {
+                preferences.editor.sort_suggestions_by_usage = true;
+                preferences.editor.suggestion_min_sort_length = 5;
+            },
+            || {
+                let mut state = state_with_entries(&[(date(2024, 5, 1), "tha tha tha there")]);
+
+                let ordered_suggestions =
+                    Main::order_suggestions(&mut state, suggestions(&["the", "tha", "there"]));
+
+                assert_eq!(ordered_suggestions, suggestions(&["there", "the", "tha"]));
+            },
+        );
+    }
+
+    /// types the text into the active entry one character at a time, the way it would be typed with the keyboard
+    fn type_text(state: &mut SharedAppState, text: &str) {
+        for chara in text.chars() {
+            state.content.perform(ContentAction::Standard(Action::Edit(
+                text_editor::Edit::Insert(chara),
+            )));
+        }
+    }
+
+    #[test]
+    fn today_button_keeps_the_history_when_already_on_today() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let mut state = state_with_entries(&[]);
+                let mut main = Main::default();
+
+                let _task = main.update(&mut state, MainMessage::LoadToday);
+
+                type_text(&mut state, "unsaved words");
+                let undo_stack_height = state.content.undo_stack_height();
+
+                let _task = main.update(&mut state, MainMessage::JumpToToday);
+
+                assert_eq!(state.global_store.current_date(), Local::now().date_naive());
+                assert_eq!(state.content.text().trim_end(), "unsaved words");
+                assert!(undo_stack_height > 0);
+                assert_eq!(state.content.undo_stack_height(), undo_stack_height);
+            },
+        );
+    }
+
+    #[test]
+    fn loading_today_reads_the_entry_from_the_store() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let today = Local::now().date_naive();
+                let yesterday = today.pred_opt().expect("no yesterday");
+
+                let mut state = state_with_entries(&[
+                    (today, "today's entry"),
+                    (yesterday, "yesterday's entry"),
+                ]);
+                state.content = UpgradedContent::with_text("yesterday's entry");
+
+                let mut main = Main::default();
+
+                let _task = main.update(&mut state, MainMessage::LoadToday);
+
+                assert_eq!(state.global_store.current_date(), today);
+                assert_eq!(state.content.text().trim_end(), "today's entry");
+            },
+        );
+    }
+
+    #[test]
+    fn autosave_persists_a_toggled_task() {
+        with_test_preferences(
+            |preferences| {
+                preferences.paths.journal_path = journal_pointer::test_dir().join("autosave-tasks");
+            },
+            || {
+                fs::create_dir_all(preferences().paths.template_tasks_dir())
+                    .expect("couldn't create the template tasks dir");
+                fs::create_dir_all(preferences().paths.event_tasks_dir())
+                    .expect("couldn't create the event tasks dir");
+
+                let today = Local::now().date_naive();
+                let mut state = state_with_entries(&[]);
+                let mut main = Main::default();
+
+                let template_tasks = &mut state.task_manager.template_tasks;
+                template_tasks.create_task(TemplateTask::new(
+                    "Stretch".to_string(),
+                    today,
+                    Frequency::Daily,
+                    TemplateData::Standard(StandardTaskTemplate::default()),
+                ));
+                template_tasks.generate_template_entries(today);
+                state.task_manager.save_all();
+
+                let task_id = state
+                    .task_manager
+                    .template_tasks
+                    .get_active_template_ids(today)[0];
+                state
+                    .task_manager
+                    .template_tasks
+                    .toggle_completion(today, task_id);
+                assert!(state.task_manager.has_unsaved_changes());
+
+                let _task = main.update(&mut state, MainMessage::Autosave);
+
+                assert!(!state.task_manager.has_unsaved_changes());
+
+                let saved_items = TemplateTasks::load_templates().scheduled_agenda_items(today);
+                assert_eq!(saved_items.len(), 1);
+                assert!(saved_items[0].completed());
+            },
+        );
+    }
+
+    #[test]
+    fn the_agenda_is_only_recomputed_when_tasks_change() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let mut state = state_with_entries(&[]);
+                let mut main = Main::default();
+                let today = state.global_store.current_date();
+
+                let template_tasks = &mut state.task_manager.template_tasks;
+                template_tasks.create_task(TemplateTask::new(
+                    "Stretch".to_string(),
+                    today,
+                    Frequency::Daily,
+                    TemplateData::Standard(StandardTaskTemplate::default()),
+                ));
+                template_tasks.generate_template_entries(today);
+
+                let _task = main.update(&mut state, MainMessage::TabSwitched(Tab::Agenda));
+                assert_eq!(main.agenda.scheduled.len(), 1);
+                assert!(!main.agenda.scheduled[0].completed());
+
+                let task_id = state
+                    .task_manager
+                    .template_tasks
+                    .get_active_template_ids(today)[0];
+                state.task_manager.toggle_completion(today, task_id);
+
+                let _task = main.update(&mut state, MainMessage::MouseMoved(Point::ORIGIN));
+                assert!(!main.agenda.scheduled[0].completed());
+
+                let _task =
+                    main.update(&mut state, MainMessage::WindowEvent(window::Event::Focused));
+                assert!(main.agenda.scheduled[0].completed());
+            },
+        );
+    }
+
+    #[test]
+    fn saving_keeps_the_cursor_in_place() {
+        with_test_preferences(
+            |preferences| {
+                preferences.paths.journal_path = journal_pointer::test_dir().join("save-cursor");
+            },
+            || {
+                fs::create_dir_all(preferences().paths.event_tasks_dir())
+                    .expect("couldn't create the event tasks dir");
+
+                let entry_text = "first line\nsecond line";
+                let mut state = state_with_entries(&[(date(2024, 5, 1), entry_text)]);
+                state.content = UpgradedContent::with_text(entry_text);
+                state
+                    .content
+                    .move_cursor_to(Position { line: 1, column: 3 });
+
+                let mut main = Main::default();
+
+                let _task = main.update(&mut state, MainMessage::KeyEvent(KeyboardAction::Save));
+
+                assert_eq!(state.content.cursor_line(), 1);
+                assert_eq!(state.content.cursor_column(), 3);
+                assert_eq!(state.content.text().trim_end(), entry_text);
+                assert!(!state.global_store.has_unsaved_changes());
+            },
+        );
+    }
+
+    #[test]
+    fn visiting_a_day_and_leaving_it_blank_writes_nothing() {
+        with_test_preferences(
+            |preferences| {
+                preferences.paths.journal_path = journal_pointer::test_dir().join("blank-visit");
+            },
+            || {
+                fs::create_dir_all(preferences().paths.event_tasks_dir())
+                    .expect("couldn't create the event tasks dir");
+
+                let storage = MemoryStorage::default();
+                let mut state = SharedAppState::with_storage(Box::new(storage.clone()));
+                let mut main = Main::default();
+
+                state.global_store.set_current_store_date(date(2024, 5, 1));
+                state.content = UpgradedContent::with_text("");
+
+                main.save_all(&mut state);
+
+                assert!(!state.global_store.has_unsaved_changes());
+                assert!(storage.months().is_empty());
+            },
+        );
+    }
+
+    #[test]
+    fn locked_entries_are_not_offered_for_clearing() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let mut state = state_with_entries(&[(date(2024, 5, 1), "a locked entry")]);
+                state.global_store.day_mut().set_locked(true);
+
+                let mut main = Main::default();
+                let clear_entry = MainMessage::MenuBar(MenuMessage::Edit(EditMessage::ClearEntry));
+
+                let _task = main.update(&mut state, clear_entry.clone());
+                assert!(state.upstream_actions.is_empty());
+
+                state.global_store.day_mut().set_locked(false);
+
+                let _task = main.update(&mut state, clear_entry);
+                assert!(matches!(
+                    state.upstream_actions.as_slice(),
+                    [UpstreamAction::OpenDialog(
+                        DialogType::Confirmation(ConfirmedAction::ClearEntry(_)),
+                        _
+                    )]
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn reverting_marks_the_day_saved_and_keeps_other_drafts() {
+        with_test_preferences(
+            |preferences| {
+                preferences.paths.journal_path = journal_pointer::test_dir().join("revert-entry");
+            },
+            || {
+                let _ = fs::remove_dir_all(preferences().paths.savedata_dir());
+                fs::create_dir_all(preferences().paths.savedata_dir())
+                    .expect("couldn't create the savedata dir");
+
+                let mut state = state_with_entries(&[
+                    (date(2024, 5, 1), "other day"),
+                    (date(2024, 5, 2), "saved text"),
+                ]);
+                state.global_store.day_mut().mark_saved();
+
+                let mut main = Main::default();
+                main.load_active_entry(&mut state);
+
+                type_text(&mut state, "unsaved ");
+                main.write_active_entry_to_store(&mut state);
+
+                Draft::new(date(2024, 5, 1), "other day, edited".to_string())
+                    .write_to_disk()
+                    .expect("couldn't write draft");
+                Draft::new(date(2024, 5, 2), state.content.text())
+                    .write_to_disk()
+                    .expect("couldn't write draft");
+
+                let _task = main.update(
+                    &mut state,
+                    MainMessage::PerformConfirmed(ConfirmedAction::RevertEntry(date(2024, 5, 2))),
+                );
+
+                assert_eq!(state.content.text().trim_end(), "saved text");
+                assert!(!state.global_store.day().unsaved());
+                assert_eq!(
+                    Draft::load_from_disk(),
+                    vec![Draft::new(
+                        date(2024, 5, 1),
+                        "other day, edited".to_string()
+                    )]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn jumping_far_from_today_asks_first() {
+        with_test_preferences(
+            |preferences| preferences.general.far_date_confirmation_days = 365,
+            || {
+                let mut state = state_with_entries(&[]);
+                let mut main = Main::default();
+                let starting_date = state.global_store.current_date();
+
+                for jump_date_text in ["24-05-01", "1024-05-01"] {
+                    main.jump_date_content = UpgradedContent::with_text(jump_date_text);
+                    let _task = main.update(&mut state, MainMessage::JumpToDate);
+                }
+
+                assert_eq!(state.global_store.current_date(), starting_date);
+                assert!(matches!(
+                    state.upstream_actions.as_slice(),
+                    [UpstreamAction::OpenDialog(
+                        DialogType::Confirmation(ConfirmedAction::NavigateToDate(far_date)),
+                        _
+                    )] if *far_date == date(1024, 5, 1)
+                ));
+            },
+        );
+    }
+
+    #[test]
+    fn find_matches_ignores_case_when_asked() {
+        let text = "Cat cat\nCAT";
+
+        assert_eq!(
+            Main::find_matches(text, "cat", true),
+            vec![
+                Position { line: 0, column: 0 },
+                Position { line: 0, column: 4 },
+                Position { line: 1, column: 0 },
+            ]
+        );
+        assert_eq!(
+            Main::find_matches(text, "cat", false),
+            vec![Position { line: 0, column: 4 }]
+        );
+    }
+
+    #[test]
+    fn find_matches_counts_columns_in_characters() {
+        // the multibyte characters before each match would put the columns off if they were counted in bytes
+        assert_eq!(
+            Main::find_matches("été café, ÉTÉ", "été", true),
+            vec![
+                Position { line: 0, column: 0 },
+                Position {
+                    line: 0,
+                    column: 10
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn replacing_keeps_later_matches_in_place() {
+        let (replaced_text, replacement_count, last_replacement_end) =
+            Main::replace_matches("aXa\nbaé a", "a", "ää", false);
+
+        assert_eq!(replaced_text, "ääXää\nbääé ää");
+        assert_eq!(replacement_count, 4);
+        assert_eq!(last_replacement_end, Some(Position { line: 1, column: 7 }));
+
+        // the replacement contains the search text, but isn't searched again
+        let (replaced_text, replacement_count, _last_replacement_end) =
+            Main::replace_matches("cat", "cat", "catcat", true);
+
+        assert_eq!(replaced_text, "catcat");
+        assert_eq!(replacement_count, 1);
+    }
+}
@@ -1,6 +1,8 @@
+pub mod diagnostics_window;
 pub mod file_export_window;
 pub mod file_import_window;
 pub mod main_window;
 pub mod preferences_window;
+pub mod quick_capture_window;
 pub mod task_creator_window;
 pub mod window_manager;
@@ -1,19 +1,28 @@
 use super::window_manager::{WindowType, Windowable};
 use crate::{
     SharedAppState, UpstreamAction,
-    config::{UserPreferences, overwrite_preferences, preferences},
+    config::{
+        EditorAlignment, JsonFormat, SaveNotification, SearchEnterAction, StorageBackend,
+        ThemePreference, UserPreferences, overwrite_preferences, preferences, preferences_mut,
+    },
     content::{ContentAction, Restriction, UpgradedContent},
     custom_widgets::file_picker::{FilePicker, FilePickerMessage},
     custom_widgets::tabview::{TabviewItem, tabview_content_horizontal},
-    keyboard_manager::KeyboardAction,
+    keyboard_manager::{BindableAction, KeyboardAction, conflicting_bindings},
+    store::{Draft, journal_cipher},
     utils::file_extensions::{AFF_EXT_LIST, DIC_EXT_LIST, JSON_EXT_LIST, build_extensions},
+    utils::logbox::logbox_mut,
+    utils::summon_hotkey::parse_hotkey,
 };
 
 use iced::{
-    Length, Task,
-    widget::{self, Space, Text, button, checkbox, column, row, text_editor::Action},
+    Element, Length, Task,
+    widget::{
+        self, Space, Text, button, checkbox, column, pick_list, row, text_editor::Action,
+        text_input,
+    },
 };
-use std::time::Duration;
+use std::{fs, io, path::PathBuf, time::Duration};
 use strum::Display;
 
 #[derive(Debug, Default, Clone, PartialEq, Display)]
@@ -22,6 +31,7 @@ pub enum PreferencesTab {
     General,
     Paths,
     Keyboard,
+    Snippets,
 }
 
 impl PreferencesTab {
@@ -30,6 +40,7 @@ impl PreferencesTab {
             PreferencesTab::General => 0,
             PreferencesTab::Paths => 1,
             PreferencesTab::Keyboard => 2,
+            PreferencesTab::Snippets => 3,
         }
     }
 }
@@ -39,7 +50,43 @@ pub enum GeneralMessage {
     ToggleAutosave(bool),
     EditAutosaveMinute(Action),
     EditAutosaveSecond(Action),
+    ToggleSaveAfterSentence(bool),
+    ToggleBackups(bool),
+    EditBackupInterval(Action),
+    EditBackupRetention(Action),
+    EditBackupRetentionDays(Action),
     ToggleSmartNavigation,
+    ToggleTodayScroll(bool),
+    ToggleCompactCalender,
+    ToggleCalenderWordCounts(bool),
+    EditFarDateDays(Action),
+    EditMonthStripLength(Action),
+    ToggleLargePasteConfirmation(bool),
+    ToggleLineNumbers(bool),
+    ToggleListContinuation(bool),
+    TogglePasteImages(bool),
+    SelectTextAlignment(EditorAlignment),
+    TogglePersistSearchHistory(bool),
+    SelectSearchEnterAction(SearchEnterAction),
+    ToggleExcludeWeekends(bool),
+    ToggleIncludeEmptyDays(bool),
+    EditDailyWordGoal(Action),
+    EditDailyCharGoal(Action),
+    EditLargePasteThreshold(Action),
+    ToggleSuggestionSorting(bool),
+    EditSuggestionMinLength(Action),
+    EditHistoryCacheDays(Action),
+    EditSpellcheckLengthLimit(Action),
+    EditExportHeader(Action),
+    EditExportFooter(Action),
+    SelectTheme(ThemePreference),
+    ToggleAutoExpandTasks(bool),
+    ToggleResetTaskCreator(bool),
+    SelectSaveNotification(SaveNotification),
+    ToggleMinimizeToTray(bool),
+    ToggleSummonHotkey(bool),
+    EditSummonHotkey(Action),
+    EditQuickCaptureTimestamp(Action),
 }
 
 #[derive(Debug, Clone)]
@@ -49,6 +96,31 @@ pub enum PathsMessage {
     SystemDic(FilePickerMessage),
     SystemAff(FilePickerMessage),
     PersonalDic(FilePickerMessage),
+    ExportDirectory(FilePickerMessage),
+    ToggleMovePersonalDic(bool),
+    SelectStorageBackend(StorageBackend),
+    SelectJsonFormat(JsonFormat),
+    EditEncryptionPassword(String),
+    EditEncryptionPasswordConfirmation(String),
+    EncryptJournal,
+    DecryptJournal,
+}
+
+#[derive(Debug, Clone)]
+pub enum SnippetsMessage {
+    EditName(Action),
+    EditText(Action),
+    Add,
+    Remove(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum KeybindsMessage {
+    StartRebind(BindableAction),
+    CancelRebind,
+    BindingCaptured(String),
+    ResetBinding(BindableAction),
+    ResetAllBindings,
 }
 
 #[derive(Debug, Clone)]
@@ -62,18 +134,39 @@ pub enum PreferencesMessage {
 
     General(GeneralMessage),
     Paths(PathsMessage),
+    Keybinds(KeybindsMessage),
+    Snippets(SnippetsMessage),
 }
 
 #[derive(Debug)]
 pub enum ActiveContent {
     AutosaveMinute,
     AutosaveSecond,
+    LargePasteThreshold,
+    SuggestionMinLength,
+    HistoryCacheDays,
+    SpellcheckLengthLimit,
+    DailyWordGoal,
+    DailyCharGoal,
+    FarDateDays,
+    MonthStripLength,
+    BackupInterval,
+    BackupRetention,
+    BackupRetentionDays,
+    ExportHeader,
+    ExportFooter,
+    SummonHotkey,
+    QuickCaptureTimestamp,
 
     JournalPath,
     PreferencesPath,
     SystemDicPath,
     SystemAffPath,
     PersonalDicPath,
+    ExportDirectoryPath,
+
+    SnippetName,
+    SnippetText,
 }
 
 #[derive(Debug)]
@@ -81,6 +174,7 @@ pub struct Preferences {
     working_preferences: UserPreferences,
     edited_preferences: bool,
     preference_edit_requires_restart: bool,
+    preference_edit_requires_reload: bool,
 
     current_preference_tab: PreferencesTab,
     active_content: Option<ActiveContent>,
@@ -90,11 +184,46 @@ pub struct Preferences {
     autosave_second_content: UpgradedContent,
     autosave_seconds: u64,
 
+    large_paste_threshold_content: UpgradedContent,
+    suggestion_min_length_content: UpgradedContent,
+    history_cache_days_content: UpgradedContent,
+    spellcheck_length_limit_content: UpgradedContent,
+
+    backup_interval_content: UpgradedContent,
+    backup_retention_content: UpgradedContent,
+    backup_retention_days_content: UpgradedContent,
+
+    daily_word_goal_content: UpgradedContent,
+    far_date_days_content: UpgradedContent,
+    month_strip_length_content: UpgradedContent,
+    daily_char_goal_content: UpgradedContent,
+
+    export_header_content: UpgradedContent,
+    export_footer_content: UpgradedContent,
+
+    summon_hotkey_content: UpgradedContent,
+    quick_capture_timestamp_content: UpgradedContent,
+
     journal_path_picker: FilePicker,
     preferences_path_picker: FilePicker,
     system_dic_path_picker: FilePicker,
     system_aff_path_picker: FilePicker,
     personal_dic_path_picker: FilePicker,
+    export_directory_picker: FilePicker,
+    move_personal_dictionary: bool,
+
+    /// the password the journal gets encrypted with. it is cleared as soon as it has been used
+    encryption_password: String,
+
+    /// the password typed a second time. a mistyped password can never be recovered, so the journal is only encrypted
+    /// if both match
+    encryption_password_confirmation: String,
+
+    /// the action waiting for its new binding to be pressed, if any
+    rebinding_action: Option<BindableAction>,
+
+    snippet_name_content: UpgradedContent,
+    snippet_text_content: UpgradedContent,
 }
 
 impl Default for Preferences {
@@ -105,6 +234,7 @@ impl Default for Preferences {
             working_preferences: working_preferences.clone(),
             edited_preferences: false,
             preference_edit_requires_restart: false,
+            preference_edit_requires_reload: false,
 
             current_preference_tab: PreferencesTab::default(),
             active_content: None,
@@ -114,6 +244,67 @@ impl Default for Preferences {
             autosave_second_content: UpgradedContent::with_text("0"),
             autosave_seconds: 0,
 
+            large_paste_threshold_content: UpgradedContent::with_text(
+                &working_preferences.editor.large_paste_threshold.to_string(),
+            ),
+            suggestion_min_length_content: UpgradedContent::with_text(
+                &working_preferences
+                    .editor
+                    .suggestion_min_sort_length
+                    .to_string(),
+            ),
+            history_cache_days_content: UpgradedContent::with_text(
+                &working_preferences.editor.history_cache_days.to_string(),
+            ),
+            spellcheck_length_limit_content: UpgradedContent::with_text(
+                &working_preferences
+                    .editor
+                    .spellcheck_length_limit
+                    .to_string(),
+            ),
+
+            backup_interval_content: UpgradedContent::with_text(
+                &(working_preferences.general.backup_interval.as_secs() / 3600).to_string(),
+            ),
+            backup_retention_content: UpgradedContent::with_text(
+                &working_preferences
+                    .general
+                    .backup_retention_count
+                    .to_string(),
+            ),
+            backup_retention_days_content: UpgradedContent::with_text(
+                &working_preferences
+                    .general
+                    .backup_retention_days
+                    .to_string(),
+            ),
+
+            far_date_days_content: UpgradedContent::with_text(
+                &working_preferences
+                    .general
+                    .far_date_confirmation_days
+                    .to_string(),
+            ),
+            month_strip_length_content: UpgradedContent::with_text(
+                &working_preferences.general.month_strip_length.to_string(),
+            ),
+            daily_word_goal_content: UpgradedContent::with_text(&goal_text(
+                working_preferences.stats.daily_word_goal,
+            )),
+            daily_char_goal_content: UpgradedContent::with_text(&goal_text(
+                working_preferences.stats.daily_char_goal,
+            )),
+
+            export_header_content: UpgradedContent::with_text(&working_preferences.export.header),
+            export_footer_content: UpgradedContent::with_text(&working_preferences.export.footer),
+
+            summon_hotkey_content: UpgradedContent::with_text(
+                &working_preferences.general.summon_hotkey,
+            ),
+            quick_capture_timestamp_content: UpgradedContent::with_text(
+                &working_preferences.general.quick_capture_timestamp,
+            ),
+
             journal_path_picker: FilePicker::directory(working_preferences.paths.journal_path),
             preferences_path_picker: FilePicker::file(
                 working_preferences.paths.preferences_path,
@@ -131,6 +322,22 @@ impl Default for Preferences {
                 working_preferences.paths.personal_dictionary_dic,
                 &build_extensions(DIC_EXT_LIST),
             ),
+            export_directory_picker: FilePicker::directory(
+                working_preferences
+                    .export
+                    .default_directory
+                    .clone()
+                    .unwrap_or_default(),
+            ),
+            move_personal_dictionary: true,
+
+            encryption_password: String::new(),
+            encryption_password_confirmation: String::new(),
+
+            rebinding_action: None,
+
+            snippet_name_content: UpgradedContent::default(),
+            snippet_text_content: UpgradedContent::default(),
         }
     }
 }
@@ -185,7 +392,141 @@ impl Windowable<PreferencesMessage> for Preferences {
                 autosave_second_editor
             ];
 
-            let autosave = column![autosave_checkbox, autosave_time];
+            let sentence_save_checkbox = checkbox(general_prefs.save_after_sentence)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleSaveAfterSentence(checked))
+                })
+                .label("Auto save after finishing a sentence");
+
+            let save_notification_picker = pick_list(
+                SaveNotification::ALL,
+                Some(general_prefs.save_notification),
+                |save_notification| {
+                    PreferencesMessage::General(GeneralMessage::SelectSaveNotification(
+                        save_notification,
+                    ))
+                },
+            );
+
+            let save_notification =
+                row![Text::new("Confirm saves with: "), save_notification_picker];
+
+            let autosave = column![
+                autosave_checkbox,
+                autosave_time,
+                sentence_save_checkbox,
+                save_notification
+            ];
+
+            let backups_checkbox = checkbox(general_prefs.backups_enabled)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleBackups(checked))
+                })
+                .label("Periodically back up the journal");
+
+            let backup_interval_editor = if general_prefs.backups_enabled {
+                widget::text_editor(self.backup_interval_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditBackupInterval(action))
+                    })
+                    .width(60)
+            } else {
+                widget::text_editor(self.backup_interval_content.raw_content()).width(60)
+            };
+
+            let backup_retention_editor = if general_prefs.backups_enabled {
+                widget::text_editor(self.backup_retention_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditBackupRetention(action))
+                    })
+                    .width(60)
+            } else {
+                widget::text_editor(self.backup_retention_content.raw_content()).width(60)
+            };
+
+            let backup_retention_days_editor = if general_prefs.backups_enabled {
+                widget::text_editor(self.backup_retention_days_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditBackupRetentionDays(action))
+                    })
+                    .width(60)
+            } else {
+                widget::text_editor(self.backup_retention_days_content.raw_content()).width(60)
+            };
+
+            let backup_schedule = row![
+                Space::new().width(SUB_OPTION_SPACE_WIDTH),
+                Text::new("Every "),
+                backup_interval_editor,
+                Text::new(" hours, keeping the "),
+                backup_retention_editor,
+                Text::new(" most recent backups")
+            ];
+
+            let backup_age_limit = row![
+                Space::new().width(SUB_OPTION_SPACE_WIDTH),
+                Text::new("Delete backups older than "),
+                backup_retention_days_editor,
+                Text::new(" days (0 to keep them regardless of age)")
+            ];
+
+            let backups = column![backups_checkbox, backup_schedule, backup_age_limit];
+
+            let theme_picker =
+                pick_list(ThemePreference::ALL, Some(general_prefs.theme), |theme| {
+                    PreferencesMessage::General(GeneralMessage::SelectTheme(theme))
+                });
+
+            let theme = row![Text::new("Theme: "), theme_picker];
+
+            let tray_checkbox = checkbox(general_prefs.minimize_to_tray)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleMinimizeToTray(checked))
+                })
+                .label("Keep running in the system tray when the main window is closed");
+
+            let summon_hotkey_checkbox = checkbox(general_prefs.summon_hotkey_enabled)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleSummonHotkey(checked))
+                })
+                .label("Bring ironnote to the front with a global hotkey");
+
+            let summon_hotkey_editor =
+                widget::text_editor(self.summon_hotkey_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditSummonHotkey(action))
+                    })
+                    .width(200);
+
+            let summon_hotkey_status = if parse_hotkey(&general_prefs.summon_hotkey).is_ok() {
+                Text::new("")
+            } else {
+                Text::new(" Not a valid hotkey")
+            };
+
+            let quick_capture_timestamp_editor =
+                widget::text_editor(self.quick_capture_timestamp_content.raw_content())
+                    .placeholder("No timestamp")
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditQuickCaptureTimestamp(
+                            action,
+                        ))
+                    })
+                    .width(200);
+
+            let quick_capture_timestamp = row![
+                Text::new("Quick capture timestamp format: "),
+                quick_capture_timestamp_editor
+            ];
+
+            let hotkey = column![
+                summon_hotkey_checkbox,
+                row![
+                    Text::new("Hotkey: "),
+                    summon_hotkey_editor,
+                    summon_hotkey_status
+                ]
+            ];
 
             let smart_nav_text = Text::new("Smart navigation");
 
@@ -195,9 +536,287 @@ impl Windowable<PreferencesMessage> for Preferences {
                 })
                 .label("Enable smart navigation");
 
-            let smart_navigation = column![smart_nav_text, smart_nav_checkbox];
+            let today_scroll_checkbox = checkbox(general_prefs.today_scrolls_to_top)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleTodayScroll(checked))
+                })
+                .label("Scroll to the top when pressing Today while already on today");
+
+            let smart_navigation =
+                column![smart_nav_text, smart_nav_checkbox, today_scroll_checkbox];
+
+            let compact_calender_checkbox = checkbox(general_prefs.compact_calender)
+                .on_toggle(|_checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleCompactCalender)
+                })
+                .label("Only show the weeks needed for each month in the calender");
+
+            let far_date_editor = widget::text_editor(self.far_date_days_content.raw_content())
+                .on_action(|action| {
+                    PreferencesMessage::General(GeneralMessage::EditFarDateDays(action))
+                })
+                .width(80);
+
+            let calender_word_counts_checkbox = checkbox(general_prefs.calender_word_counts)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleCalenderWordCounts(checked))
+                })
+                .label("Show each day's word count on the calender");
+
+            let far_date = row![
+                Text::new("Confirm going to a new month more than "),
+                far_date_editor,
+                Text::new(" days from today (0 to never confirm)")
+            ];
+
+            let month_strip_length = row![
+                Text::new("Recent months shown above the calender (0 to hide): "),
+                widget::text_editor(self.month_strip_length_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditMonthStripLength(action))
+                    })
+                    .width(50)
+            ];
+
+            let calender = column![
+                Text::new("Calender"),
+                compact_calender_checkbox,
+                calender_word_counts_checkbox,
+                far_date,
+                month_strip_length
+            ];
+
+            let auto_expand_checkbox = checkbox(general_prefs.auto_expand_tasks)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleAutoExpandTasks(checked))
+                })
+                .label("Expand tasks that have notes on the current day");
+
+            let reset_task_creator_checkbox = checkbox(general_prefs.reset_task_creator_on_reopen)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleResetTaskCreator(checked))
+                })
+                .label("Clear the new task form when it is opened again while already open");
+
+            let tasks = column![
+                Text::new("Tasks"),
+                auto_expand_checkbox,
+                reset_task_creator_checkbox
+            ];
+
+            let editor_prefs = &self.working_preferences.editor;
+
+            let large_paste_checkbox = checkbox(editor_prefs.confirm_large_pastes)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleLargePasteConfirmation(
+                        checked,
+                    ))
+                })
+                .label("Confirm before pasting large amounts of text");
+
+            let large_paste_editor = if editor_prefs.confirm_large_pastes {
+                widget::text_editor(self.large_paste_threshold_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditLargePasteThreshold(action))
+                    })
+                    .width(80)
+            } else {
+                widget::text_editor(self.large_paste_threshold_content.raw_content()).width(80)
+            };
+
+            let large_paste_threshold = row![
+                Space::new().width(SUB_OPTION_SPACE_WIDTH),
+                Text::new("Characters"),
+                large_paste_editor
+            ];
+
+            let line_numbers_checkbox = checkbox(editor_prefs.show_line_numbers)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleLineNumbers(checked))
+                })
+                .label("Show line numbers (disables line wrapping)");
+
+            let list_continuation_checkbox = checkbox(editor_prefs.continue_lists)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleListContinuation(checked))
+                })
+                .label("Continue lists when pressing Enter");
+
+            let paste_images_checkbox = checkbox(editor_prefs.paste_images_as_attachments)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::TogglePasteImages(checked))
+                })
+                .label("Save pasted images as attachments");
+
+            let text_alignment_picker = pick_list(
+                EditorAlignment::ALL,
+                Some(editor_prefs.text_alignment),
+                |text_alignment| {
+                    PreferencesMessage::General(GeneralMessage::SelectTextAlignment(text_alignment))
+                },
+            );
+
+            let text_alignment = row![Text::new("Align entry text: "), text_alignment_picker];
+
+            let suggestion_sorting_checkbox = checkbox(editor_prefs.sort_suggestions_by_usage)
+                .on_toggle(|checked| {
+                    PreferencesMessage::General(GeneralMessage::ToggleSuggestionSorting(checked))
+                })
+                .label("Sort spellcheck suggestions by how often you use them");
+
+            let suggestion_min_length_editor = if editor_prefs.sort_suggestions_by_usage {
+                widget::text_editor(self.suggestion_min_length_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditSuggestionMinLength(action))
+                    })
+                    .width(50)
+            } else {
+                widget::text_editor(self.suggestion_min_length_content.raw_content()).width(50)
+            };
+
+            let suggestion_min_length = row![
+                Space::new().width(SUB_OPTION_SPACE_WIDTH),
+                Text::new("Minimum word length"),
+                suggestion_min_length_editor
+            ];
+
+            let history_cache_days = row![
+                Text::new("Keep the undo history of recently visited days: "),
+                widget::text_editor(self.history_cache_days_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditHistoryCacheDays(action))
+                    })
+                    .width(50)
+            ];
+
+            let spellcheck_length_limit = row![
+                Text::new("Only spellcheck near the cursor in entries longer than (characters): "),
+                widget::text_editor(self.spellcheck_length_limit_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditSpellcheckLengthLimit(
+                            action,
+                        ))
+                    })
+                    .width(80)
+            ];
+
+            let editor = column![
+                Text::new("Editor"),
+                large_paste_checkbox,
+                large_paste_threshold,
+                line_numbers_checkbox,
+                list_continuation_checkbox,
+                paste_images_checkbox,
+                text_alignment,
+                suggestion_sorting_checkbox,
+                suggestion_min_length,
+                history_cache_days,
+                spellcheck_length_limit
+            ];
+
+            let persist_search_checkbox =
+                checkbox(self.working_preferences.search.persist_search_history)
+                    .on_toggle(|checked| {
+                        PreferencesMessage::General(GeneralMessage::TogglePersistSearchHistory(
+                            checked,
+                        ))
+                    })
+                    .label("Remember recent searches between sessions");
+
+            let search_enter_picker = pick_list(
+                SearchEnterAction::ALL,
+                Some(self.working_preferences.search.enter_action),
+                |enter_action| {
+                    PreferencesMessage::General(GeneralMessage::SelectSearchEnterAction(
+                        enter_action,
+                    ))
+                },
+            );
+
+            let search_enter = row![
+                Text::new("Pressing Enter in the search bar: "),
+                search_enter_picker
+            ];
+
+            let search = column![Text::new("Search"), persist_search_checkbox, search_enter];
+
+            let exclude_weekends_checkbox =
+                checkbox(self.working_preferences.stats.exclude_weekends)
+                    .on_toggle(|checked| {
+                        PreferencesMessage::General(GeneralMessage::ToggleExcludeWeekends(checked))
+                    })
+                    .label("Exclude weekends from streaks and averages");
+
+            let include_empty_days_checkbox =
+                checkbox(self.working_preferences.stats.include_empty_days)
+                    .on_toggle(|checked| {
+                        PreferencesMessage::General(GeneralMessage::ToggleIncludeEmptyDays(checked))
+                    })
+                    .label("Include days without an entry in averages");
+
+            let word_goal_editor = widget::text_editor(self.daily_word_goal_content.raw_content())
+                .placeholder("None")
+                .on_action(|action| {
+                    PreferencesMessage::General(GeneralMessage::EditDailyWordGoal(action))
+                })
+                .width(80);
+
+            let char_goal_editor = widget::text_editor(self.daily_char_goal_content.raw_content())
+                .placeholder("None")
+                .on_action(|action| {
+                    PreferencesMessage::General(GeneralMessage::EditDailyCharGoal(action))
+                })
+                .width(80);
+
+            let stats = column![
+                Text::new("Stats"),
+                exclude_weekends_checkbox,
+                include_empty_days_checkbox,
+                row![Text::new("Daily word goal: "), word_goal_editor],
+                row![Text::new("Daily character goal: "), char_goal_editor]
+            ];
+
+            let export_header_editor =
+                widget::text_editor(self.export_header_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditExportHeader(action))
+                    })
+                    .height(60);
+
+            let export_footer_editor =
+                widget::text_editor(self.export_footer_content.raw_content())
+                    .on_action(|action| {
+                        PreferencesMessage::General(GeneralMessage::EditExportFooter(action))
+                    })
+                    .height(60);
+
+            let export = column![
+                Text::new("Export"),
+                Text::new("Header added before each exported entry"),
+                export_header_editor,
+                Text::new("Footer added after each exported entry"),
+                export_footer_editor,
+                Text::new(
+                    "{date}, {weekday}, {day}, {month}, and {year} are replaced with the entry's date"
+                )
+            ];
 
-            column![title, autosave, smart_navigation]
+            column![
+                title,
+                theme,
+                tray_checkbox,
+                hotkey,
+                quick_capture_timestamp,
+                autosave,
+                backups,
+                smart_navigation,
+                calender,
+                tasks,
+                editor,
+                search,
+                stats,
+                export
+            ]
         };
 
         let general_tab = TabviewItem {
@@ -214,8 +833,101 @@ impl Windowable<PreferencesMessage> for Preferences {
                 .journal_path_picker
                 .view()
                 .map(|message| PreferencesMessage::Paths(PathsMessage::Journal(message)));
-            let journal_location =
-                column![Text::new("Journal Save Location"), journal_location_path];
+            // the personal dictionary defaults to living inside the journal, so it would be left behind when the
+            // journal moves unless it is brought along
+            let move_personal_dic_checkbox =
+                self.relocated_personal_dictionary().map(|_new_path| {
+                    checkbox(self.move_personal_dictionary)
+                        .on_toggle(|checked| {
+                            PreferencesMessage::Paths(PathsMessage::ToggleMovePersonalDic(checked))
+                        })
+                        .label("Copy the personal dictionary to the new journal location")
+                });
+
+            let storage_backend_picker = pick_list(
+                StorageBackend::ALL,
+                Some(self.working_preferences.paths.storage_backend),
+                |storage_backend| {
+                    PreferencesMessage::Paths(PathsMessage::SelectStorageBackend(storage_backend))
+                },
+            );
+
+            let storage_backend = row![Text::new("Store entries as: "), storage_backend_picker];
+
+            let json_format_picker = pick_list(
+                JsonFormat::ALL,
+                Some(self.working_preferences.paths.json_format),
+                |json_format| {
+                    PreferencesMessage::Paths(PathsMessage::SelectJsonFormat(json_format))
+                },
+            );
+
+            let json_format = row![Text::new("Entry JSON format: "), json_format_picker];
+
+            let journal_location = column![
+                Text::new("Journal Save Location"),
+                journal_location_path,
+                move_personal_dic_checkbox,
+                storage_backend,
+                json_format
+            ];
+
+            let encryption_enabled = journal_cipher::encryption_enabled();
+
+            let encryption_status = if encryption_enabled {
+                "The journal is encrypted with a password"
+            } else {
+                "The journal is stored as plain text"
+            };
+
+            let encryption_password_input = (!encryption_enabled).then(|| {
+                text_input("Password", &self.encryption_password)
+                    .secure(true)
+                    .on_input(|password| {
+                        PreferencesMessage::Paths(PathsMessage::EditEncryptionPassword(password))
+                    })
+                    .width(200)
+            });
+
+            let encryption_password_confirmation_input = (!encryption_enabled).then(|| {
+                text_input("Confirm password", &self.encryption_password_confirmation)
+                    .secure(true)
+                    .on_input(|password| {
+                        PreferencesMessage::Paths(PathsMessage::EditEncryptionPasswordConfirmation(
+                            password,
+                        ))
+                    })
+                    .width(200)
+            });
+
+            let passwords_mismatched = !self.encryption_password_confirmation.is_empty()
+                && self.encryption_password_confirmation != self.encryption_password;
+
+            let password_mismatch_warning = (!encryption_enabled && passwords_mismatched)
+                .then(|| Text::new("The passwords don't match"));
+
+            let encryption_button = if encryption_enabled {
+                button(Text::new("Decrypt Journal")).on_press_maybe(
+                    (!journal_cipher::journal_locked())
+                        .then_some(PreferencesMessage::Paths(PathsMessage::DecryptJournal)),
+                )
+            } else {
+                button(Text::new("Encrypt Journal")).on_press_maybe(
+                    self.encryption_passwords_match()
+                        .then_some(PreferencesMessage::Paths(PathsMessage::EncryptJournal)),
+                )
+            };
+
+            let journal_encryption = column![
+                Text::new("Journal Encryption"),
+                Text::new(encryption_status),
+                row![
+                    encryption_password_input,
+                    encryption_password_confirmation_input,
+                    encryption_button
+                ],
+                password_mismatch_warning
+            ];
 
             let preferences_path_editor = self
                 .preferences_path_picker
@@ -244,13 +956,24 @@ impl Windowable<PreferencesMessage> for Preferences {
                 .map(|message| PreferencesMessage::Paths(PathsMessage::PersonalDic(message)));
             let personal_dic = column![Text::new("Personal Dictionary .dic"), personal_dic_path];
 
+            let export_directory_path = self
+                .export_directory_picker
+                .view()
+                .map(|message| PreferencesMessage::Paths(PathsMessage::ExportDirectory(message)));
+            let export_directory = column![
+                Text::new("Default Export Directory (updated after each export)"),
+                export_directory_path
+            ];
+
             column![
                 title,
                 journal_location,
+                journal_encryption,
                 preferences_path,
                 system_dic,
                 system_aff,
-                personal_dic
+                personal_dic,
+                export_directory
             ]
             .into()
         };
@@ -262,7 +985,68 @@ impl Windowable<PreferencesMessage> for Preferences {
             overlay: None,
         };
 
-        let keyboard_tab_content = { column![Text::new("Keyboard Settings")] };
+        let keyboard_tab_content = {
+            let title = Text::new("Keyboard Settings");
+
+            let custom_bindings = &self.working_preferences.keyboard.custom_bindings;
+
+            let binding_rows = BindableAction::ALL
+                .iter()
+                .map(|action| {
+                    let rebind_button = if self.rebinding_action == Some(*action) {
+                        button(Text::new("Press a key..."))
+                            .on_press(PreferencesMessage::Keybinds(KeybindsMessage::CancelRebind))
+                    } else {
+                        button(Text::new("Rebind")).on_press(PreferencesMessage::Keybinds(
+                            KeybindsMessage::StartRebind(*action),
+                        ))
+                    };
+
+                    let reset_button = button(Text::new("Reset")).on_press_maybe(
+                        custom_bindings.contains_key(action).then_some(
+                            PreferencesMessage::Keybinds(KeybindsMessage::ResetBinding(*action)),
+                        ),
+                    );
+
+                    row![
+                        Text::new(action.to_string()).width(300),
+                        Text::new(action.binding(custom_bindings)).width(200),
+                        rebind_button,
+                        reset_button
+                    ]
+                    .into()
+                })
+                .collect::<Vec<Element<PreferencesMessage>>>();
+
+            let conflict_warnings = conflicting_bindings(custom_bindings)
+                .into_iter()
+                .map(|(binding, actions)| {
+                    let action_names = actions
+                        .iter()
+                        .map(|action| action.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ");
+
+                    Text::new(format!(
+                        "Warning: {binding} is bound to more than one action: {action_names}"
+                    ))
+                    .into()
+                })
+                .collect::<Vec<Element<PreferencesMessage>>>();
+
+            let reset_all_button = button(Text::new("Reset to Defaults")).on_press_maybe(
+                (!custom_bindings.is_empty()).then_some(PreferencesMessage::Keybinds(
+                    KeybindsMessage::ResetAllBindings,
+                )),
+            );
+
+            column![
+                title,
+                widget::Column::with_children(binding_rows),
+                widget::Column::with_children(conflict_warnings),
+                reset_all_button
+            ]
+        };
 
         let keyboard_tab = TabviewItem {
             title: PreferencesTab::Keyboard.to_string(),
@@ -271,7 +1055,53 @@ impl Windowable<PreferencesMessage> for Preferences {
             overlay: None,
         };
 
-        let tab_elements = vec![general_tab, paths_tab, keyboard_tab];
+        let snippets_tab_content = {
+            let title = Text::new("Snippets");
+
+            let existing_snippets = self.working_preferences.editor.snippets.iter().map(
+                |(snippet_name, snippet_text)| {
+                    let preview = snippet_text.lines().next().unwrap_or_default().to_string();
+
+                    row![
+                        Text::new(snippet_name.clone()).width(150),
+                        Text::new(preview).width(Length::Fill),
+                        button(Text::new("Remove")).on_press(PreferencesMessage::Snippets(
+                            SnippetsMessage::Remove(snippet_name.clone())
+                        ))
+                    ]
+                    .into()
+                },
+            );
+
+            let snippet_list = widget::scrollable(column(existing_snippets)).height(Length::Fill);
+
+            let name_editor = widget::text_editor(self.snippet_name_content.raw_content())
+                .placeholder("Snippet name")
+                .on_action(|action| {
+                    PreferencesMessage::Snippets(SnippetsMessage::EditName(action))
+                });
+
+            let text_editor = widget::text_editor(self.snippet_text_content.raw_content())
+                .placeholder("Snippet text")
+                .on_action(|action| PreferencesMessage::Snippets(SnippetsMessage::EditText(action)))
+                .height(80);
+
+            let add_button = button(Text::new("Add Snippet")).on_press_maybe(
+                (!self.snippet_name().is_empty())
+                    .then_some(PreferencesMessage::Snippets(SnippetsMessage::Add)),
+            );
+
+            column![title, snippet_list, name_editor, text_editor, add_button]
+        };
+
+        let snippets_tab = TabviewItem {
+            title: PreferencesTab::Snippets.to_string(),
+            clicked_message: PreferencesMessage::TabSwitched(PreferencesTab::Snippets),
+            content: snippets_tab_content.into(),
+            overlay: None,
+        };
+
+        let tab_elements = vec![general_tab, paths_tab, keyboard_tab, snippets_tab];
 
         let preference_editor = tabview_content_horizontal(
             tab_elements,
@@ -314,6 +1144,15 @@ impl Windowable<PreferencesMessage> for Preferences {
                     }
                 }
                 KeyboardAction::Debug => {}
+                KeyboardAction::OpenPreferences => {}
+                KeyboardAction::NewTask => {}
+                KeyboardAction::SearchCursorWord => {}
+                KeyboardAction::Paste => {}
+                KeyboardAction::RevertEntry => {}
+                KeyboardAction::JumpToDate => {}
+                KeyboardAction::SwitchFocusRegion => {}
+                KeyboardAction::ToggleFocusMode => {}
+                KeyboardAction::TaskNavigation(_task_navigation) => {}
                 KeyboardAction::Unbound(_unbound_key) => {}
             },
             PreferencesMessage::TabSwitched(new_preferences_tab) => {
@@ -389,48 +1228,418 @@ impl Windowable<PreferencesMessage> for Preferences {
 
                     self.edited_preferences = true;
                 }
-            },
-            PreferencesMessage::Paths(paths_message) => {
-                match paths_message {
-                    PathsMessage::Journal(message) => {
-                        self.active_content =
-                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
-                                .then_some(ActiveContent::JournalPath);
+                GeneralMessage::ToggleTodayScroll(is_checked) => {
+                    self.working_preferences.general.today_scrolls_to_top = is_checked;
 
-                        self.journal_path_picker.update(message);
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleSaveAfterSentence(is_checked) => {
+                    self.working_preferences.general.save_after_sentence = is_checked;
 
-                        self.working_preferences.paths.journal_path =
-                            self.journal_path_picker.path();
-                    }
-                    PathsMessage::Preferences(message) => {
-                        self.active_content =
-                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
-                                .then_some(ActiveContent::PreferencesPath);
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleLineNumbers(is_checked) => {
+                    self.working_preferences.editor.show_line_numbers = is_checked;
 
-                        self.preferences_path_picker.update(message);
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::TogglePasteImages(is_checked) => {
+                    self.working_preferences.editor.paste_images_as_attachments = is_checked;
 
-                        self.working_preferences.paths.preferences_path =
-                            self.preferences_path_picker.path();
-                    }
-                    PathsMessage::SystemDic(message) => {
-                        self.active_content =
-                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
-                                .then_some(ActiveContent::SystemDicPath);
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::SelectTextAlignment(text_alignment) => {
+                    self.working_preferences.editor.text_alignment = text_alignment;
 
-                        self.system_dic_path_picker.update(message);
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleListContinuation(is_checked) => {
+                    self.working_preferences.editor.continue_lists = is_checked;
 
-                        self.working_preferences.paths.system_dictionary_dic =
-                            self.system_dic_path_picker.path();
-                    }
-                    PathsMessage::SystemAff(message) => {
-                        self.active_content =
-                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
-                                .then_some(ActiveContent::SystemAffPath);
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditFarDateDays(action) => {
+                    self.active_content = Some(ActiveContent::FarDateDays);
+
+                    self.far_date_days_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    let days_text = self.far_date_days_content.text();
+                    let days = days_text.parse::<usize>().unwrap_or(0).min(99999999);
+
+                    // prevents leading 0s and enforces the max days, same as the other number editors
+                    if self.far_date_days_content.text() != days.to_string() {
+                        self.far_date_days_content = UpgradedContent::with_text(&days.to_string())
+                    }
+
+                    self.working_preferences.general.far_date_confirmation_days = days;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditMonthStripLength(action) => {
+                    self.active_content = Some(ActiveContent::MonthStripLength);
+
+                    self.month_strip_length_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    let length_text = self.month_strip_length_content.text();
+                    let strip_length = length_text.parse::<usize>().unwrap_or(0).min(99);
+
+                    if self.month_strip_length_content.text() != strip_length.to_string() {
+                        self.month_strip_length_content =
+                            UpgradedContent::with_text(&strip_length.to_string())
+                    }
+
+                    self.working_preferences.general.month_strip_length = strip_length;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleBackups(checked) => {
+                    self.working_preferences.general.backups_enabled = checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditBackupInterval(action) => {
+                    self.active_content = Some(ActiveContent::BackupInterval);
+
+                    self.backup_interval_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    // a backup every 0 hours would never stop, so the interval is at least an hour
+                    let hours_text = self.backup_interval_content.text();
+                    let hours = hours_text.parse::<u64>().unwrap_or(0).clamp(1, 9999);
+
+                    if self.backup_interval_content.text() != hours.to_string() {
+                        self.backup_interval_content =
+                            UpgradedContent::with_text(&hours.to_string())
+                    }
+
+                    self.working_preferences.general.backup_interval = Duration::from_hours(hours);
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditBackupRetention(action) => {
+                    self.active_content = Some(ActiveContent::BackupRetention);
+
+                    self.backup_retention_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    // the backup just made is always kept, so at least 1 backup is retained
+                    let count_text = self.backup_retention_content.text();
+                    let count = count_text.parse::<usize>().unwrap_or(0).clamp(1, 9999);
+
+                    if self.backup_retention_content.text() != count.to_string() {
+                        self.backup_retention_content =
+                            UpgradedContent::with_text(&count.to_string())
+                    }
+
+                    self.working_preferences.general.backup_retention_count = count;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditBackupRetentionDays(action) => {
+                    self.active_content = Some(ActiveContent::BackupRetentionDays);
+
+                    self.backup_retention_days_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    let days_text = self.backup_retention_days_content.text();
+                    let days = days_text.parse::<usize>().unwrap_or(0).min(9999);
+
+                    if self.backup_retention_days_content.text() != days.to_string() {
+                        self.backup_retention_days_content =
+                            UpgradedContent::with_text(&days.to_string())
+                    }
+
+                    self.working_preferences.general.backup_retention_days = days;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleCompactCalender => {
+                    self.working_preferences.general.compact_calender =
+                        !self.working_preferences.general.compact_calender;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleCalenderWordCounts(checked) => {
+                    self.working_preferences.general.calender_word_counts = checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::TogglePersistSearchHistory(is_checked) => {
+                    self.working_preferences.search.persist_search_history = is_checked;
+
+                    if !is_checked {
+                        self.working_preferences.search.search_history.clear();
+                    }
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::SelectTheme(theme) => {
+                    self.working_preferences.general.theme = theme;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleAutoExpandTasks(is_checked) => {
+                    self.working_preferences.general.auto_expand_tasks = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleResetTaskCreator(is_checked) => {
+                    self.working_preferences
+                        .general
+                        .reset_task_creator_on_reopen = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::SelectSaveNotification(save_notification) => {
+                    self.working_preferences.general.save_notification = save_notification;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleMinimizeToTray(is_checked) => {
+                    self.working_preferences.general.minimize_to_tray = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleSummonHotkey(is_checked) => {
+                    self.working_preferences.general.summon_hotkey_enabled = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditSummonHotkey(action) => {
+                    self.active_content = Some(ActiveContent::SummonHotkey);
+
+                    self.summon_hotkey_content
+                        .perform(ContentAction::Restricted((Restriction::NoNewlines, action)));
+
+                    self.working_preferences.general.summon_hotkey =
+                        self.summon_hotkey_content.text().trim().to_string();
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditQuickCaptureTimestamp(action) => {
+                    self.active_content = Some(ActiveContent::QuickCaptureTimestamp);
+
+                    self.quick_capture_timestamp_content
+                        .perform(ContentAction::Restricted((Restriction::NoNewlines, action)));
+
+                    self.working_preferences.general.quick_capture_timestamp =
+                        self.quick_capture_timestamp_content.text();
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::SelectSearchEnterAction(enter_action) => {
+                    self.working_preferences.search.enter_action = enter_action;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleExcludeWeekends(is_checked) => {
+                    self.working_preferences.stats.exclude_weekends = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleIncludeEmptyDays(is_checked) => {
+                    self.working_preferences.stats.include_empty_days = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditDailyWordGoal(action) => {
+                    self.active_content = Some(ActiveContent::DailyWordGoal);
+
+                    self.working_preferences.stats.daily_word_goal =
+                        edit_goal(&mut self.daily_word_goal_content, action);
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditDailyCharGoal(action) => {
+                    self.active_content = Some(ActiveContent::DailyCharGoal);
+
+                    self.working_preferences.stats.daily_char_goal =
+                        edit_goal(&mut self.daily_char_goal_content, action);
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleLargePasteConfirmation(is_checked) => {
+                    self.working_preferences.editor.confirm_large_pastes = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditLargePasteThreshold(action) => {
+                    self.active_content = Some(ActiveContent::LargePasteThreshold);
+
+                    self.large_paste_threshold_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    let threshold_text = self.large_paste_threshold_content.text();
+                    let threshold = threshold_text.parse::<usize>().unwrap_or(0).min(99999999);
+
+                    // prevents leading 0s and enforces the max threshold, same as the autosave editors
+                    if self.large_paste_threshold_content.text() != threshold.to_string() {
+                        self.large_paste_threshold_content =
+                            UpgradedContent::with_text(&threshold.to_string())
+                    }
+
+                    self.working_preferences.editor.large_paste_threshold = threshold;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::ToggleSuggestionSorting(is_checked) => {
+                    self.working_preferences.editor.sort_suggestions_by_usage = is_checked;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditSuggestionMinLength(action) => {
+                    self.active_content = Some(ActiveContent::SuggestionMinLength);
+
+                    self.suggestion_min_length_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    let length_text = self.suggestion_min_length_content.text();
+                    let min_length = length_text.parse::<usize>().unwrap_or(0).min(99);
+
+                    if self.suggestion_min_length_content.text() != min_length.to_string() {
+                        self.suggestion_min_length_content =
+                            UpgradedContent::with_text(&min_length.to_string())
+                    }
+
+                    self.working_preferences.editor.suggestion_min_sort_length = min_length;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditHistoryCacheDays(action) => {
+                    self.active_content = Some(ActiveContent::HistoryCacheDays);
+
+                    self.history_cache_days_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    let days_text = self.history_cache_days_content.text();
+                    let cache_days = days_text.parse::<usize>().unwrap_or(0).min(99);
+
+                    if self.history_cache_days_content.text() != cache_days.to_string() {
+                        self.history_cache_days_content =
+                            UpgradedContent::with_text(&cache_days.to_string())
+                    }
+
+                    self.working_preferences.editor.history_cache_days = cache_days;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditSpellcheckLengthLimit(action) => {
+                    self.active_content = Some(ActiveContent::SpellcheckLengthLimit);
+
+                    self.spellcheck_length_limit_content
+                        .perform(ContentAction::Restricted((
+                            Restriction::NumbersOnly,
+                            action,
+                        )));
+
+                    let limit_text = self.spellcheck_length_limit_content.text();
+                    let length_limit = limit_text.parse::<usize>().unwrap_or(0).min(99999999);
+
+                    if self.spellcheck_length_limit_content.text() != length_limit.to_string() {
+                        self.spellcheck_length_limit_content =
+                            UpgradedContent::with_text(&length_limit.to_string())
+                    }
+
+                    self.working_preferences.editor.spellcheck_length_limit = length_limit;
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditExportHeader(action) => {
+                    self.active_content = Some(ActiveContent::ExportHeader);
+
+                    self.export_header_content
+                        .perform(ContentAction::Standard(action));
+
+                    self.working_preferences.export.header = self.export_header_content.text();
+
+                    self.edited_preferences = true;
+                }
+                GeneralMessage::EditExportFooter(action) => {
+                    self.active_content = Some(ActiveContent::ExportFooter);
+
+                    self.export_footer_content
+                        .perform(ContentAction::Standard(action));
+
+                    self.working_preferences.export.footer = self.export_footer_content.text();
+
+                    self.edited_preferences = true;
+                }
+            },
+            PreferencesMessage::Paths(paths_message) => {
+                match paths_message {
+                    PathsMessage::Journal(message) => {
+                        self.active_content =
+                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
+                                .then_some(ActiveContent::JournalPath);
+
+                        self.journal_path_picker.update(message);
+
+                        self.working_preferences.paths.journal_path =
+                            self.journal_path_picker.path();
+
+                        self.preference_edit_requires_reload = true;
+                    }
+                    PathsMessage::Preferences(message) => {
+                        self.active_content =
+                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
+                                .then_some(ActiveContent::PreferencesPath);
+
+                        self.preferences_path_picker.update(message);
+
+                        self.working_preferences.paths.preferences_path =
+                            self.preferences_path_picker.path();
+
+                        self.preference_edit_requires_restart = true;
+                    }
+                    PathsMessage::SystemDic(message) => {
+                        self.active_content =
+                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
+                                .then_some(ActiveContent::SystemDicPath);
+
+                        self.system_dic_path_picker.update(message);
+
+                        self.working_preferences.paths.system_dictionary_dic =
+                            self.system_dic_path_picker.path();
+
+                        self.preference_edit_requires_reload = true;
+                    }
+                    PathsMessage::SystemAff(message) => {
+                        self.active_content =
+                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
+                                .then_some(ActiveContent::SystemAffPath);
 
                         self.system_aff_path_picker.update(message);
 
                         self.working_preferences.paths.system_dictionary_aff =
                             self.system_aff_path_picker.path();
+
+                        self.preference_edit_requires_reload = true;
                     }
                     PathsMessage::PersonalDic(message) => {
                         self.active_content =
@@ -441,12 +1650,193 @@ impl Windowable<PreferencesMessage> for Preferences {
 
                         self.working_preferences.paths.personal_dictionary_dic =
                             self.personal_dic_path_picker.path();
+
+                        self.preference_edit_requires_reload = true;
+                    }
+                    PathsMessage::ExportDirectory(message) => {
+                        self.active_content =
+                            matches!(&message, FilePickerMessage::FilepathEdit(_content_action))
+                                .then_some(ActiveContent::ExportDirectoryPath);
+
+                        self.export_directory_picker.update(message);
+
+                        let export_directory = self.export_directory_picker.path();
+
+                        self.working_preferences.export.default_directory =
+                            (!export_directory.as_os_str().is_empty()).then_some(export_directory);
+                    }
+                    PathsMessage::ToggleMovePersonalDic(is_checked) => {
+                        self.move_personal_dictionary = is_checked;
+                    }
+                    PathsMessage::SelectStorageBackend(storage_backend) => {
+                        self.working_preferences.paths.storage_backend = storage_backend;
+
+                        // the entries are moved into the new backend when the journal is reloaded
+                        self.preference_edit_requires_reload = true;
+                    }
+                    PathsMessage::SelectJsonFormat(json_format) => {
+                        self.working_preferences.paths.json_format = json_format;
+                    }
+                    // encryption is applied to the journal right away rather than being saved as a preference
+                    PathsMessage::EditEncryptionPassword(password) => {
+                        self.encryption_password = password;
+
+                        return Task::none();
+                    }
+                    PathsMessage::EditEncryptionPasswordConfirmation(password) => {
+                        self.encryption_password_confirmation = password;
+
+                        return Task::none();
+                    }
+                    PathsMessage::EncryptJournal => {
+                        if !self.encryption_passwords_match() {
+                            return Task::none();
+                        }
+
+                        let password = std::mem::take(&mut self.encryption_password);
+                        self.encryption_password_confirmation.clear();
+
+                        if let Err(encryption_error) = journal_cipher::enable_encryption(&password)
+                        {
+                            logbox_mut().log(&format!(
+                                "Couldn't encrypt the journal: {}",
+                                encryption_error
+                            ));
+
+                            return Task::none();
+                        }
+
+                        // searches can hold anything written in the journal, so they are no longer kept in the
+                        // plain text preferences file
+                        self.working_preferences.search.search_history.clear();
+                        preferences_mut().search.search_history.clear();
+                        preferences().write_to_disk();
+
+                        if let Err(rewrite_error) = rewrite_journal_files(state) {
+                            logbox_mut().log(&format!(
+                                "Couldn't encrypt all of the journal, the rest is encrypted the next time it is \
+                                 saved: {}",
+                                rewrite_error
+                            ));
+                        }
+
+                        // anything unsaved is written encrypted along with the rest of the journal
+                        state.upstream_actions.push(UpstreamAction::Autosave);
+
+                        logbox_mut().log("Encrypted the journal");
+
+                        return Task::none();
+                    }
+                    PathsMessage::DecryptJournal => {
+                        if journal_cipher::journal_locked() {
+                            return Task::none();
+                        }
+
+                        // the key is still held, so the months encrypted on the disk can be read one last time
+                        if let Err(decryption_error) =
+                            journal_cipher::disable_encryption(|| rewrite_journal_files(state))
+                        {
+                            logbox_mut().log(&format!(
+                                "Couldn't decrypt the journal, so it is still encrypted: {}",
+                                decryption_error
+                            ));
+
+                            return Task::none();
+                        }
+
+                        journal_cipher::lock();
+
+                        logbox_mut().log("Decrypted the journal");
+
+                        return Task::none();
                     }
                 }
 
                 self.edited_preferences = true;
-                self.preference_edit_requires_restart = true;
             }
+            PreferencesMessage::Keybinds(keybinds_message) => match keybinds_message {
+                KeybindsMessage::StartRebind(action) => {
+                    self.active_content = None;
+
+                    self.rebinding_action = Some(action);
+                }
+                KeybindsMessage::CancelRebind => {
+                    self.rebinding_action = None;
+                }
+                KeybindsMessage::BindingCaptured(binding) => {
+                    if let Some(action) = self.rebinding_action.take() {
+                        let custom_bindings =
+                            &mut self.working_preferences.keyboard.custom_bindings;
+
+                        // rebinding back to the default is the same as never having rebound it
+                        if binding == action.default_binding() {
+                            custom_bindings.remove(&action);
+                        } else {
+                            custom_bindings.insert(action, binding);
+                        }
+
+                        self.edited_preferences = true;
+                    }
+                }
+                KeybindsMessage::ResetBinding(action) => {
+                    self.working_preferences
+                        .keyboard
+                        .custom_bindings
+                        .remove(&action);
+
+                    self.edited_preferences = true;
+                }
+                KeybindsMessage::ResetAllBindings => {
+                    self.rebinding_action = None;
+
+                    self.working_preferences.keyboard.custom_bindings.clear();
+
+                    self.edited_preferences = true;
+                }
+            },
+
+            PreferencesMessage::Snippets(snippets_message) => match snippets_message {
+                SnippetsMessage::EditName(action) => {
+                    self.active_content = Some(ActiveContent::SnippetName);
+
+                    self.snippet_name_content
+                        .perform(ContentAction::Restricted((Restriction::NoNewlines, action)));
+                }
+                SnippetsMessage::EditText(action) => {
+                    self.active_content = Some(ActiveContent::SnippetText);
+
+                    self.snippet_text_content
+                        .perform(ContentAction::Standard(action));
+                }
+                SnippetsMessage::Add => {
+                    self.active_content = None;
+
+                    let snippet_name = self.snippet_name();
+                    let snippet_text = self.snippet_text_content.text();
+
+                    if !snippet_name.is_empty() {
+                        self.working_preferences
+                            .editor
+                            .snippets
+                            .insert(snippet_name, snippet_text);
+
+                        self.snippet_name_content = UpgradedContent::default();
+                        self.snippet_text_content = UpgradedContent::default();
+
+                        self.edited_preferences = true;
+                    }
+                }
+                SnippetsMessage::Remove(snippet_name) => {
+                    self.active_content = None;
+
+                    self.working_preferences
+                        .editor
+                        .snippets
+                        .remove(&snippet_name);
+
+                    self.edited_preferences = true;
+                }
+            },
 
             PreferencesMessage::Cancel => {
                 state
@@ -454,15 +1844,36 @@ impl Windowable<PreferencesMessage> for Preferences {
                     .push(UpstreamAction::CloseWindow(WindowType::Preferences));
             }
             PreferencesMessage::Save => {
-                self.save_preferences();
+                if self.move_personal_dictionary
+                    && let Some(new_dictionary_path) = self.relocated_personal_dictionary()
+                {
+                    self.move_personal_dictionary_to(new_dictionary_path);
+                }
 
                 if self.preference_edit_requires_restart {
+                    self.save_preferences();
+
                     state.upstream_actions.push(UpstreamAction::Autosave);
 
                     state
                         .upstream_actions
                         .push(UpstreamAction::RestartApplication);
+                } else if self.preference_edit_requires_reload {
+                    // the current journal must be saved before the new paths take effect, so applying the
+                    // preferences is deferred to the reload itself
+                    state.upstream_actions.push(UpstreamAction::Autosave);
+
+                    state.upstream_actions.push(UpstreamAction::ReloadJournal(
+                        self.working_preferences.clone(),
+                    ));
+
+                    self.edited_preferences = false;
+                    self.preference_edit_requires_reload = false;
+                } else {
+                    self.save_preferences();
                 }
+
+                state.upstream_actions.push(UpstreamAction::ReloadKeybinds);
             }
             PreferencesMessage::SaveAndExit => {
                 let save_task = self.update(state, PreferencesMessage::Save);
@@ -483,6 +1894,25 @@ impl Windowable<PreferencesMessage> for Preferences {
             match active_content {
                 ActiveContent::AutosaveMinute => self.autosave_minute_content.perform(action),
                 ActiveContent::AutosaveSecond => self.autosave_second_content.perform(action),
+                ActiveContent::LargePasteThreshold => {
+                    self.large_paste_threshold_content.perform(action)
+                }
+                ActiveContent::SuggestionMinLength => {
+                    self.suggestion_min_length_content.perform(action)
+                }
+                ActiveContent::HistoryCacheDays => self.history_cache_days_content.perform(action),
+                ActiveContent::SpellcheckLengthLimit => {
+                    self.spellcheck_length_limit_content.perform(action)
+                }
+                ActiveContent::FarDateDays => self.far_date_days_content.perform(action),
+                ActiveContent::MonthStripLength => self.month_strip_length_content.perform(action),
+                ActiveContent::BackupInterval => self.backup_interval_content.perform(action),
+                ActiveContent::BackupRetention => self.backup_retention_content.perform(action),
+                ActiveContent::BackupRetentionDays => {
+                    self.backup_retention_days_content.perform(action)
+                }
+                ActiveContent::DailyWordGoal => self.daily_word_goal_content.perform(action),
+                ActiveContent::DailyCharGoal => self.daily_char_goal_content.perform(action),
                 ActiveContent::JournalPath => self
                     .journal_path_picker
                     .update(FilePickerMessage::FilepathEdit(action)),
@@ -498,12 +1928,51 @@ impl Windowable<PreferencesMessage> for Preferences {
                 ActiveContent::PersonalDicPath => self
                     .personal_dic_path_picker
                     .update(FilePickerMessage::FilepathEdit(action)),
+                ActiveContent::ExportDirectoryPath => self
+                    .export_directory_picker
+                    .update(FilePickerMessage::FilepathEdit(action)),
+                ActiveContent::SnippetName => self.snippet_name_content.perform(action),
+                ActiveContent::SnippetText => self.snippet_text_content.perform(action),
+                ActiveContent::ExportHeader => {
+                    self.export_header_content.perform(action);
+                    self.working_preferences.export.header = self.export_header_content.text();
+                    self.edited_preferences = true;
+                }
+                ActiveContent::ExportFooter => {
+                    self.export_footer_content.perform(action);
+                    self.working_preferences.export.footer = self.export_footer_content.text();
+                    self.edited_preferences = true;
+                }
+                ActiveContent::QuickCaptureTimestamp => {
+                    self.quick_capture_timestamp_content.perform(action);
+                    self.working_preferences.general.quick_capture_timestamp =
+                        self.quick_capture_timestamp_content.text();
+                    self.edited_preferences = true;
+                }
+                ActiveContent::SummonHotkey => {
+                    self.summon_hotkey_content.perform(action);
+                    self.working_preferences.general.summon_hotkey =
+                        self.summon_hotkey_content.text().trim().to_string();
+                    self.edited_preferences = true;
+                }
             }
         }
     }
 }
 
 impl Preferences {
+    /// true while a binding is being captured. key presses are given to the preferences as the new binding rather than
+    /// being dispatched as shortcuts
+    pub fn capturing_binding(&self) -> bool {
+        self.rebinding_action.is_some()
+    }
+
+    /// true if a password was entered for encrypting the journal, and typed the same way a second time
+    fn encryption_passwords_match(&self) -> bool {
+        !self.encryption_password.is_empty()
+            && self.encryption_password == self.encryption_password_confirmation
+    }
+
     /// copies the current working preferences as stored in the preference editor into the actual preferences. since
     /// the working preferences are now up to date with the actual ones, the current state is now "no preferences have
     /// been changed"
@@ -512,4 +1981,89 @@ impl Preferences {
 
         self.edited_preferences = false;
     }
+
+    /// if the journal path is being changed and the personal dictionary is inside the current journal, returns where the
+    /// dictionary would be inside the new journal. returns None if the personal dictionary path was changed by hand
+    fn relocated_personal_dictionary(&self) -> Option<PathBuf> {
+        let current_paths = preferences().paths.clone();
+        let working_paths = &self.working_preferences.paths;
+
+        if working_paths.journal_path == current_paths.journal_path
+            || working_paths.personal_dictionary_dic != current_paths.personal_dictionary_dic
+        {
+            return None;
+        }
+
+        let dictionary_in_journal = current_paths
+            .personal_dictionary_dic
+            .strip_prefix(&current_paths.journal_path)
+            .ok()?;
+
+        Some(working_paths.journal_path.join(dictionary_in_journal))
+    }
+
+    /// copies the personal dictionary to the new path and points the working preferences at it. an existing dictionary
+    /// at the new path is kept as is, rather than being overwritten
+    fn move_personal_dictionary_to(&mut self, new_dictionary_path: PathBuf) {
+        let current_dictionary_path = preferences().paths.personal_dictionary_dic.clone();
+
+        if current_dictionary_path.is_file() && !new_dictionary_path.exists() {
+            let copy_result = match new_dictionary_path.parent() {
+                Some(dictionary_dir) => fs::create_dir_all(dictionary_dir)
+                    .and_then(|_| fs::copy(&current_dictionary_path, &new_dictionary_path)),
+                None => fs::copy(&current_dictionary_path, &new_dictionary_path),
+            };
+
+            if copy_result.is_err() {
+                logbox_mut().log("Couldn't copy the personal dictionary to the new journal");
+
+                return;
+            }
+        }
+
+        self.personal_dic_path_picker =
+            FilePicker::file(new_dictionary_path.clone(), &build_extensions(DIC_EXT_LIST));
+        self.working_preferences.paths.personal_dictionary_dic = new_dictionary_path;
+    }
+
+    /// the name of the snippet being created, without any surrounding whitespace
+    fn snippet_name(&self) -> String {
+        self.snippet_name_content.text().trim().to_string()
+    }
+}
+
+/// the text shown in a goal editor. no goal is shown as an empty editor
+fn goal_text(goal: Option<usize>) -> String {
+    goal.map(|goal| goal.to_string()).unwrap_or_default()
+}
+
+/// applies the action to a goal editor and returns the goal it now holds. an empty editor or a goal of 0 means there is
+/// no goal
+fn edit_goal(goal_content: &mut UpgradedContent, action: Action) -> Option<usize> {
+    goal_content.perform(ContentAction::Restricted((
+        Restriction::NumbersOnly,
+        action,
+    )));
+
+    let goal = goal_content
+        .text()
+        .parse::<usize>()
+        .ok()
+        .map(|goal| goal.min(99999999))
+        .filter(|goal| *goal > 0);
+
+    // prevents leading 0s and enforces the max goal, same as the other number editors
+    if goal_content.text() != goal_text(goal) {
+        *goal_content = UpgradedContent::with_text(&goal_text(goal));
+    }
+
+    goal
+}
+
+/// writes every file of the journal back to the disk, so each is encrypted or decrypted to match the current encryption
+/// setting. fails if any file couldn't be rewritten
+fn rewrite_journal_files(state: &SharedAppState) -> io::Result<()> {
+    state.global_store.rewrite_storage()?;
+
+    Draft::rewrite_on_disk()
 }
@@ -0,0 +1,120 @@
+use super::window_manager::{WindowType, Windowable};
+use crate::{
+    SharedAppState, UpstreamAction,
+    content::{ContentAction, UpgradedContent},
+    keyboard_manager::KeyboardAction,
+};
+
+use iced::{
+    Length, Task,
+    widget::{self, Space, Text, button, column, row, text_editor::Action},
+};
+
+#[derive(Debug, Clone)]
+pub enum QuickCaptureMessage {
+    KeyEvent(KeyboardAction),
+
+    EditCapture(Action),
+    AppendToToday,
+    Close,
+}
+
+#[derive(Debug, Default)]
+/// a small always on top window for jotting a quick note onto the end of today's entry, without going through the main
+/// window
+pub struct QuickCapture {
+    capture_content: UpgradedContent,
+}
+
+impl Windowable<QuickCaptureMessage> for QuickCapture {
+    fn title(&self) -> String {
+        "Quick Capture".to_string()
+    }
+
+    fn view<'a>(&'a self, _state: &'a SharedAppState) -> iced::Element<'a, QuickCaptureMessage> {
+        let capture_editor = widget::text_editor(self.capture_content.raw_content())
+            .placeholder("Add a note to today's entry")
+            .on_action(QuickCaptureMessage::EditCapture)
+            .height(Length::Fill);
+
+        let append_button = button(Text::new("Append to Today")).on_press_maybe(
+            (!self.capture_text().is_empty()).then_some(QuickCaptureMessage::AppendToToday),
+        );
+        let close_button = button(Text::new("Close")).on_press(QuickCaptureMessage::Close);
+
+        let bottom_buttons = row![
+            Space::new().width(Length::Fill),
+            append_button,
+            close_button
+        ];
+
+        column![capture_editor, bottom_buttons].into()
+    }
+
+    fn update(
+        &mut self,
+        state: &mut SharedAppState,
+        message: QuickCaptureMessage,
+    ) -> Task<QuickCaptureMessage> {
+        match message {
+            QuickCaptureMessage::KeyEvent(keyboard_action) => match keyboard_action {
+                KeyboardAction::Content(text_edit) => {
+                    self.content_perform(state, text_edit.to_content_action());
+                }
+                KeyboardAction::Save => {
+                    return self.update(state, QuickCaptureMessage::AppendToToday);
+                }
+                KeyboardAction::Debug => {}
+                KeyboardAction::OpenPreferences => {}
+                KeyboardAction::NewTask => {}
+                KeyboardAction::SearchCursorWord => {}
+                KeyboardAction::Paste => {}
+                KeyboardAction::RevertEntry => {}
+                KeyboardAction::JumpToDate => {}
+                KeyboardAction::SwitchFocusRegion => {}
+                KeyboardAction::ToggleFocusMode => {}
+                KeyboardAction::TaskNavigation(_task_navigation) => {}
+                KeyboardAction::Unbound(_unbound_key) => {}
+            },
+            QuickCaptureMessage::EditCapture(action) => {
+                self.capture_content
+                    .perform(ContentAction::Standard(action));
+            }
+            QuickCaptureMessage::AppendToToday => {
+                let capture_text = self.capture_text();
+
+                if capture_text.is_empty() {
+                    return Task::none();
+                }
+
+                state
+                    .upstream_actions
+                    .push(UpstreamAction::AppendToToday(capture_text));
+
+                self.capture_content = UpgradedContent::default();
+
+                state
+                    .upstream_actions
+                    .push(UpstreamAction::CloseWindow(WindowType::QuickCapture));
+            }
+            QuickCaptureMessage::Close => {
+                state
+                    .upstream_actions
+                    .push(UpstreamAction::CloseWindow(WindowType::QuickCapture));
+            }
+        }
+
+        Task::none()
+    }
+
+    fn content_perform(&mut self, _state: &mut SharedAppState, action: ContentAction) {
+        self.capture_content.perform(action);
+    }
+}
+
+impl QuickCapture {
+    /// the note being captured, without any surrounding whitespace
+    fn capture_text(&self) -> String {
+        self.capture_content.text().trim().to_string()
+    }
+}
@@ -6,8 +6,8 @@ use crate::{
     tasks::{
         TaskType,
         template_tasks::{
-            Frequency, FrequencyType, MultiBinaryTaskTemplate, StandardTaskTemplate, TemplateData,
-            TemplateTask,
+            CounterTaskTemplate, Frequency, FrequencyType, MultiBinaryTaskTemplate,
+            StandardTaskTemplate, TemplateData, TemplateTask,
         },
     },
     utils::month_day::{DispMonth, MonthDay},
@@ -36,10 +36,12 @@ pub enum TaskCreatorMessage {
     IncreasedMultiBinCount,
     DecreasedMultiBinCount,
     EditedMultiBinName((usize, Action)),
+    EditedCounterTarget(Action),
     SelectedMonth(DispMonth),
     SelectedDay(u32),
     Cancel,
     CreateTask,
+    Reset,
 }
 
 #[derive(Debug)]
@@ -47,6 +49,7 @@ pub enum TaskCreatorMessage {
 pub enum ActiveContent {
     Name,
     MultiBinaryComponent(usize),
+    CounterTarget,
 }
 
 #[derive(Debug)]
@@ -60,6 +63,7 @@ pub struct TaskCreator {
     freq_day: u32,
     freq_month: DispMonth,
     multi_binary_contents: Vec<UpgradedContent>,
+    counter_target_content: UpgradedContent,
 }
 
 impl Default for TaskCreator {
@@ -74,6 +78,7 @@ impl Default for TaskCreator {
             freq_day: 1,
             freq_month: DispMonth::January,
             multi_binary_contents: vec![UpgradedContent::default(), UpgradedContent::default()],
+            counter_target_content: UpgradedContent::default(),
         }
     }
 }
@@ -81,15 +86,17 @@ impl Default for TaskCreator {
 impl TaskCreator {
     /// returns true if all the information required to create a task is present and false if any information is missing
     pub fn is_valid_task(&self, state: &SharedAppState) -> bool {
-        let name_text = self.name_content.text();
+        let name_text = self.task_name();
 
-        if name_text.is_empty() {
+        // the name is used in the task's filename, so any newline that made it past the editor restriction must not be
+        // saved into it
+        if name_text.is_empty() || name_text.contains(['\n', '\r']) {
             return false;
         }
 
         match self.selected_frequency {
             FrequencyType::Daily => {}
-            FrequencyType::Weekly => {
+            FrequencyType::Weekly | FrequencyType::Biweekly => {
                 let selected_day_count: u32 = self
                     .freq_weekmap
                     .iter()
@@ -124,6 +131,21 @@ impl TaskCreator {
 
         true
     }
+
+    /// the name of the task being created, without any surrounding whitespace
+    fn task_name(&self) -> String {
+        self.name_content.text().trim().to_string()
+    }
+
+    /// the count that completes the counter being created. an empty target or a target of 0 means there is no target
+    fn counter_target(&self) -> Option<u32> {
+        self.counter_target_content
+            .text()
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|target| *target > 0)
+    }
 }
 
 impl Windowable<TaskCreatorMessage> for TaskCreator {
@@ -152,7 +174,19 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
             TaskCreatorMessage::SelectedTask,
         );
 
-        let type_selection = column![task_select_message, radio_standard, radio_multi_binary];
+        let radio_counter = radio(
+            "Counter, for things done a number of times",
+            TaskType::Counter,
+            (self.selected_task_type == TaskType::Counter).then_some(TaskType::Counter),
+            TaskCreatorMessage::SelectedTask,
+        );
+
+        let type_selection = column![
+            task_select_message,
+            radio_standard,
+            radio_multi_binary,
+            radio_counter
+        ];
 
         let type_config = {
             let task_specifc = match self.selected_task_type {
@@ -187,6 +221,15 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
 
                     row![subtasks_scrollable, inc_dec]
                 }
+                TaskType::Counter => {
+                    let target_editor =
+                        widget::text_editor(self.counter_target_content.raw_content())
+                            .placeholder("None")
+                            .on_action(TaskCreatorMessage::EditedCounterTarget)
+                            .width(80);
+
+                    row![Text::new("Daily target: "), target_editor]
+                }
             };
 
             column![task_specifc]
@@ -212,7 +255,8 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
             (self.selected_frequency == FrequencyType::Weekly).then_some(FrequencyType::Weekly),
             TaskCreatorMessage::SelectedFrequency,
         );
-        let freq_weekly = if self.selected_frequency == FrequencyType::Weekly {
+        // the weekly and biweekly frequencies share the same schedule of weekdays
+        let weekday_schedule = || {
             let weekday_width = 2;
             let weekdays = row![
                 Text::new("Sun"),
@@ -240,17 +284,37 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
                 weekmap = weekmap.push(Space::new().width(6));
             }
 
-            let schedule = column![weekdays, weekmap];
+            column![weekdays, weekmap]
+        };
 
+        let freq_weekly = if self.selected_frequency == FrequencyType::Weekly {
             column![
                 radio_freq_weekly,
                 Text::new("A task that happens on a weekly basis, with a defined schedule:"),
-                schedule,
+                weekday_schedule(),
             ]
         } else {
             column![radio_freq_weekly]
         };
 
+        let radio_freq_biweekly = radio(
+            "Biweekly",
+            FrequencyType::Biweekly,
+            (self.selected_frequency == FrequencyType::Biweekly).then_some(FrequencyType::Biweekly),
+            TaskCreatorMessage::SelectedFrequency,
+        );
+        let freq_biweekly = if self.selected_frequency == FrequencyType::Biweekly {
+            column![
+                radio_freq_biweekly,
+                Text::new(
+                    "A task that happens every other week, starting this week, with a defined schedule:"
+                ),
+                weekday_schedule(),
+            ]
+        } else {
+            column![radio_freq_biweekly]
+        };
+
         let radio_freq_monthly = radio(
             "Monthly",
             FrequencyType::Monthly,
@@ -298,7 +362,7 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
         };
 
         let radio_freq_dated = radio(
-            "Fixed Date",
+            "Yearly",
             FrequencyType::Dated,
             (self.selected_frequency == FrequencyType::Dated).then_some(FrequencyType::Dated),
             TaskCreatorMessage::SelectedFrequency,
@@ -321,7 +385,7 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
 
             column![
                 radio_freq_dated,
-                Text::new("A task that happens on a specific day of the year:"),
+                Text::new("A task that happens once a year, on a specific day:"),
                 month_day_select,
             ]
         } else {
@@ -332,6 +396,7 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
             frequency_select_message,
             freq_daily,
             freq_weekly,
+            freq_biweekly,
             freq_monthly,
             freq_dated,
         ];
@@ -373,6 +438,15 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
                         }
                     }
                     KeyboardAction::Debug => {}
+                    KeyboardAction::OpenPreferences => {}
+                    KeyboardAction::NewTask => {}
+                    KeyboardAction::SearchCursorWord => {}
+                    KeyboardAction::Paste => {}
+                    KeyboardAction::RevertEntry => {}
+                    KeyboardAction::JumpToDate => {}
+                    KeyboardAction::SwitchFocusRegion => {}
+                    KeyboardAction::ToggleFocusMode => {}
+                    KeyboardAction::TaskNavigation(_task_navigation) => {}
                     KeyboardAction::Unbound(_unbound_key) => {}
                 };
             }
@@ -420,6 +494,15 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
                 self.multi_binary_contents[index]
                     .perform(ContentAction::Restricted((Restriction::NoNewlines, action)));
             }
+            TaskCreatorMessage::EditedCounterTarget(action) => {
+                self.active_content = Some(ActiveContent::CounterTarget);
+
+                self.counter_target_content
+                    .perform(ContentAction::Restricted((
+                        Restriction::NumbersOnly,
+                        action,
+                    )));
+            }
             TaskCreatorMessage::SelectedMonth(month) => {
                 self.freq_month = month;
 
@@ -442,13 +525,18 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
             TaskCreatorMessage::CreateTask => {
                 self.active_content = None;
 
-                let name_text = self.name_content.text();
+                if !self.is_valid_task(state) {
+                    return Task::none();
+                }
+
+                let name_text = self.task_name();
 
                 let active_date = state.global_store.current_date();
 
                 let frequency = match self.selected_frequency {
                     FrequencyType::Daily => Frequency::Daily,
                     FrequencyType::Weekly => Frequency::Weekly(self.freq_weekmap),
+                    FrequencyType::Biweekly => Frequency::Biweekly(self.freq_weekmap),
                     FrequencyType::Monthly => Frequency::Monthly(self.freq_monthmap),
                     FrequencyType::Dated => {
                         Frequency::Dated(MonthDay::new(self.freq_month, self.freq_day))
@@ -466,6 +554,9 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
 
                         TemplateData::MultiBinary(MultiBinaryTaskTemplate::new(subtask_names))
                     }
+                    TaskType::Counter => {
+                        TemplateData::Counter(CounterTaskTemplate::new(self.counter_target()))
+                    }
                 };
 
                 let template_task = TemplateTask::new(name_text, active_date, frequency, template);
@@ -479,6 +570,9 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
 
                 *self = Self::default();
             }
+            TaskCreatorMessage::Reset => {
+                *self = Self::default();
+            }
         }
 
         Task::none()
@@ -491,7 +585,63 @@ impl Windowable<TaskCreatorMessage> for TaskCreator {
                 ActiveContent::MultiBinaryComponent(component_index) => {
                     self.multi_binary_contents[*component_index].perform(action);
                 }
+                ActiveContent::CounterTarget => self.counter_target_content.perform(action),
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::with_test_preferences;
+    use crate::store::journal_storage::MemoryStorage;
+
+    fn creator_named(name: &str) -> TaskCreator {
+        TaskCreator {
+            name_content: UpgradedContent::with_text(name),
+            ..TaskCreator::default()
+        }
+    }
+
+    #[test]
+    fn trailing_newlines_are_trimmed_from_the_name() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let state = SharedAppState::with_storage(Box::new(MemoryStorage::default()));
+                let task_creator = creator_named("Read a chapter\n");
+
+                assert_eq!(task_creator.task_name(), "Read a chapter");
+                assert!(task_creator.is_valid_task(&state));
+            },
+        );
+    }
+
+    #[test]
+    fn names_with_inner_newlines_are_invalid() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let state = SharedAppState::with_storage(Box::new(MemoryStorage::default()));
+                let task_creator = creator_named("Read a\nchapter");
+
+                assert!(!task_creator.is_valid_task(&state));
+            },
+        );
+    }
+
+    #[test]
+    fn names_of_only_whitespace_are_invalid() {
+        with_test_preferences(
+            |_preferences| {},
+            || {
+                let state = SharedAppState::with_storage(Box::new(MemoryStorage::default()));
+                let task_creator = creator_named(" \n");
+
+                assert_eq!(task_creator.task_name(), "");
+                assert!(!task_creator.is_valid_task(&state));
+            },
+        );
+    }
+}
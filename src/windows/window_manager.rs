@@ -1,7 +1,8 @@
 use crate::{
     SharedAppState,
+    config::preferences,
     content::ContentAction,
-    ui::layout::{MEDIUM_WINDOW_SIZE, SMALL_WINDOW_SIZE, WINDOW_SIZE},
+    ui::layout::{MEDIUM_WINDOW_SIZE, QUICK_CAPTURE_WINDOW_SIZE, SMALL_WINDOW_SIZE, WINDOW_SIZE},
 };
 
 use iced::{Element, Task, window};
@@ -14,6 +15,8 @@ pub enum WindowType {
     FileExport,
     TaskCreator,
     Preferences,
+    Diagnostics,
+    QuickCapture,
 }
 
 impl WindowType {
@@ -22,6 +25,8 @@ impl WindowType {
         match self {
             WindowType::Main => window::Settings {
                 size: WINDOW_SIZE,
+                // closing is held back until any unsaved changes are dealt with
+                exit_on_close_request: false,
                 ..Default::default()
             },
             WindowType::FileImport => window::Settings {
@@ -48,6 +53,31 @@ impl WindowType {
                 position: window::Position::Centered,
                 ..Default::default()
             },
+            WindowType::Diagnostics => window::Settings {
+                size: MEDIUM_WINDOW_SIZE,
+                position: window::Position::Centered,
+                ..Default::default()
+            },
+            WindowType::QuickCapture => window::Settings {
+                size: QUICK_CAPTURE_WINDOW_SIZE,
+                position: window::Position::Centered,
+                level: window::Level::AlwaysOnTop,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// true if opening a window of this type while one is already open starts the window over, rather than only
+    /// focusing it
+    pub fn resets_on_reopen(&self) -> bool {
+        match self {
+            WindowType::TaskCreator => preferences().general.reset_task_creator_on_reopen,
+            WindowType::Main
+            | WindowType::FileImport
+            | WindowType::FileExport
+            | WindowType::Preferences
+            | WindowType::Diagnostics
+            | WindowType::QuickCapture => false,
         }
     }
 }